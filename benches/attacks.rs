@@ -0,0 +1,66 @@
+/* Mesure de la détection des attaques : parcours du tableau (`ChessPosition::is_attacked`)
+face aux masques d'occupation (`Bitboards::is_attacked`), puis perft qui s'appuie sur ces derniers.
+
+`cargo bench --bench attacks` ; chaque durée est la meilleure de trois passes. */
+
+use fen_parser::{ChessPosition, Color, RandomPositionGenerator, Square, SquareSet};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const PASSES: usize = 3;
+
+fn best(mut run: impl FnMut()) -> Duration {
+    (0..PASSES)
+        .map(|_| {
+            let started = Instant::now();
+            run();
+            started.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let mut generator = RandomPositionGenerator::new(2024);
+    let positions: Vec<ChessPosition> = (0..2_000).map(|_| generator.random_plausible()).collect();
+    let boards: Vec<_> = positions.iter().map(ChessPosition::bitboards).collect();
+    let squares: Vec<Square> = SquareSet::full().iter().collect();
+    let queries = (positions.len() * squares.len() * 2) as f64;
+
+    let array = best(|| {
+        for position in &positions {
+            for &square in &squares {
+                for color in [Color::White, Color::Black] {
+                    black_box(position.is_attacked(black_box(square), color));
+                }
+            }
+        }
+    });
+    let masks = best(|| {
+        for boards in &boards {
+            for &square in &squares {
+                for color in [Color::White, Color::Black] {
+                    black_box(boards.is_attacked(black_box(square), color));
+                }
+            }
+        }
+    });
+    for (name, elapsed) in [("array scan", array), ("bitboards", masks)] {
+        println!(
+            "{:<12} {:>8.1} ms  {:>6.1} ns/query",
+            name,
+            elapsed.as_secs_f64() * 1e3,
+            elapsed.as_secs_f64() * 1e9 / queries
+        );
+    }
+    println!("speedup: {:.2}x", array.as_secs_f64() / masks.as_secs_f64());
+
+    let start = ChessPosition::default();
+    let perft = best(|| {
+        black_box(start.perft(4));
+    });
+    println!(
+        "perft(4) from the start position: {:.1} ms",
+        perft.as_secs_f64() * 1e3
+    );
+}
@@ -0,0 +1,54 @@
+/* Mesure de la lecture parallèle selon le nombre de fils.
+
+Le corpus synthétique compte un million de FEN (`FEN_BENCH_COUNT` pour le changer) ; la durée retenue est la meilleure de trois passes : `cargo bench --bench parallel --features parallel`. */
+
+use fen_parser::parallel::parse_fens_par_with;
+use fen_parser::RandomPositionGenerator;
+use std::env;
+use std::hint::black_box;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const PASSES: usize = 3;
+
+fn main() {
+    let count = env::var("FEN_BENCH_COUNT")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(1_000_000);
+    let mut generator = RandomPositionGenerator::new(2024);
+    let corpus: Vec<String> = (0..count)
+        .map(|_| generator.random_plausible().to_fen())
+        .collect();
+    let lines: Vec<&str> = corpus.iter().map(String::as_str).collect();
+
+    let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+    let mut threads = vec![1];
+    while threads[threads.len() - 1] * 2 <= cores {
+        threads.push(threads[threads.len() - 1] * 2);
+    }
+    if threads[threads.len() - 1] != cores {
+        threads.push(cores);
+    }
+
+    let mut single = Duration::ZERO;
+    for &count in &threads {
+        let elapsed = (0..PASSES)
+            .map(|_| {
+                let started = Instant::now();
+                black_box(parse_fens_par_with(black_box(&lines), count));
+                started.elapsed()
+            })
+            .min()
+            .unwrap_or_default();
+        if count == 1 {
+            single = elapsed;
+        }
+        println!(
+            "{:>3} threads {:>8.1} ms  speedup {:>5.2}x",
+            count,
+            elapsed.as_secs_f64() * 1e3,
+            single.as_secs_f64() / elapsed.as_secs_f64(),
+        );
+    }
+}
@@ -0,0 +1,55 @@
+/* Mesure du débit de `parse_fen` (voie rapide) face au parseur nom de référence.
+
+Le corpus compte un million de FEN générées (`FEN_BENCH_COUNT` pour le changer), moitié positions vraisemblables, moitié plateaux quelconques. Sans criterion dans l'environnement de construction, la mesure se fait avec `Instant` sur la meilleure de plusieurs passes : `cargo bench --bench parse`. */
+
+use fen_parser::{parse_fen, parse_fen_nom, ChessPosition, FenError, RandomPositionGenerator};
+use std::env;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const PASSES: usize = 3;
+
+/* Meilleure durée de lecture du corpus sur plusieurs passes */
+fn measure(corpus: &[String], parse: fn(&str) -> Result<ChessPosition, FenError>) -> Duration {
+    (0..PASSES)
+        .map(|_| {
+            let started = Instant::now();
+            for fen in corpus {
+                black_box(parse(black_box(fen))).unwrap();
+            }
+            started.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() {
+    let count = env::var("FEN_BENCH_COUNT")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(1_000_000);
+    let mut generator = RandomPositionGenerator::new(2024);
+    let corpus: Vec<String> = (0..count)
+        .map(|index| {
+            let position = if index % 2 == 0 {
+                generator.random_plausible()
+            } else {
+                generator.random_board()
+            };
+            position.to_fen()
+        })
+        .collect();
+
+    let nom = measure(&corpus, parse_fen_nom);
+    let fast = measure(&corpus, parse_fen);
+    for (name, elapsed) in [("nom parser", nom), ("fast path", fast)] {
+        println!(
+            "{:<12} {:>8.1} ms  {:>7.1} ns/FEN  {:>6.2} M FEN/s",
+            name,
+            elapsed.as_secs_f64() * 1e3,
+            elapsed.as_secs_f64() * 1e9 / count as f64,
+            count as f64 / elapsed.as_secs_f64() / 1e6,
+        );
+    }
+    println!("speedup: {:.2}x", nom.as_secs_f64() / fast.as_secs_f64());
+}
@@ -0,0 +1,83 @@
+/* Suite de mesures de référence : lecture de la position initiale, lecture du corpus mixte de 10 000 FEN, écriture FEN et rendu du plateau, en positions par seconde.
+
+Chaque mesure porte dans son nom le débit relevé lors de son introduction (machine à un cœur, `cargo bench`), et la ligne affiche le rapport à cette référence : un travail sur les performances se juge ainsi sur des chiffres avant/après concrets. Le corpus vient de `fen_parser::test_support` (`benches/data/mixed.fen`, repris en boucle). Sans criterion dans l'environnement de construction, la mesure se fait avec `Instant` sur la meilleure de plusieurs passes : `cargo bench --bench suite --features test-support`. */
+
+use fen_parser::test_support::mixed_corpus;
+use fen_parser::{parse_fen, ChessPosition, DisplayOptions};
+use std::hint::black_box;
+use std::time::Instant;
+
+const PASSES: usize = 3;
+const CORPUS_SIZE: usize = 10_000;
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/* Meilleur débit sur plusieurs passes, `run` traitant `count` positions à chaque appel */
+fn throughput(count: usize, mut run: impl FnMut()) -> f64 {
+    (0..PASSES)
+        .map(|_| {
+            let started = Instant::now();
+            run();
+            count as f64 / started.elapsed().as_secs_f64()
+        })
+        .fold(0.0, f64::max)
+}
+
+/* Affiche le débit mesuré ; `baseline` est le débit de référence en positions par seconde */
+fn report(name: &str, baseline: f64, measured: f64) {
+    let name = format!("{} (baseline {:.3} M/s)", name, baseline / 1e6);
+    println!(
+        "{:<44} {:>8.3} M pos/s  {:>5.2}x baseline",
+        name,
+        measured / 1e6,
+        measured / baseline
+    );
+}
+
+fn main() {
+    let corpus = mixed_corpus(CORPUS_SIZE);
+    let positions: Vec<ChessPosition> = corpus
+        .iter()
+        .filter_map(|fen| parse_fen(fen).ok())
+        .collect();
+    let options = DisplayOptions::default();
+
+    report(
+        "parse/start_position",
+        5_000_000.0,
+        throughput(CORPUS_SIZE, || {
+            for _ in 0..CORPUS_SIZE {
+                let _ = black_box(parse_fen(black_box(START)));
+            }
+        }),
+    );
+
+    report(
+        "parse/mixed_10k",
+        1_350_000.0,
+        throughput(corpus.len(), || {
+            for fen in &corpus {
+                let _ = black_box(parse_fen(black_box(fen)));
+            }
+        }),
+    );
+
+    report(
+        "to_fen/mixed",
+        750_000.0,
+        throughput(positions.len(), || {
+            for position in &positions {
+                black_box(black_box(position).to_fen());
+            }
+        }),
+    );
+
+    report(
+        "render_board/mixed",
+        85_000.0,
+        throughput(positions.len(), || {
+            for position in &positions {
+                black_box(black_box(position).render_board(&options));
+            }
+        }),
+    );
+}
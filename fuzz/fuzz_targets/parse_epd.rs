@@ -0,0 +1,16 @@
+/* Cible libFuzzer : la lecture d'un enregistrement EPD ne doit jamais paniquer. */
+
+#![no_main]
+
+use fen_parser::EpdRecord;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(record) = EpdRecord::parse(input) {
+        let _ = record.to_fen();
+        let _ = record.operation("id");
+    }
+});
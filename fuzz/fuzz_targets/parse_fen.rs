@@ -0,0 +1,23 @@
+/* Cible libFuzzer : `parse_fen` et `parse_fen_all_errors` ne doivent jamais paniquer, et l'extrait
+ * d'une erreur doit correspondre à son intervalle dans l'entrée. */
+
+#![no_main]
+
+use fen_parser::{parse_fen, parse_fen_all_errors};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Err(error) = parse_fen(input) {
+        if let Some(span) = error.span.clone() {
+            assert_eq!(&input[span], error.snippet);
+        }
+        let _ = error.to_string();
+        let _ = error.diagnostic(input);
+    }
+    if let Err(errors) = parse_fen_all_errors(input) {
+        assert!(!errors.is_empty());
+    }
+});
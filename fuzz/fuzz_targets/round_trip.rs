@@ -0,0 +1,18 @@
+/* Cible libFuzzer : toute FEN acceptée se relit à l'identique une fois réécrite,
+ * `parse(to_fen(parse(x))) == parse(x)`. */
+
+#![no_main]
+
+use fen_parser::parse_fen;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(position) = parse_fen(input) else {
+        return;
+    };
+    let fen = position.to_fen();
+    assert_eq!(parse_fen(&fen).as_ref(), Ok(&position), "{}", fen);
+});
@@ -0,0 +1,48 @@
+/* Interface C de fen_parser (fonctionnalité `capi`).
+ *
+ * Les chaînes sont en UTF-8 et terminées par un zéro ; aucun pointeur ne doit être nul,
+ * sauf `buf` quand `len` vaut 0. Les fonctions ne conservent aucun pointeur reçu. */
+
+#ifndef FEN_PARSER_H
+#define FEN_PARSER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define FEN_OK 0
+#define FEN_ERR_NULL_POINTER -1
+#define FEN_ERR_INVALID_UTF8 -2
+#define FEN_ERR_INVALID_POSITION -3
+#define FEN_ERR_PIECE_PLACEMENT 1
+#define FEN_ERR_ACTIVE_COLOR 2
+#define FEN_ERR_CASTLING_RIGHTS 3
+#define FEN_ERR_EN_PASSANT 4
+#define FEN_ERR_HALFMOVE_CLOCK 5
+#define FEN_ERR_FULLMOVE_NUMBER 6
+#define FEN_ERR_MISSING_FIELD 7
+#define FEN_ERR_OTHER 8
+
+#define FEN_NO_EN_PASSANT 255
+
+/* board : a1 (0) à h8 (63) ; 0 vide, 1-6 roi, dame, tour, fou, cavalier, pion blancs,
+ * 7-12 les mêmes pièces noires. castling : K = 1, Q = 2, k = 4, q = 8. */
+typedef struct FenPositionC {
+    uint8_t board[64];
+    uint8_t active_color;
+    uint8_t castling;
+    uint8_t en_passant;
+    uint32_t halfmove_clock;
+    uint32_t fullmove_number;
+} FenPositionC;
+
+/* Lit une FEN ; renvoie FEN_OK ou un code d'erreur, `out` n'étant écrit qu'en cas de succès */
+int32_t fen_parse(const char *fen, FenPositionC *out);
+
+/* Écrit la FEN dans `buf` (tronquée, toujours terminée par un zéro si len > 0) ;
+ * renvoie la longueur complète de la FEN, ou un code négatif */
+int32_t fen_to_string(const FenPositionC *position, char *buf, size_t len);
+
+/* Message statique décrivant un code de retour */
+const char *fen_error_message(int32_t code);
+
+#endif
@@ -0,0 +1,835 @@
+/* Analyse statique d'une position d'échecs.
+
+Ce module regroupe les mesures calculées directement à partir du placement des pièces, sans générer de coups. */
+
+use crate::types::{ChessPosition, Color, File, Piece, PieceKind, Rank, Square, SquareSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "eval")]
+pub use crate::pst::pst_eval;
+
+/* Nombre de pièces au-delà duquel `tablebase_key` renonce, comme les tables Syzygy */
+pub const TABLEBASE_PIECE_LIMIT: u32 = 7;
+
+/* Nombre de pièces de chaque type pour chaque camp */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MaterialCount {
+    /* Comptes indexés dans l'ordre de `PieceKind::ALL` */
+    white: [u32; 6],
+    black: [u32; 6],
+}
+
+impl MaterialCount {
+    /* Décompte les pièces présentes sur le plateau */
+    pub fn of(position: &ChessPosition) -> Self {
+        let mut material = MaterialCount::default();
+        for piece in position.pieces.iter().flatten().flatten() {
+            let index = piece.kind.index();
+            match piece.color {
+                Color::White => material.white[index] += 1,
+                Color::Black => material.black[index] += 1,
+            }
+        }
+        material
+    }
+
+    /* Nombre de pièces d'un type pour un camp */
+    pub fn count(&self, color: Color, kind: PieceKind) -> u32 {
+        let index = kind.index();
+        match color {
+            Color::White => self.white[index],
+            Color::Black => self.black[index],
+        }
+    }
+
+    /* Valeur totale du matériel d'un camp, en pions */
+    pub fn value(&self, color: Color) -> u32 {
+        PieceKind::ALL
+            .iter()
+            .map(|&kind| self.count(color, kind) * kind.value())
+            .sum()
+    }
+
+    /* Déséquilibre matériel en pions : positif si les blancs sont en avance */
+    pub fn imbalance(&self) -> i32 {
+        self.value(Color::White) as i32 - self.value(Color::Black) as i32
+    }
+
+    /* Nombre total de pièces sur le plateau, rois compris */
+    pub fn total(&self) -> u32 {
+        self.white.iter().chain(self.black.iter()).sum()
+    }
+
+    /* Signature matérielle, pièces blanches puis noires du roi au pion : `KRPPvKR` */
+    pub fn signature(&self) -> String {
+        let mut signature = self.side_signature(Color::White);
+        signature.push('v');
+        signature.push_str(&self.side_signature(Color::Black));
+        signature
+    }
+
+    /* Pièces d'un camp du roi au pion : `KRPP` */
+    fn side_signature(&self, color: Color) -> String {
+        let mut side = String::new();
+        for kind in PieceKind::ALL {
+            let letter = Piece {
+                color: Color::White,
+                kind,
+            }
+            .to_fen_char();
+            for _ in 0..self.count(color, kind) {
+                side.push(letter);
+            }
+        }
+        side
+    }
+}
+
+/* Camp d'une signature : nombre de pièces de chaque type (ordre de `PieceKind::ALL`) et
+ * présence d'un `*`, qui admet des pions en plus de ceux écrits */
+pub(crate) fn parse_signature_side(side: &str) -> Option<([u32; 6], bool)> {
+    let mut counts = [0; 6];
+    let mut wildcard = false;
+    for letter in side.chars() {
+        if letter == '*' {
+            wildcard = true;
+            continue;
+        }
+        let kind = PieceKind::ALL.into_iter().find(|&kind| {
+            Piece {
+                color: Color::White,
+                kind,
+            }
+            .to_fen_char()
+                == letter
+        })?;
+        counts[kind.index()] += 1;
+    }
+    (counts[PieceKind::King.index()] > 0).then_some((counts, wildcard))
+}
+
+impl ChessPosition {
+    /* Décompte du matériel de la position */
+    pub fn material(&self) -> MaterialCount {
+        MaterialCount::of(self)
+    }
+
+    /* Signature matérielle, le camp le plus fort (en valeur) en premier, les blancs à
+     * égalité : `KQvKR`, `KRPPvKRP` */
+    pub fn material_signature(&self) -> String {
+        let material = self.material();
+        let (first, second) = if material.value(Color::Black) > material.value(Color::White) {
+            (Color::Black, Color::White)
+        } else {
+            (Color::White, Color::Black)
+        };
+        let mut signature = material.side_signature(first);
+        signature.push('v');
+        signature.push_str(&material.side_signature(second));
+        signature
+    }
+
+    /* Nombre de pièces sur le plateau, rois et pions compris */
+    pub fn piece_count(&self) -> u32 {
+        self.material().total()
+    }
+
+    /* Clé de table de finales à la manière de Syzygy (`KRvKN`, `KBNvK`), ou `None` au-delà de
+     * `TABLEBASE_PIECE_LIMIT` pièces */
+    pub fn tablebase_key(&self) -> Option<String> {
+        self.tablebase_key_with_limit(TABLEBASE_PIECE_LIMIT)
+    }
+
+    /* Clé de table de finales, ou `None` au-delà de `limit` pièces. Chaque camp est écrit du
+     * roi au pion ; le camp qui a le plus de pièces vient en premier, puis, à nombre égal,
+     * celui dont les pièces sont les plus fortes, si bien que la clé ne dépend pas de la
+     * couleur */
+    pub fn tablebase_key_with_limit(&self, limit: u32) -> Option<String> {
+        if self.piece_count() > limit {
+            return None;
+        }
+        let material = self.material();
+        let white = material.side_signature(Color::White);
+        let black = material.side_signature(Color::Black);
+        let strength = |side: &str| {
+            let ranks: Vec<usize> = side
+                .chars()
+                .filter_map(|letter| "KQRBNP".find(letter))
+                .collect();
+            (core::cmp::Reverse(side.len()), ranks)
+        };
+        let (first, second) = if strength(&black) < strength(&white) {
+            (black, white)
+        } else {
+            (white, black)
+        };
+        Some(first + "v" + &second)
+    }
+
+    /* Vrai si le matériel correspond à la signature, dans un sens ou dans l'autre. Un `*`
+     * dans un camp admet un nombre quelconque de pions en plus de ceux écrits : `KRP*vKR`
+     * demande au moins un pion. Une signature mal formée ne correspond à rien. */
+    pub fn matches_signature(&self, signature: &str) -> bool {
+        let Some((first, second)) = signature.split_once('v') else {
+            return false;
+        };
+        let (Some(first), Some(second)) =
+            (parse_signature_side(first), parse_signature_side(second))
+        else {
+            return false;
+        };
+        let material = self.material();
+        (material.side_matches(Color::White, first) && material.side_matches(Color::Black, second))
+            || (material.side_matches(Color::Black, first)
+                && material.side_matches(Color::White, second))
+    }
+}
+
+impl MaterialCount {
+    /* Vrai si le camp a exactement les pièces d'un côté de signature lu par
+     * `parse_signature_side`, des pions en plus étant admis après un `*` */
+    pub(crate) fn side_matches(&self, color: Color, (counts, wildcard): ([u32; 6], bool)) -> bool {
+        PieceKind::ALL.iter().zip(counts).all(|(&kind, expected)| {
+            let found = self.count(color, kind);
+            if kind == PieceKind::Pawn && wildcard {
+                found >= expected
+            } else {
+                found == expected
+            }
+        })
+    }
+}
+
+/* Pions d'un camp sur chaque colonne, de a à h */
+fn pawns_per_file(position: &ChessPosition, color: Color) -> [u8; 8] {
+    let pawn = Some(Piece {
+        color,
+        kind: PieceKind::Pawn,
+    });
+    let mut counts = [0; 8];
+    for (count, file) in counts.iter_mut().zip(position.files()) {
+        *count = file.iter().filter(|&&square| square == pawn).count() as u8;
+    }
+    counts
+}
+
+/* Colonnes portant au moins deux pions du camp, avec leur nombre de pions (3 pour des
+ * pions triplés) */
+pub fn doubled_pawns(position: &ChessPosition, color: Color) -> Vec<(File, u8)> {
+    File::ALL
+        .into_iter()
+        .zip(pawns_per_file(position, color))
+        .filter(|&(_, count)| count >= 2)
+        .collect()
+}
+
+/* Nombre de pions doublés d'un camp : les pions en surnombre sur leur colonne, soit un
+ * pour des pions doublés et deux pour des pions triplés */
+pub fn doubled_pawn_count(position: &ChessPosition, color: Color) -> u32 {
+    doubled_pawns(position, color)
+        .iter()
+        .map(|&(_, count)| u32::from(count) - 1)
+        .sum()
+}
+
+/* Cases des colonnes voisines d'une colonne : une seule pour les colonnes a et h */
+fn adjacent_files(file: File) -> SquareSet {
+    let index = file.index();
+    [index.checked_sub(1), Some(index + 1)]
+        .into_iter()
+        .flatten()
+        .filter_map(|index| File::ALL.get(index))
+        .fold(SquareSet::empty(), |set, &file| set | SquareSet::file(file))
+}
+
+/* Vrai si la case porte un pion sans pion du même camp sur les colonnes voisines, quelle
+ * que soit la rangée de ces pions */
+pub fn is_isolated(position: &ChessPosition, square: Square) -> bool {
+    match position.piece_at(square) {
+        Some(piece) if piece.kind == PieceKind::Pawn => {
+            let pawns = position.bitboard(piece.color, PieceKind::Pawn);
+            (pawns & adjacent_files(square.file())).is_empty()
+        }
+        _ => false,
+    }
+}
+
+/* Pions isolés d'un camp */
+pub fn isolated_pawns(position: &ChessPosition, color: Color) -> SquareSet {
+    let pawns = position.bitboard(color, PieceKind::Pawn);
+    pawns
+        .into_iter()
+        .filter(|&square| (pawns & adjacent_files(square.file())).is_empty())
+        .collect()
+}
+
+/* Rangées situées devant une case du point de vue d'un camp : au-dessus pour les blancs */
+fn ranks_ahead(square: Square, color: Color) -> SquareSet {
+    let rank = square.rank().index() as u32;
+    SquareSet(match color {
+        Color::White => u64::MAX.checked_shl(8 * (rank + 1)).unwrap_or(0),
+        Color::Black => (1u64 << (8 * rank)) - 1,
+    })
+}
+
+/* Cases devant un pion, sur sa colonne et les colonnes voisines, jusqu'à la rangée de
+ * promotion */
+fn front_span(square: Square, color: Color) -> SquareSet {
+    (SquareSet::file(square.file()) | adjacent_files(square.file())) & ranks_ahead(square, color)
+}
+
+/* Pions passés d'un camp : aucun pion adverse devant eux, sur leur colonne ni sur les
+ * colonnes voisines */
+pub fn passed_pawns(position: &ChessPosition, color: Color) -> SquareSet {
+    let enemy = position.bitboard(color.opposite(), PieceKind::Pawn);
+    position
+        .bitboard(color, PieceKind::Pawn)
+        .into_iter()
+        .filter(|&square| (enemy & front_span(square, color)).is_empty())
+        .collect()
+}
+
+/* Cases attaquées par les pions d'un camp */
+pub fn pawn_attacks(position: &ChessPosition, color: Color) -> SquareSet {
+    let pawns = position.bitboard(color, PieceKind::Pawn);
+    let west = (pawns & !SquareSet::file(File::A)).0;
+    let east = (pawns & !SquareSet::file(File::H)).0;
+    SquareSet(match color {
+        Color::White => (west << 7) | (east << 9),
+        Color::Black => (west >> 9) | (east >> 7),
+    })
+}
+
+/* Pions arriérés d'un camp. Critère retenu : des pions du camp sur les colonnes voisines,
+ * mais tous devant lui, aucun ne pouvant donc avancer pour le soutenir, et case d'arrêt (la
+ * case juste devant le pion) attaquée par un pion adverse. Un pion isolé relève de
+ * `isolated_pawns` et n'est pas compté ici ; un pion sur la rangée de promotion n'a pas de
+ * case d'arrêt et n'est jamais arriéré. */
+pub fn backward_pawns(position: &ChessPosition, color: Color) -> SquareSet {
+    let pawns = position.bitboard(color, PieceKind::Pawn);
+    let enemy_attacks = pawn_attacks(position, color.opposite());
+    pawns
+        .into_iter()
+        .filter(|&square| {
+            let neighbours = pawns & adjacent_files(square.file());
+            let supporters = neighbours & !ranks_ahead(square, color);
+            let stop = Square::from_index(match color {
+                Color::White => square.index() + 8,
+                Color::Black => square.index().wrapping_sub(8),
+            });
+            !neighbours.is_empty()
+                && supporters.is_empty()
+                && stop.is_some_and(|stop| enemy_attacks.contains(stop))
+        })
+        .collect()
+}
+
+/* État d'une colonne selon les pions qui l'occupent */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileState {
+    /* Aucun pion */
+    Open,
+    /* Semi-ouverte pour ce camp : seuls les pions adverses y restent */
+    SemiOpenFor(Color),
+    /* Des pions des deux camps */
+    Closed,
+}
+
+/* État d'une colonne */
+pub fn file_state(position: &ChessPosition, file: File) -> FileState {
+    let column = position.files().nth(file.index()).unwrap_or_default();
+    let has_pawn = |color| {
+        column.contains(&Some(Piece {
+            color,
+            kind: PieceKind::Pawn,
+        }))
+    };
+    match (has_pawn(Color::White), has_pawn(Color::Black)) {
+        (false, false) => FileState::Open,
+        (false, true) => FileState::SemiOpenFor(Color::White),
+        (true, false) => FileState::SemiOpenFor(Color::Black),
+        (true, true) => FileState::Closed,
+    }
+}
+
+/* Colonnes ouvertes, de a à h */
+pub fn open_files(position: &ChessPosition) -> Vec<File> {
+    File::ALL
+        .into_iter()
+        .filter(|&file| file_state(position, file) == FileState::Open)
+        .collect()
+}
+
+/* Colonnes semi-ouvertes pour un camp, de a à h */
+pub fn semi_open_files(position: &ChessPosition, color: Color) -> Vec<File> {
+    File::ALL
+        .into_iter()
+        .filter(|&file| file_state(position, file) == FileState::SemiOpenFor(color))
+        .collect()
+}
+
+/* Fous d'un camp sur cases blanches et sur cases noires */
+pub fn bishops_by_square_color(position: &ChessPosition, color: Color) -> (u8, u8) {
+    let bishops = position.bitboard(color, PieceKind::Bishop);
+    (
+        (bishops & SquareSet::LIGHT_SQUARES).len() as u8,
+        (bishops & SquareSet::DARK_SQUARES).len() as u8,
+    )
+}
+
+/* Vrai si le camp a deux fous de couleurs opposées ; deux fous de même couleur, après une
+ * promotion, ne forment pas la paire */
+pub fn has_bishop_pair(position: &ChessPosition, color: Color) -> bool {
+    let (light, dark) = bishops_by_square_color(position, color);
+    light > 0 && dark > 0
+}
+
+/* Valeur des pièces en centipions ; le roi n'a pas de valeur matérielle */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PieceValues {
+    pub pawn: i32,
+    pub knight: i32,
+    pub bishop: i32,
+    pub rook: i32,
+    pub queen: i32,
+}
+
+/* Barème usuel : 100, 320, 330, 500 et 900 */
+impl Default for PieceValues {
+    fn default() -> Self {
+        PieceValues {
+            pawn: 100,
+            knight: 320,
+            bishop: 330,
+            rook: 500,
+            queen: 900,
+        }
+    }
+}
+
+impl PieceValues {
+    /* Valeur d'un type de pièce, 0 pour le roi */
+    pub fn value(&self, kind: PieceKind) -> i32 {
+        match kind {
+            PieceKind::King => 0,
+            PieceKind::Queen => self.queen,
+            PieceKind::Rook => self.rook,
+            PieceKind::Bishop => self.bishop,
+            PieceKind::Knight => self.knight,
+            PieceKind::Pawn => self.pawn,
+        }
+    }
+}
+
+/* Bilan matériel en centipions, positif si les blancs sont en avance */
+pub fn material_balance(position: &ChessPosition, values: &PieceValues) -> i32 {
+    let material = position.material();
+    PieceKind::ALL
+        .iter()
+        .map(|&kind| {
+            let white = material.count(Color::White, kind) as i32;
+            let black = material.count(Color::Black, kind) as i32;
+            (white - black) * values.value(kind)
+        })
+        .sum()
+}
+
+/* Bilan matériel avec le barème par défaut */
+pub fn material_balance_simple(position: &ChessPosition) -> i32 {
+    material_balance(position, &PieceValues::default())
+}
+
+/* Phase de la partie */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Opening,
+    Middlegame,
+    Endgame,
+}
+
+/* Seuils séparant les phases, sur l'échelle de `phase_value` : ouverture à partir de
+ * `opening`, finale à partir de `endgame` et en dessous */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhaseThresholds {
+    pub opening: f32,
+    pub endgame: f32,
+}
+
+/* Par défaut, ouverture tant qu'il reste 21 points sur 24 (au plus trois pièces mineures ou
+ * une tour et une pièce mineure échangées), finale à 7 points ou moins (par exemple une
+ * tour et une pièce mineure de chaque côté) */
+impl Default for PhaseThresholds {
+    fn default() -> Self {
+        PhaseThresholds {
+            opening: 21.0 / 24.0,
+            endgame: 7.0 / 24.0,
+        }
+    }
+}
+
+/* Avancement de la partie d'après le matériel hors pions restant : 1 pour le matériel de
+ * départ, 0 sans pièce. Une pièce mineure compte 1, une tour 2 et une dame 4, sur 24 ; les
+ * pièces promues ne portent pas la valeur au-delà de 1. */
+pub fn phase_value(position: &ChessPosition) -> f32 {
+    phase_points(position) as f32 / 24.0
+}
+
+/* Points de phase de 0 à 24, dont `phase_value` est la fraction */
+pub(crate) fn phase_points(position: &ChessPosition) -> u32 {
+    let material = position.material();
+    let points: u32 = [Color::White, Color::Black]
+        .into_iter()
+        .map(|color| {
+            material.count(color, PieceKind::Knight)
+                + material.count(color, PieceKind::Bishop)
+                + 2 * material.count(color, PieceKind::Rook)
+                + 4 * material.count(color, PieceKind::Queen)
+        })
+        .sum();
+    points.min(24)
+}
+
+/* Phase de la partie selon les seuils donnés */
+pub fn game_phase_with(position: &ChessPosition, thresholds: &PhaseThresholds) -> Phase {
+    let value = phase_value(position);
+    if value >= thresholds.opening {
+        Phase::Opening
+    } else if value <= thresholds.endgame {
+        Phase::Endgame
+    } else {
+        Phase::Middlegame
+    }
+}
+
+/* Phase de la partie selon les seuils par défaut */
+pub fn game_phase(position: &ChessPosition) -> Phase {
+    game_phase_with(position, &PhaseThresholds::default())
+}
+
+/* Case du roi d'un camp, le premier trouvé s'il y en a plusieurs */
+fn king_square(position: &ChessPosition, color: Color) -> Option<Square> {
+    position.bitboard(color, PieceKind::King).into_iter().next()
+}
+
+/* Pions du camp devant son roi, sur sa colonne et les colonnes voisines : à une rangée puis à
+ * deux rangées du roi */
+pub fn pawn_shield_ranks(position: &ChessPosition, color: Color) -> (u8, u8) {
+    let Some(king) = king_square(position, color) else {
+        return (0, 0);
+    };
+    let files = SquareSet::file(king.file()) | adjacent_files(king.file());
+    let pawns = position.bitboard(color, PieceKind::Pawn) & files;
+    let rank = king.rank().index();
+    let row = |offset: usize| {
+        let target = match color {
+            Color::White => rank.checked_add(offset),
+            Color::Black => rank.checked_sub(offset),
+        };
+        match target.and_then(|target| Rank::ALL.get(target)) {
+            Some(&target) => (pawns & SquareSet::rank(target)).len() as u8,
+            None => 0,
+        }
+    };
+    (row(1), row(2))
+}
+
+/* Bouclier de pions du roi : pions du camp sur les trois colonnes autour du roi, dans les
+ * deux rangées devant lui */
+pub fn pawn_shield(position: &ChessPosition, color: Color) -> u8 {
+    let (near, far) = pawn_shield_ranks(position, color);
+    near + far
+}
+
+/* Colonnes ouvertes ou semi-ouvertes pour le camp parmi celle de son roi et ses voisines,
+ * avec leur état */
+pub fn king_file_openness(position: &ChessPosition, color: Color) -> Vec<(File, FileState)> {
+    let Some(king) = king_square(position, color) else {
+        return Vec::new();
+    };
+    let index = king.file().index();
+    File::ALL[index.saturating_sub(1)..(index + 2).min(8)]
+        .iter()
+        .map(|&file| (file, file_state(position, file)))
+        .filter(|&(_, state)| state == FileState::Open || state == FileState::SemiOpenFor(color))
+        .collect()
+}
+
+/* Vrai si chaque camp a exactement un fou et que les deux fous sont de couleurs opposées,
+ * quelles que soient les autres pièces */
+pub fn has_opposite_colored_bishops(position: &ChessPosition) -> bool {
+    let white = bishops_by_square_color(position, Color::White);
+    let black = bishops_by_square_color(position, Color::Black);
+    matches!((white, black), ((1, 0), (0, 1)) | ((0, 1), (1, 0)))
+}
+
+/* Finale de fous de couleurs opposées : un fou de chaque côté, de couleurs opposées, et
+ * aucune autre pièce que les rois et les pions */
+pub fn is_opposite_colored_bishops(position: &ChessPosition) -> bool {
+    let material = position.material();
+    let others = [Color::White, Color::Black].into_iter().all(|color| {
+        [PieceKind::Queen, PieceKind::Rook, PieceKind::Knight]
+            .into_iter()
+            .all(|kind| material.count(color, kind) == 0)
+    });
+    others && has_opposite_colored_bishops(position)
+}
+
+/* Indice de position inaccessible depuis la position initiale.
+
+Les contrôles de `reachability_warnings` sont prudents : chacun repose sur un raisonnement rétrograde sûr (un pion sur sa case de départ n'a jamais bougé, un droit de roque signifie que le roi et la tour n'ont jamais bougé, chaque prise d'un pion retire une unité adverse), si bien qu'une position atteignable n'en déclenche aucun. Une position peut en revanche être inaccessible sans qu'ils le voient. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ReachabilityWarning {
+    /* Le fou de `home`, muré par les deux pions restés devant lui, n'a jamais pu partir :
+     * le fou de `square`, de la même couleur de cases, est donc une promotion, et le camp a
+     * plus de pièces promues que de pions manquants */
+    TrappedBishop {
+        color: Color,
+        home: Square,
+        square: Square,
+    },
+    /* Les colonnes des pions du camp demandent au moins `required` prises, mais l'adversaire
+     * n'a perdu que `available` unités */
+    PawnCaptures {
+        color: Color,
+        required: u32,
+        available: u32,
+    },
+    /* Le droit de roque `right` fixe le roi et la tour à leur place depuis le début ; avec
+     * les pions de la rangée de départ, l'aile est close et la pièce de `square`, ni un
+     * cavalier ni l'une des pièces d'origine de l'aile, n'a pu y entrer */
+    SealedCorner {
+        right: char,
+        square: Square,
+        piece: Piece,
+    },
+}
+
+impl fmt::Display for ReachabilityWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ReachabilityWarning::TrappedBishop {
+                color,
+                home,
+                square,
+            } => write!(
+                f,
+                "{} bishop on {} must be promoted, since the bishop of {} is walled in by its pawns, but {} has no missing pawn left to promote",
+                color.name(),
+                square,
+                home,
+                color.name()
+            ),
+            ReachabilityWarning::PawnCaptures {
+                color,
+                required,
+                available,
+            } => write!(
+                f,
+                "{} pawns need at least {} captures but {} has lost only {} units",
+                color.name(),
+                required,
+                color.opposite().name(),
+                available
+            ),
+            ReachabilityWarning::SealedCorner {
+                right,
+                square,
+                piece,
+            } => write!(
+                f,
+                "castling right '{}' keeps the {} corner closed, yet a {} {} stands on {}",
+                right,
+                if right.eq_ignore_ascii_case(&'K') {
+                    "kingside"
+                } else {
+                    "queenside"
+                },
+                piece.color.name(),
+                piece.kind.name(),
+                square
+            ),
+        }
+    }
+}
+
+/* Rangées de départ des pièces et des pions d'un camp */
+fn home_ranks(color: Color) -> (Rank, Rank) {
+    match color {
+        Color::White => (Rank::First, Rank::Second),
+        Color::Black => (Rank::Eighth, Rank::Seventh),
+    }
+}
+
+/* Vrai si un pion du camp occupe sa case de départ sur chacune des colonnes */
+fn unmoved_pawns(position: &ChessPosition, color: Color, files: &[File]) -> bool {
+    let (_, pawn_rank) = home_ranks(color);
+    files.iter().all(|&file| {
+        position.piece_at(Square::new(file, pawn_rank))
+            == Some(Piece {
+                color,
+                kind: PieceKind::Pawn,
+            })
+    })
+}
+
+/* Indices d'inaccessibilité de la position, blancs puis noirs, règle par règle */
+pub fn reachability_warnings(position: &ChessPosition) -> Vec<ReachabilityWarning> {
+    let mut warnings = Vec::new();
+    for color in [Color::White, Color::Black] {
+        trapped_bishops(position, color, &mut warnings);
+    }
+    for color in [Color::White, Color::Black] {
+        pawn_captures(position, color, &mut warnings);
+    }
+    for color in [Color::White, Color::Black] {
+        sealed_corners(position, color, &mut warnings);
+    }
+    warnings
+}
+
+/* Fou muré : une case de départ de fou dont les deux diagonales de sortie portent des pions
+ * du camp jamais déplacés. Tous les fous de cette couleur de cases hors de leur case de
+ * départ sont alors des promotions ; on compte au plus bas les pièces promues (dame au-delà
+ * de une, tours et cavaliers au-delà de deux, fous comme on vient de le dire) et l'indice
+ * est donné quand elles dépassent les pions manquants. */
+fn trapped_bishops(
+    position: &ChessPosition,
+    color: Color,
+    warnings: &mut Vec<ReachabilityWarning>,
+) {
+    let (piece_rank, _) = home_ranks(color);
+    let material = position.material();
+    let pawns = material.count(color, PieceKind::Pawn);
+    if pawns > 8 {
+        return;
+    }
+    let bishops = position.bitboard(color, PieceKind::Bishop);
+    let mut promoted = material.count(color, PieceKind::Queen).saturating_sub(1)
+        + material.count(color, PieceKind::Rook).saturating_sub(2)
+        + material.count(color, PieceKind::Knight).saturating_sub(2);
+    let mut trapped = Vec::new();
+    for (file, walls) in [(File::C, [File::B, File::D]), (File::F, [File::E, File::G])] {
+        let home = Square::new(file, piece_rank);
+        let complex = home.color_complex();
+        let same_complex: Vec<Square> = bishops
+            .into_iter()
+            .filter(|square| square.color_complex() == complex)
+            .collect();
+        if unmoved_pawns(position, color, &walls) {
+            let away: Vec<Square> = same_complex
+                .iter()
+                .copied()
+                .filter(|&square| square != home)
+                .collect();
+            promoted += away.len() as u32;
+            if let Some(&square) = away.first() {
+                trapped.push((home, square));
+            }
+        } else {
+            promoted += (same_complex.len() as u32).saturating_sub(1);
+        }
+    }
+    if promoted > 8 - pawns {
+        warnings.extend(trapped.into_iter().map(|(home, square)| {
+            ReachabilityWarning::TrappedBishop {
+                color,
+                home,
+                square,
+            }
+        }));
+    }
+}
+
+/* Prises de pions : les pions restants viennent de colonnes de départ distinctes, et chaque
+ * colonne franchie coûte une prise. Le minimum est celui de l'appariement dans l'ordre des
+ * pions restants, triés par colonne, avec des colonnes de départ croissantes. Chaque prise
+ * retire une unité adverse, pièce ou pion ; les promotions n'en rendent aucune. */
+fn pawn_captures(position: &ChessPosition, color: Color, warnings: &mut Vec<ReachabilityWarning>) {
+    let mut files: Vec<usize> = position
+        .bitboard(color, PieceKind::Pawn)
+        .into_iter()
+        .map(|square| square.file().index())
+        .collect();
+    if files.len() > 8 {
+        return;
+    }
+    files.sort_unstable();
+    /* best[j] : coût minimal des pions déjà vus sur les j premières colonnes de départ */
+    let mut best = [0u32; 9];
+    for (index, &file) in files.iter().enumerate() {
+        let mut next = [u32::MAX; 9];
+        for origin in index..8 {
+            let placed = best[origin].saturating_add(file.abs_diff(origin) as u32);
+            next[origin + 1] = next[origin].min(placed);
+        }
+        best = next;
+    }
+    let required = best[files.len()..].iter().copied().min().unwrap_or(0);
+    let material = position.material();
+    let units: u32 = PieceKind::ALL
+        .iter()
+        .map(|&kind| material.count(color.opposite(), kind))
+        .sum();
+    let available = 16u32.saturating_sub(units);
+    if required > available {
+        warnings.push(ReachabilityWarning::PawnCaptures {
+            color,
+            required,
+            available,
+        });
+    }
+}
+
+/* Aile close : avec un droit de roque, le roi et la tour n'ont jamais bougé ; si les pions de
+ * la colonne du roi à celle de la tour sont aussi sur leur case de départ, aucune pièce
+ * autre qu'un cavalier n'a pu entrer entre le roi et la tour. Seuls y restent les pièces
+ * d'origine de l'aile (le fou sur sa case, et la dame du côté dame, qui peut y changer de
+ * case après la prise d'un cavalier entré) et des cavaliers. */
+fn sealed_corners(position: &ChessPosition, color: Color, warnings: &mut Vec<ReachabilityWarning>) {
+    let rights = position.castling_rights;
+    let (piece_rank, _) = home_ranks(color);
+    let (kingside, queenside) = match color {
+        Color::White => (rights.white_kingside, rights.white_queenside),
+        Color::Black => (rights.black_kingside, rights.black_queenside),
+    };
+    let letter = |right: char| match color {
+        Color::White => right,
+        Color::Black => right.to_ascii_lowercase(),
+    };
+    /* Colonnes des pions qui ferment l'aile, de celle du roi à celle de la tour, et cases
+     * entre le roi et la tour */
+    let corners = [
+        (kingside, 'K', File::H, &File::ALL[4..], &File::ALL[5..7]),
+        (queenside, 'Q', File::A, &File::ALL[..5], &File::ALL[1..4]),
+    ];
+    let own = |kind| Piece { color, kind };
+    for (enabled, right, rook_file, pawn_files, inside) in corners {
+        let closed = enabled
+            && position.piece_at(Square::new(File::E, piece_rank)) == Some(own(PieceKind::King))
+            && position.piece_at(Square::new(rook_file, piece_rank)) == Some(own(PieceKind::Rook))
+            && unmoved_pawns(position, color, pawn_files);
+        if !closed {
+            continue;
+        }
+        for &file in inside {
+            let square = Square::new(file, piece_rank);
+            let Some(piece) = position.piece_at(square) else {
+                continue;
+            };
+            /* Le fou de départ est sur c ou f, de part et d'autre */
+            let original = piece.kind == PieceKind::Knight
+                || (piece == own(PieceKind::Queen) && right == 'Q')
+                || (piece == own(PieceKind::Bishop) && matches!(file, File::C | File::F));
+            if !original {
+                warnings.push(ReachabilityWarning::SealedCorner {
+                    right: letter(right),
+                    square,
+                    piece,
+                });
+            }
+        }
+    }
+}
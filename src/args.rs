@@ -0,0 +1,457 @@
+/* Découpage de la ligne de commande du programme.
+
+Les arguments sont répartis entre une sous-commande, ses arguments positionnels et ses options. Une FEN donnée sans sous-commande vaut `show`, comme dans les premières versions du programme. Chaque sous-commande déclare les options qu'elle accepte ; les options globales (`--format`, `--color`, orientation, `--quiet`, `--log-format`) valent pour toutes. */
+
+use crate::logger::LogFormat;
+use fen_parser::{FenDialect, MaterialFilter, Orientation};
+
+/* Sous-commandes, dans l'ordre de l'aide, avec leur description en une ligne */
+pub const SUBCOMMANDS: [(&str, &str); 13] = [
+    ("show", "render one or more positions (the default)"),
+    ("explain", "describe every field of a FEN"),
+    ("convert", "print positions in another format"),
+    ("validate", "check syntax and consistency"),
+    ("diff", "compare two positions square by square"),
+    ("apply", "play UCI moves onto a position"),
+    ("random", "generate pseudo-random positions"),
+    ("svg", "write an SVG diagram of a position"),
+    ("epd", "read an EPD test suite"),
+    ("stats", "summarize a FEN corpus"),
+    ("perft", "count the leaves of the move tree"),
+    ("repl", "start an interactive session"),
+    ("help", "show the help of a command"),
+];
+
+/* Options reconnues, et si elles attendent une valeur */
+const FLAGS: [(&str, bool); 25] = [
+    ("--format", true),
+    ("--quiet", false),
+    ("--log-format", true),
+    ("--color", true),
+    ("--orient", true),
+    ("--flip", false),
+    ("--file", true),
+    ("--verbose", false),
+    ("--trace", false),
+    ("--count", true),
+    ("--seed", true),
+    ("--legal-ish", false),
+    ("--output", true),
+    ("--size", true),
+    ("--coordinates", false),
+    ("--force", false),
+    ("--print-field", true),
+    ("--to-fen", false),
+    ("--top", true),
+    ("--from", true),
+    ("--to", true),
+    ("--depth", true),
+    ("--divide", false),
+    ("--material", true),
+    ("--help", false),
+];
+
+/* Options propres à chaque sous-commande, en plus des options globales */
+fn command_flags(command: &str) -> &'static [&'static str] {
+    match command {
+        "show" | "explain" => &["--file", "--material"],
+        "convert" => &["--file", "--from", "--to"],
+        "validate" => &["--file", "--verbose"],
+        "apply" => &["--trace"],
+        "random" => &["--count", "--seed", "--legal-ish"],
+        "svg" => &["--output", "--size", "--coordinates", "--force"],
+        "epd" => &["--print-field", "--to-fen"],
+        "stats" => &["--file", "--top", "--material"],
+        "perft" => &["--depth", "--divide"],
+        _ => &[],
+    }
+}
+
+/* Format de sortie demandé par `--format` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /* Plateau avec les symboles Unicode (`board` en est un alias) */
+    Unicode,
+    /* Plateau entièrement en ASCII */
+    Ascii,
+    /* FEN canonique, pour normaliser des positions */
+    Fen,
+    Json,
+    Markdown,
+}
+
+impl OutputFormat {
+    /* Format correspondant à la valeur de `--format`, ou message listant les choix */
+    fn from_arg(value: &str) -> Result<Self, String> {
+        match value {
+            "unicode" | "board" => Ok(OutputFormat::Unicode),
+            "ascii" => Ok(OutputFormat::Ascii),
+            "fen" => Ok(OutputFormat::Fen),
+            "json" => Ok(OutputFormat::Json),
+            "markdown" => Ok(OutputFormat::Markdown),
+            _ => Err(format!(
+                "Invalid format '{}': expected one of unicode, ascii, fen, json, markdown",
+                value
+            )),
+        }
+    }
+
+    /* Vrai pour les formats qui dessinent le plateau */
+    pub fn is_board(self) -> bool {
+        matches!(self, OutputFormat::Unicode | OutputFormat::Ascii)
+    }
+}
+
+/* Choix de `--color` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+/* Ligne de commande analysée */
+#[derive(Debug)]
+pub struct Cli {
+    /* Sous-commande (`show` par défaut) */
+    pub command: &'static str,
+    /* Vrai si la sous-commande a été nommée explicitement */
+    pub explicit_command: bool,
+    /* Arguments positionnels restants : FEN, coups... */
+    pub positionals: Vec<String>,
+    pub help: bool,
+    pub format: Option<OutputFormat>,
+    pub color: ColorChoice,
+    pub quiet: bool,
+    pub log_format: Option<LogFormat>,
+    /* Orientation demandée, avec l'option qui l'a fixée pour les messages d'erreur */
+    pub orientation: Option<(Orientation, &'static str)>,
+    pub file: Option<String>,
+    pub verbose: bool,
+    pub trace: bool,
+    pub count: u64,
+    pub seed: Option<u64>,
+    pub legal_ish: bool,
+    /* Fichier de sortie (`-` pour la sortie standard) */
+    pub output: Option<String>,
+    pub size: Option<u64>,
+    pub coordinates: bool,
+    pub force: bool,
+    /* Opération EPD à extraire (`fen` pour les champs de position) */
+    pub print_field: Option<String>,
+    pub to_fen: bool,
+    /* Nombre de signatures matérielles listées par `stats` */
+    pub top: Option<u64>,
+    /* Variantes de FEN lue et écrite par `convert` */
+    pub from: FenDialect,
+    pub to: Option<FenDialect>,
+    pub depth: Option<u64>,
+    pub divide: bool,
+    /* Filtre `--material` : seules les positions qui le remplissent sont traitées */
+    pub material: Option<MaterialFilter>,
+}
+
+/* Valeur numérique d'une option */
+fn parse_number(name: &str, value: &str) -> Result<u64, String> {
+    value.parse().map_err(|_| {
+        format!(
+            "Invalid value '{}' for {}: expected a non-negative integer",
+            value, name
+        )
+    })
+}
+
+/* Variante de FEN nommée par `--from` ou `--to` */
+fn parse_dialect(name: &str, value: &str) -> Result<FenDialect, String> {
+    FenDialect::from_name(value).ok_or_else(|| {
+        format!(
+            "Invalid value '{}' for {}: expected fen, shredder, xfen, epd or fen4",
+            value, name
+        )
+    })
+}
+
+/* Analyse les arguments (sans le nom du programme) ; l'erreur est le message à afficher */
+pub fn parse_args(args: &[String]) -> Result<Cli, String> {
+    let mut command = None;
+    let mut positionals = Vec::new();
+    let mut flags: Vec<(&'static str, Option<String>)> = Vec::new();
+
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg == "-h" {
+            flags.push(("--help", None));
+        } else if arg == "-o" {
+            match rest.next() {
+                Some(value) => flags.push(("--output", Some(value.clone()))),
+                None => return Err("Missing value for -o".to_string()),
+            }
+        } else if arg.starts_with("--") {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let Some(&(name, takes_value)) = FLAGS.iter().find(|(flag, _)| *flag == name) else {
+                return Err(format!("Unknown option '{}'", name));
+            };
+            let value = match (takes_value, inline) {
+                (true, Some(value)) => Some(value),
+                (true, None) => match rest.next() {
+                    Some(value) => Some(value.clone()),
+                    None => return Err(format!("Missing value for {}", name)),
+                },
+                (false, Some(_)) => return Err(format!("{} does not take a value", name)),
+                (false, None) => None,
+            };
+            flags.push((name, value));
+        } else if command.is_none() && positionals.is_empty() {
+            match SUBCOMMANDS.iter().find(|(name, _)| *name == arg.as_str()) {
+                Some(&(name, _)) => command = Some(name),
+                None => positionals.push(arg.clone()),
+            }
+        } else {
+            positionals.push(arg.clone());
+        }
+    }
+
+    let mut cli = Cli {
+        command: command.unwrap_or("show"),
+        explicit_command: command.is_some(),
+        positionals,
+        help: false,
+        format: None,
+        color: ColorChoice::Auto,
+        quiet: false,
+        log_format: None,
+        orientation: None,
+        file: None,
+        verbose: false,
+        trace: false,
+        count: 1,
+        seed: None,
+        legal_ish: false,
+        output: None,
+        size: None,
+        coordinates: false,
+        force: false,
+        print_field: None,
+        to_fen: false,
+        top: None,
+        from: FenDialect::Fen,
+        to: None,
+        depth: None,
+        divide: false,
+        material: None,
+    };
+
+    for (name, value) in flags {
+        let value = value.unwrap_or_default();
+        match name {
+            "--help" => cli.help = true,
+            "--format" => cli.format = Some(OutputFormat::from_arg(&value)?),
+            "--color" => {
+                cli.color = match value.as_str() {
+                    "auto" => ColorChoice::Auto,
+                    "always" => ColorChoice::Always,
+                    "never" => ColorChoice::Never,
+                    _ => {
+                        return Err(format!(
+                            "Invalid color '{}': expected auto, always or never",
+                            value
+                        ))
+                    }
+                }
+            }
+            "--quiet" => cli.quiet = true,
+            "--log-format" => {
+                cli.log_format = match value.as_str() {
+                    "text" => Some(LogFormat::Text),
+                    "json" => Some(LogFormat::Json),
+                    _ => {
+                        return Err(format!(
+                            "Invalid log format '{}': expected text or json",
+                            value
+                        ))
+                    }
+                }
+            }
+            "--flip" => cli.orientation = Some((Orientation::Black, "--flip")),
+            "--orient" => {
+                let orientation = match value.as_str() {
+                    "white" => Orientation::White,
+                    "black" => Orientation::Black,
+                    "auto" => Orientation::SideToMove,
+                    _ => {
+                        return Err(format!(
+                            "Invalid orientation '{}': expected white, black or auto",
+                            value
+                        ))
+                    }
+                };
+                cli.orientation = Some((orientation, "--orient"));
+            }
+            _ if !command_flags(cli.command).contains(&name) => {
+                return Err(format!(
+                    "{} is not an option of '{}' (see 'fen_parser help {}')",
+                    name, cli.command, cli.command
+                ));
+            }
+            "--file" => cli.file = Some(value),
+            "--verbose" => cli.verbose = true,
+            "--trace" => cli.trace = true,
+            "--count" => cli.count = parse_number(name, &value)?,
+            "--seed" => cli.seed = Some(parse_number(name, &value)?),
+            "--legal-ish" => cli.legal_ish = true,
+            "--output" => cli.output = Some(value),
+            "--size" => cli.size = Some(parse_number(name, &value)?),
+            "--coordinates" => cli.coordinates = true,
+            "--force" => cli.force = true,
+            "--print-field" => cli.print_field = Some(value),
+            "--to-fen" => cli.to_fen = true,
+            "--top" => cli.top = Some(parse_number(name, &value)?),
+            "--from" => cli.from = parse_dialect(name, &value)?,
+            "--to" => cli.to = Some(parse_dialect(name, &value)?),
+            "--depth" => cli.depth = Some(parse_number(name, &value)?),
+            "--divide" => cli.divide = true,
+            "--material" => {
+                cli.material = Some(
+                    MaterialFilter::parse(&value)
+                        .map_err(|error| format!("Invalid value for --material: {}", error))?,
+                )
+            }
+            _ => {}
+        }
+    }
+    Ok(cli)
+}
+
+const GLOBAL_OPTIONS: &str = "\
+Global options:
+  --format <unicode|ascii|fen|json|markdown>  output format (default: unicode)
+  --color <auto|always|never>                 colored board; auto honours NO_COLOR and needs a terminal
+  --flip                                      show the board from Black's side
+  --orient <white|black|auto>                 side at the bottom; auto follows the side to move
+  --quiet                                     print only the requested output (FEN, JSON...)
+  --log-format <text|json>                    one `level: message` line or JSON object per diagnostic
+  -h, --help                                  show this help
+";
+
+/* Aide générale : sous-commandes, options globales et exemples */
+pub fn general_help() -> String {
+    let mut out = String::from("Usage: fen_parser [COMMAND] [OPTIONS] [FEN...]\n\n");
+    out.push_str("Render, check and transform chess positions written in FEN.\n");
+    out.push_str(
+        "A bare FEN is shown as with `show`; without FEN, it is read from standard input.\n\n",
+    );
+    out.push_str("Commands:\n");
+    for (name, description) in SUBCOMMANDS {
+        out.push_str(&format!("  {:<10} {}\n", name, description));
+    }
+    out.push('\n');
+    out.push_str(GLOBAL_OPTIONS);
+    out.push_str(
+        "\nExamples:\n  \
+         fen_parser \"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\"\n  \
+         fen_parser validate --file positions.txt\n  \
+         fen_parser apply startpos e2e4 e7e5 --format fen\n\n\
+         Run 'fen_parser help <command>' for the options of a command.\n",
+    );
+    out
+}
+
+/* Aide d'une sous-commande, ou `None` si elle est inconnue */
+pub fn command_help(command: &str) -> Option<String> {
+    let (usage, details) = match command {
+        "show" => (
+            "show [--file <path|->] [--material <filter>] [FEN...]",
+            "Render each position with its metadata; several FENs are headed [i/n].\n\
+             \nOptions:\n  --file <path|->      read one FEN per line ('-' for standard input)\n  \
+             --material <filter>  with --file, only render positions whose material matches: \"KR*vKR\",\n\
+             \x20                      \"^KQvK\" (White holds the first side) or \"B>=2 b=0\"\n\
+             \nExamples:\n  fen_parser show --flip \"<fen>\"\n  fen_parser --format json \"<fen>\" \"<fen>\"\n",
+        ),
+        "explain" => (
+            "explain [--file <path|->] [--material <filter>] [FEN...]",
+            "Describe every field of each FEN in plain words.\n\
+             \nExample:\n  fen_parser explain \"<fen>\"\n",
+        ),
+        "convert" => (
+            "convert [--from <dialect>] [--to <dialect>] [--file <path|->] [FEN...]",
+            "Print each position in the --format given, canonical FEN by default, or rewrite it\n\
+             in another FEN dialect with --to (--format then does not apply).\n\
+             \nDialects:\n  fen       classical six-field FEN (default for --from)\n  \
+             shredder  castling rights written with the rook files (HAha)\n  \
+             xfen      KQkq for the outermost rooks, as in X-FEN\n  \
+             epd       the four position fields; EPD operations are read, not written\n  \
+             fen4      the four position fields, without clocks\n\
+             \nExamples:\n  fen_parser convert --format json \"<fen>\"\n  \
+             fen_parser convert --to shredder --file chess960.fen\n",
+        ),
+        "validate" => (
+            "validate [--verbose] [--file <path|->] [FEN...]",
+            "Check the syntax and the consistency of each FEN (kings, pawns, castling, en passant).\n\
+             Nothing is printed on success unless --verbose; failures get a full report.\n\
+             Exit codes: 0 valid, 1 syntax error, 2 invalid position, 3 I/O error.\n\
+             \nExample:\n  fen_parser validate --file generated.txt\n",
+        ),
+        "diff" => (
+            "diff <FEN_a> <FEN_b>",
+            "Show both boards side by side with the changed squares marked.\n\
+             Exit codes: 0 identical, 1 different, 2 invalid FEN.\n\
+             \nExample:\n  fen_parser diff \"<fen>\" \"<fen>\" && echo same\n",
+        ),
+        "apply" => (
+            "apply [--trace] <FEN|startpos> <uci_move...>",
+            "Play each move and print the final position, or every intermediate FEN with --trace.\n\
+             \nExample:\n  fen_parser apply startpos e2e4 e7e5 g1f3 --format fen\n",
+        ),
+        "random" => (
+            "random [--count <n>] [--seed <n>] [--legal-ish]",
+            "Print pseudo-random positions, one FEN per line; a seed makes the output reproducible.\n\
+             --legal-ish keeps one king per side, sane piece counts and no pawn on the back ranks.\n\
+             \nExample:\n  fen_parser random --count 10 --seed 42 --legal-ish\n",
+        ),
+        "svg" => (
+            "svg [-o, --output <path|->] [--size <px>] [--coordinates] [--force] <FEN>",
+            "Write an SVG diagram of the position; --size is the width of the board in pixels\n\
+             (360 by default). An existing file is only replaced with --force.\n\
+             \nOptions:\n  -o, --output <path|->  destination file ('-', the default, for standard output)\n\
+             \nExample:\n  fen_parser svg \"<fen>\" -o board.svg --size 400 --flip --coordinates\n",
+        ),
+        "epd" => (
+            "epd [--print-field <opcode|fen>] [--to-fen] <path|->",
+            "Parse every EPD record of the file, report malformed lines and print the totals.\n\
+             \nOptions:\n  --print-field <opcode|fen>  print one column: an operation such as id or bm,\n\
+             \x20                             or fen for the four position fields\n  \
+             --to-fen                    print each record as a six-field FEN (clocks from hmvc/fmvn)\n\
+             \nExample:\n  fen_parser epd wac.epd --print-field bm\n",
+        ),
+        "stats" => (
+            "stats [--file <path|->] [--top <n>] [--material <filter>]",
+            "Read one FEN per line (standard input without --file) and print aggregate numbers:\n\
+             lines, parse failures, duplicate positions (same placement, side, castling and en\n\
+             passant), side to move, castling rights, the most frequent material signatures\n\
+             (--top, 5 by default) and the minimum, median and maximum piece counts.\n\
+             With --material (see 'fen_parser help show'), only matching positions are counted.\n\
+             \nExamples:\n  fen_parser stats --file corpus.fen --top 10\n  \
+             fen_parser stats --file corpus.fen --material KRPvKR\n",
+        ),
+        "perft" => (
+            "perft --depth <n> [--divide] <FEN|startpos>",
+            "Count the leaf nodes of the legal move tree, as Stockfish's 'go perft' does.\n\
+             --divide first prints the count below each root move ('e2e4: 600'); the elapsed\n\
+             time and speed go to standard error so that standard output can be diffed.\n\
+             \nExample:\n  fen_parser perft startpos --depth 4 --divide\n",
+        ),
+        "repl" => (
+            "repl",
+            "Start an interactive session; type 'help' at the prompt for its commands.\n",
+        ),
+        "help" => ("help [COMMAND]", "Show the general help or the help of a command.\n"),
+        _ => return None,
+    };
+    Some(format!(
+        "Usage: fen_parser {}\n\n{}\n{}",
+        usage, details, GLOBAL_OPTIONS
+    ))
+}
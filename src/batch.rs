@@ -0,0 +1,115 @@
+/* Traitement par lots de fichiers de FEN.
+
+Ce module lit un flux ligne à ligne, sans le charger entièrement en mémoire, en ignorant les lignes vides et les commentaires `#`, et tient le compte des positions valides et invalides. */
+
+#[cfg(feature = "telemetry")]
+use crate::telemetry::{self, Level};
+use std::fmt;
+use std::io::{self, BufRead};
+
+/* FEN lue dans un flux, avec son numéro de ligne (à partir de 1) */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchEntry {
+    pub line: usize,
+    pub fen: String,
+}
+
+/* Itérateur sur les FEN d'un flux */
+pub struct BatchEntries<R> {
+    lines: io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> Iterator for BatchEntries<R> {
+    type Item = io::Result<BatchEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let text = match self.lines.next()? {
+                Ok(text) => text,
+                Err(error) => {
+                    #[cfg(feature = "telemetry")]
+                    telemetry::event(Level::Error, "batch read failed", || {
+                        vec![
+                            ("line", (self.line + 1).to_string()),
+                            ("error", error.to_string()),
+                        ]
+                    });
+                    return Some(Err(error));
+                }
+            };
+            self.line += 1;
+            let fen = text.trim();
+            if fen.is_empty() || fen.starts_with('#') {
+                continue;
+            }
+            #[cfg(feature = "telemetry")]
+            telemetry::event(Level::Trace, "batch entry", || {
+                vec![
+                    ("line", self.line.to_string()),
+                    ("len", fen.len().to_string()),
+                ]
+            });
+            return Some(Ok(BatchEntry {
+                line: self.line,
+                fen: fen.to_string(),
+            }));
+        }
+    }
+}
+
+/* Parcourt les FEN d'un flux, une par ligne */
+pub fn entries<R: BufRead>(reader: R) -> BatchEntries<R> {
+    BatchEntries {
+        lines: reader.lines(),
+        line: 0,
+    }
+}
+
+/* Bilan d'un traitement par lots */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub ok: usize,
+    pub failed: usize,
+}
+
+impl BatchSummary {
+    /* Comptabilise le résultat d'une ligne */
+    pub fn record(&mut self, ok: bool) {
+        if ok {
+            self.ok += 1;
+        } else {
+            self.failed += 1;
+        }
+    }
+
+    /* Vrai si aucune ligne n'a échoué */
+    pub fn all_ok(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/* Bilan lisible, par exemple `1,204 ok, 3 failed` */
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ok, {} failed",
+            with_thousands(self.ok),
+            with_thousands(self.failed)
+        )
+    }
+}
+
+/* Écrit un entier avec une virgule entre chaque groupe de trois chiffres */
+fn with_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,316 @@
+/* Tables d'occupation par camp et par type de pièce.
+
+`ChessPosition` garde son tableau 8×8, dont les champs restent publics : les masques en sont dérivés à la demande, en un seul parcours, par `ChessPosition::bitboards()`. Qui enchaîne les coups peut les tenir à jour lui-même avec `make_move_tracked`, la génération des coups le fait en interne ; la détection des attaques s'y réduit alors à quelques opérations sur des entiers de 64 bits.
+
+Les masques échangés avec un moteur (`from_bitboards`, `to_bitboards`) suivent l'ordre LERF : le bit 0 est a1, le bit 7 h1, le bit 63 h8, comme les indices de `Square`. */
+
+use crate::types::{CastlingRights, ChessPosition, Color, Piece, PieceKind, Square, SquareSet};
+use core::fmt;
+
+/* Cases atteintes depuis chaque case par des décalages (colonnes, rangées) */
+const fn step_table(offsets: &[(i8, i8)]) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let file = (square % 8) as i8;
+        let rank = (square / 8) as i8;
+        let mut i = 0;
+        while i < offsets.len() {
+            let (df, dr) = offsets[i];
+            let (f, r) = (file + df, rank + dr);
+            if f >= 0 && f < 8 && r >= 0 && r < 8 {
+                table[square] |= 1 << (r * 8 + f);
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+    table
+}
+
+const KNIGHT_ATTACKS: [u64; 64] = step_table(&[
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+]);
+const KING_ATTACKS: [u64; 64] = step_table(&[
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+]);
+/* Cases d'où un pion de chaque camp attaquerait la case : en dessous pour les blancs */
+const PAWN_ATTACKERS: [[u64; 64]; 2] = [
+    step_table(&[(-1, -1), (1, -1)]),
+    step_table(&[(-1, 1), (1, 1)]),
+];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+fn kind_index(kind: PieceKind) -> usize {
+    PieceKind::ALL
+        .iter()
+        .position(|&candidate| candidate == kind)
+        .unwrap_or(0)
+}
+
+/* Une valeur par camp */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByColor<T> {
+    pub white: T,
+    pub black: T,
+}
+
+impl<T> ByColor<T> {
+    /* Valeur d'un camp */
+    pub fn get(&self, color: Color) -> &T {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    pub fn get_mut(&mut self, color: Color) -> &mut T {
+        match color {
+            Color::White => &mut self.white,
+            Color::Black => &mut self.black,
+        }
+    }
+}
+
+/* Une valeur par type de pièce */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByPieceKind<T> {
+    pub king: T,
+    pub queen: T,
+    pub rook: T,
+    pub bishop: T,
+    pub knight: T,
+    pub pawn: T,
+}
+
+impl<T> ByPieceKind<T> {
+    /* Valeur d'un type de pièce */
+    pub fn get(&self, kind: PieceKind) -> &T {
+        match kind {
+            PieceKind::King => &self.king,
+            PieceKind::Queen => &self.queen,
+            PieceKind::Rook => &self.rook,
+            PieceKind::Bishop => &self.bishop,
+            PieceKind::Knight => &self.knight,
+            PieceKind::Pawn => &self.pawn,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: PieceKind) -> &mut T {
+        match kind {
+            PieceKind::King => &mut self.king,
+            PieceKind::Queen => &mut self.queen,
+            PieceKind::Rook => &mut self.rook,
+            PieceKind::Bishop => &mut self.bishop,
+            PieceKind::Knight => &mut self.knight,
+            PieceKind::Pawn => &mut self.pawn,
+        }
+    }
+}
+
+/* Erreur de construction d'une position à partir de masques */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PositionError {
+    /* Deux masques revendiquent la même case ; `first` est la pièce rencontrée d'abord dans
+     * l'ordre blancs puis noirs, roi à pion */
+    Overlap {
+        square: Square,
+        first: Piece,
+        second: Piece,
+    },
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::Overlap {
+                square,
+                first,
+                second,
+            } => write!(
+                f,
+                "square {} is claimed by both a {} {} and a {} {}",
+                square,
+                first.color.name(),
+                first.kind.name(),
+                second.color.name(),
+                second.kind.name()
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PositionError {}
+
+/* Un masque par camp et par type de pièce (ordre de `PieceKind::ALL`) */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bitboards {
+    pieces: [[SquareSet; 6]; 2],
+}
+
+impl Bitboards {
+    /* Masques d'une position, en un parcours du tableau */
+    pub fn of(position: &ChessPosition) -> Self {
+        let mut boards = Bitboards::default();
+        for square in (0..64).filter_map(Square::from_index) {
+            boards.set(square, None, position.piece_at(square));
+        }
+        boards
+    }
+
+    /* Cases des pièces d'un camp */
+    pub fn occupancy(&self, color: Color) -> SquareSet {
+        self.pieces[color_index(color)]
+            .iter()
+            .fold(SquareSet::empty(), |all, &set| all | set)
+    }
+
+    /* Cases des pièces d'un type et d'un camp */
+    pub fn bitboard(&self, color: Color, kind: PieceKind) -> SquareSet {
+        self.pieces[color_index(color)][kind_index(kind)]
+    }
+
+    /* Cases occupées, tous camps confondus */
+    pub fn occupied(&self) -> SquareSet {
+        self.occupancy(Color::White) | self.occupancy(Color::Black)
+    }
+
+    /* Reporte le remplacement de `old` par `new` sur une case */
+    pub(crate) fn set(&mut self, square: Square, old: Option<Piece>, new: Option<Piece>) {
+        if let Some(piece) = old {
+            self.pieces[color_index(piece.color)][kind_index(piece.kind)].remove(square);
+        }
+        if let Some(piece) = new {
+            self.pieces[color_index(piece.color)][kind_index(piece.kind)].insert(square);
+        }
+    }
+
+    /* Vrai si une pièce du camp `by` attaque la case ; même réponse que
+     * `ChessPosition::is_attacked` sur la position décrite */
+    pub fn is_attacked(&self, square: Square, by: Color) -> bool {
+        let index = square.index();
+        let mask = |kind| self.bitboard(by, kind).0;
+        if PAWN_ATTACKERS[color_index(by)][index] & mask(PieceKind::Pawn) != 0
+            || KNIGHT_ATTACKS[index] & mask(PieceKind::Knight) != 0
+            || KING_ATTACKS[index] & mask(PieceKind::King) != 0
+        {
+            return true;
+        }
+
+        let queens = mask(PieceKind::Queen);
+        let occupied = self.occupied().0;
+        let sliders = [
+            (ROOK_DIRECTIONS, mask(PieceKind::Rook) | queens),
+            (BISHOP_DIRECTIONS, mask(PieceKind::Bishop) | queens),
+        ];
+        for (directions, attackers) in sliders {
+            if attackers == 0 {
+                continue;
+            }
+            for (df, dr) in directions {
+                let (mut file, mut rank) = ((index % 8) as i8 + df, (index / 8) as i8 + dr);
+                while (0..8).contains(&file) && (0..8).contains(&rank) {
+                    let bit = 1u64 << (rank * 8 + file);
+                    if occupied & bit != 0 {
+                        if attackers & bit != 0 {
+                            return true;
+                        }
+                        break;
+                    }
+                    file += df;
+                    rank += dr;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl ChessPosition {
+    /* Masques d'occupation de la position, calculés à la demande */
+    pub fn bitboards(&self) -> Bitboards {
+        Bitboards::of(self)
+    }
+
+    /* Cases des pièces d'un camp */
+    pub fn occupancy(&self, color: Color) -> SquareSet {
+        self.bitboards().occupancy(color)
+    }
+
+    /* Cases des pièces d'un type et d'un camp */
+    pub fn bitboard(&self, color: Color, kind: PieceKind) -> SquareSet {
+        self.bitboards().bitboard(color, kind)
+    }
+
+    /* Position décrite par douze masques LERF (bit 0 = a1, bit 63 = h8) ; les compteurs
+     * sont `(demi-coups, numéro du coup)`. Une case revendiquée par deux masques est refusée. */
+    pub fn from_bitboards(
+        bitboards: &ByColor<ByPieceKind<u64>>,
+        active_color: Color,
+        castling_rights: CastlingRights,
+        en_passant: Option<Square>,
+        (halfmove_clock, fullmove_number): (u32, u32),
+    ) -> Result<Self, PositionError> {
+        let mut pieces: [[Option<Piece>; 8]; 8] = [[None; 8]; 8];
+        for color in [Color::White, Color::Black] {
+            for kind in PieceKind::ALL {
+                let second = Piece { color, kind };
+                for square in SquareSet(*bitboards.get(color).get(kind)) {
+                    let slot = &mut pieces[square.rank().index()][square.file().index()];
+                    if let Some(first) = *slot {
+                        return Err(PositionError::Overlap {
+                            square,
+                            first,
+                            second,
+                        });
+                    }
+                    *slot = Some(second);
+                }
+            }
+        }
+        Ok(ChessPosition {
+            pieces,
+            active_color,
+            castling_rights,
+            en_passant: en_passant
+                .map(|square| (square.file().index() as u8, square.rank().index() as u8)),
+            halfmove_clock,
+            fullmove_number,
+        })
+    }
+
+    /* Douze masques LERF de la position (bit 0 = a1), inverse de `from_bitboards` */
+    pub fn to_bitboards(&self) -> ByColor<ByPieceKind<u64>> {
+        let boards = self.bitboards();
+        let mut out = ByColor::<ByPieceKind<u64>>::default();
+        for color in [Color::White, Color::Black] {
+            for kind in PieceKind::ALL {
+                *out.get_mut(color).get_mut(kind) = boards.bitboard(color, kind).0;
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,131 @@
+/* Cache des positions lues, pour les services qui relisent sans cesse les mêmes FEN.
+
+`FenCache` associe la chaîne FEN, telle quelle, à la position partagée (`Arc<ChessPosition>`) ; deux écritures d'une même position (espaces en plus, par exemple) occupent donc deux entrées. Le nombre d'entrées est borné : au-delà, l'entrée lue ou insérée il y a le plus longtemps est évincée. Les erreurs ne sont pas gardées, une FEN invalide est relue à chaque demande.
+
+Le cache est protégé par un `Mutex` de la bibliothèque standard et se partage entre fils ; la lecture d'une FEN absente se fait hors du verrou. */
+
+use crate::error::FenError;
+use crate::parser::parse_fen;
+use crate::types::ChessPosition;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/* Entrées et ordre d'utilisation, sous le verrou */
+#[derive(Debug, Default)]
+struct Entries {
+    /* Position et date de dernière utilisation de chaque FEN */
+    positions: HashMap<String, (Arc<ChessPosition>, u64)>,
+    /* FEN par date de dernière utilisation, la plus ancienne en tête */
+    order: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl Entries {
+    /* Marque une entrée comme la plus récente et renvoie sa position */
+    fn touch(&mut self, fen: &str) -> Option<Arc<ChessPosition>> {
+        self.clock += 1;
+        let clock = self.clock;
+        let (position, used) = self.positions.get_mut(fen)?;
+        let key = self.order.remove(used).unwrap_or_else(|| fen.to_string());
+        *used = clock;
+        self.order.insert(clock, key);
+        Some(Arc::clone(position))
+    }
+
+    fn insert(&mut self, fen: &str, position: Arc<ChessPosition>, capacity: usize) {
+        while self.positions.len() >= capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.positions.remove(&oldest);
+        }
+        self.clock += 1;
+        self.positions
+            .insert(fen.to_string(), (position, self.clock));
+        self.order.insert(self.clock, fen.to_string());
+    }
+}
+
+/* Cache borné des positions lues, évincées de la moins récemment utilisée à la plus récente */
+#[derive(Debug)]
+pub struct FenCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl FenCache {
+    /* Cache d'au plus `capacity` positions ; avec 0, rien n'est gardé */
+    pub fn new(capacity: usize) -> Self {
+        FenCache {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /* Un fil qui a paniqué sous le verrou n'a pu laisser qu'un compteur de plus ou une
+     * entrée de moins : les entrées restent utilisables */
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /* Position de `fen`, lue du cache s'il la contient, sinon lue puis gardée */
+    pub fn get_or_parse(&self, fen: &str) -> Result<Arc<ChessPosition>, FenError> {
+        if let Some(position) = self.lock().touch(fen) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(position);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let position = Arc::new(parse_fen(fen)?);
+        if self.capacity > 0 {
+            let mut entries = self.lock();
+            /* Un autre fil a pu lire la même FEN entre-temps : sa position est gardée */
+            if let Some(existing) = entries.touch(fen) {
+                return Ok(existing);
+            }
+            entries.insert(fen, Arc::clone(&position), self.capacity);
+        }
+        Ok(position)
+    }
+
+    /* Vrai si `fen` est en cache ; ne compte ni comme utilisation ni comme succès */
+    pub fn contains(&self, fen: &str) -> bool {
+        self.lock().positions.contains_key(fen)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /* Nombre de positions gardées */
+    pub fn len(&self) -> usize {
+        self.lock().positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /* Demandes servies par le cache */
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /* Demandes qui ont dû lire la FEN, erreurs comprises */
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /* Vide le cache ; les compteurs sont conservés */
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.positions.clear();
+        entries.order.clear();
+    }
+}
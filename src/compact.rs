@@ -0,0 +1,327 @@
+/* Encodage binaire compact d'une position.
+
+Les caches et files de messages n'ont que faire d'une FEN lisible : cette forme tient en 37 à 45 octets, sans chaîne ni nom de champ. Disposition :
+
+- octet 0 : version du format (1) ;
+- octets 1 à 32 : les 64 cases de a1 à h8, un demi-octet par case, la case paire dans les bits de poids faible (0 = vide, 1 à 6 = roi à pion blancs, 9 à 14 = roi à pion noirs) ;
+- octet 33 : trait (bit 0, noirs si levé) puis roques `K`, `Q`, `k`, `q` (bits 1 à 4) ;
+- octet 34 : case de prise en passant (0 = a1 ... 63 = h8) ou 0xFF ;
+- ensuite les deux compteurs de coups en entiers variables (7 bits par octet, bit de poids fort pour « suite »), comme LEB128.
+
+`CompactPosition` reprend le plateau en demi-octets et les drapeaux sous forme de structure de 40 octets, pour les grandes collections en mémoire : comparaison, hachage et écriture FEN se font sans repasser par `ChessPosition`. */
+
+use crate::types::{CastlingRights, ChessPosition, Color, Piece, PieceKind, Square};
+use std::fmt::Write;
+use thiserror::Error;
+
+/* Version écrite en tête de chaque encodage */
+const VERSION: u8 = 1;
+/* Octets précédant les compteurs : version, plateau, drapeaux, prise en passant */
+const HEADER_LEN: usize = 35;
+const NO_EN_PASSANT: u8 = 0xFF;
+
+/* Erreur de décodage ou de conversion vers `CompactPosition` ; un tampon tronqué ou corrompu
+ * n'entraîne jamais de panique */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum CompactError {
+    #[error("buffer too short: expected at least {expected} bytes, found {found}")]
+    Truncated { expected: usize, found: usize },
+    #[error("unsupported format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("invalid piece code {code} on square {square}")]
+    InvalidPiece { code: u8, square: Square },
+    #[error("invalid flags byte {0:#04x}")]
+    InvalidFlags(u8),
+    #[error("invalid en passant square {0}: expected a square on rank 3 or 6")]
+    InvalidEnPassant(u8),
+    #[error("move counter does not fit in 32 bits")]
+    CounterOverflow,
+    #[error("{0} unexpected trailing bytes")]
+    TrailingBytes(usize),
+    /* Compteur de demi-coups trop grand pour `CompactPosition` */
+    #[error("halfmove clock {0} does not fit in 16 bits")]
+    HalfmoveClockOverflow(u32),
+}
+
+/* Code d'une pièce sur quatre bits */
+fn piece_code(piece: Option<Piece>) -> u8 {
+    let Some(piece) = piece else {
+        return 0;
+    };
+    let kind = PieceKind::ALL
+        .iter()
+        .position(|&kind| kind == piece.kind)
+        .unwrap_or(0) as u8;
+    match piece.color {
+        Color::White => 1 + kind,
+        Color::Black => 9 + kind,
+    }
+}
+
+/* Pièce correspondant à un code, `Ok(None)` pour une case vide */
+fn piece_from_code(code: u8, square: Square) -> Result<Option<Piece>, CompactError> {
+    let (color, kind) = match code {
+        0 => return Ok(None),
+        1..=6 => (Color::White, code - 1),
+        9..=14 => (Color::Black, code - 9),
+        _ => return Err(CompactError::InvalidPiece { code, square }),
+    };
+    Ok(Some(Piece {
+        color,
+        kind: PieceKind::ALL[kind as usize],
+    }))
+}
+
+fn push_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/* Lit un entier variable ; renvoie la valeur et le nombre d'octets consommés */
+fn read_varint(bytes: &[u8], offset: usize) -> Result<(u32, usize), CompactError> {
+    let mut value: u32 = 0;
+    for (index, &byte) in bytes[offset..].iter().enumerate().take(5) {
+        let bits = u32::from(byte & 0x7F);
+        let shift = 7 * index as u32;
+        if shift == 28 && bits > 0x0F {
+            return Err(CompactError::CounterOverflow);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    if bytes.len() - offset >= 5 {
+        Err(CompactError::CounterOverflow)
+    } else {
+        Err(CompactError::Truncated {
+            expected: bytes.len() + 1,
+            found: bytes.len(),
+        })
+    }
+}
+
+/* Octet des drapeaux : trait (bit 0) puis roques `K`, `Q`, `k`, `q` (bits 1 à 4) */
+fn flags(position: &ChessPosition) -> u8 {
+    let rights = position.castling_rights;
+    u8::from(position.active_color == Color::Black)
+        | (u8::from(rights.white_kingside) << 1)
+        | (u8::from(rights.white_queenside) << 2)
+        | (u8::from(rights.black_kingside) << 3)
+        | (u8::from(rights.black_queenside) << 4)
+}
+
+fn color_from_flags(flags: u8) -> Color {
+    if flags & 0x01 != 0 {
+        Color::Black
+    } else {
+        Color::White
+    }
+}
+
+fn castling_from_flags(flags: u8) -> CastlingRights {
+    CastlingRights {
+        white_kingside: flags & 0x02 != 0,
+        white_queenside: flags & 0x04 != 0,
+        black_kingside: flags & 0x08 != 0,
+        black_queenside: flags & 0x10 != 0,
+    }
+}
+
+fn en_passant_byte(position: &ChessPosition) -> u8 {
+    position
+        .en_passant_square()
+        .map_or(NO_EN_PASSANT, |square| square.index() as u8)
+}
+
+/* Plateau en demi-octets, la case paire dans les bits de poids faible */
+fn packed_board(position: &ChessPosition) -> [u8; 32] {
+    let mut board = [0; 32];
+    for (pair, byte) in board.iter_mut().enumerate() {
+        let low = Square::from_index(2 * pair).and_then(|square| position.piece_at(square));
+        let high = Square::from_index(2 * pair + 1).and_then(|square| position.piece_at(square));
+        *byte = piece_code(low) | (piece_code(high) << 4);
+    }
+    board
+}
+
+impl ChessPosition {
+    /* Encodage binaire compact de la position (45 octets au plus) */
+    pub fn to_compact(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN + 10);
+        out.push(VERSION);
+        out.extend_from_slice(&packed_board(self));
+        out.push(flags(self));
+        out.push(en_passant_byte(self));
+        push_varint(&mut out, self.halfmove_clock);
+        push_varint(&mut out, self.fullmove_number);
+        out
+    }
+
+    /* Décode une position écrite par `to_compact` ; le tampon doit être consommé en entier */
+    pub fn from_compact(bytes: &[u8]) -> Result<Self, CompactError> {
+        if bytes.len() < HEADER_LEN + 2 {
+            return Err(CompactError::Truncated {
+                expected: HEADER_LEN + 2,
+                found: bytes.len(),
+            });
+        }
+        if bytes[0] != VERSION {
+            return Err(CompactError::UnsupportedVersion(bytes[0]));
+        }
+
+        let mut pieces = [[None; 8]; 8];
+        for square in (0..64).filter_map(Square::from_index) {
+            let byte = bytes[1 + square.index() / 2];
+            let code = if square.index().is_multiple_of(2) {
+                byte & 0x0F
+            } else {
+                byte >> 4
+            };
+            pieces[square.rank().index()][square.file().index()] = piece_from_code(code, square)?;
+        }
+
+        let flags = bytes[33];
+        if flags & !0x1F != 0 {
+            return Err(CompactError::InvalidFlags(flags));
+        }
+        let castling_rights = castling_from_flags(flags);
+
+        let en_passant = match bytes[34] {
+            NO_EN_PASSANT => None,
+            index => match Square::from_index(index as usize) {
+                Some(square) if matches!(square.rank().index(), 2 | 5) => {
+                    Some((square.file().index() as u8, square.rank().index() as u8))
+                }
+                _ => return Err(CompactError::InvalidEnPassant(index)),
+            },
+        };
+
+        let (halfmove_clock, used) = read_varint(bytes, HEADER_LEN)?;
+        let (fullmove_number, used_fullmove) = read_varint(bytes, HEADER_LEN + used)?;
+        let end = HEADER_LEN + used + used_fullmove;
+        if end < bytes.len() {
+            return Err(CompactError::TrailingBytes(bytes.len() - end));
+        }
+
+        Ok(ChessPosition {
+            pieces,
+            active_color: color_from_flags(flags),
+            castling_rights,
+            en_passant,
+            halfmove_clock,
+            fullmove_number,
+        })
+    }
+}
+
+/* Position tenant en 40 octets : plateau en demi-octets (codes de `to_compact`), drapeaux,
+ * prise en passant et compteurs. Le compteur de demi-coups tient sur 16 bits, bien au-delà de
+ * ce que permet la règle des cinquante coups ; la conversion refuse une position qui le
+ * dépasse. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompactPosition {
+    board: [u8; 32],
+    flags: u8,
+    en_passant: u8,
+    halfmove_clock: u16,
+    fullmove_number: u32,
+}
+
+impl CompactPosition {
+    /* Pièce occupant une case */
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        let byte = self.board[square.index() / 2];
+        let code = if square.index().is_multiple_of(2) {
+            byte & 0x0F
+        } else {
+            byte >> 4
+        };
+        /* Les codes écrits par `TryFrom<&ChessPosition>` sont toujours valides */
+        piece_from_code(code, square).ok().flatten()
+    }
+
+    /* Camp qui a le trait */
+    pub fn active_color(&self) -> Color {
+        color_from_flags(self.flags)
+    }
+
+    /* Chaîne FEN, écrite directement depuis la forme compacte */
+    pub fn to_fen(&self) -> String {
+        let mut out = String::with_capacity(90);
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match Square::from_coords(file, rank).and_then(|square| self.piece_at(square)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            out.push(char::from(b'0' + empty));
+                            empty = 0;
+                        }
+                        out.push(piece.to_fen_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                out.push(char::from(b'0' + empty));
+            }
+            if rank > 0 {
+                out.push('/');
+            }
+        }
+        let color = match self.active_color() {
+            Color::White => 'w',
+            Color::Black => 'b',
+        };
+        let en_passant = Square::from_index(self.en_passant as usize);
+        let _ = write!(
+            out,
+            " {} {} {} {} {}",
+            color,
+            castling_from_flags(self.flags).to_fen(),
+            en_passant.map_or("-".to_string(), |square| square.to_string()),
+            self.halfmove_clock,
+            self.fullmove_number
+        );
+        out
+    }
+}
+
+impl TryFrom<&ChessPosition> for CompactPosition {
+    type Error = CompactError;
+
+    fn try_from(position: &ChessPosition) -> Result<Self, CompactError> {
+        let halfmove_clock = u16::try_from(position.halfmove_clock)
+            .map_err(|_| CompactError::HalfmoveClockOverflow(position.halfmove_clock))?;
+        Ok(CompactPosition {
+            board: packed_board(position),
+            flags: flags(position),
+            en_passant: en_passant_byte(position),
+            halfmove_clock,
+            fullmove_number: position.fullmove_number,
+        })
+    }
+}
+
+impl From<CompactPosition> for ChessPosition {
+    fn from(compact: CompactPosition) -> Self {
+        let mut pieces = [[None; 8]; 8];
+        for square in (0..64).filter_map(Square::from_index) {
+            pieces[square.rank().index()][square.file().index()] = compact.piece_at(square);
+        }
+        ChessPosition {
+            pieces,
+            active_color: compact.active_color(),
+            castling_rights: castling_from_flags(compact.flags),
+            en_passant: Square::from_index(compact.en_passant as usize)
+                .map(|square| (square.file().index() as u8, square.rank().index() as u8)),
+            halfmove_clock: u32::from(compact.halfmove_clock),
+            fullmove_number: compact.fullmove_number,
+        }
+    }
+}
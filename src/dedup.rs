@@ -0,0 +1,98 @@
+/* Recherche des doublons dans une collection de FEN.
+
+Deux FEN décrivent la même position quand elles ont la même clé de répétition (`repetition_key`) : les compteurs de coups n'y entrent pas, et une case de prise en passant qu'aucun pion ne peut atteindre par un coup légal est retirée avant le calcul, comme le veut la règle de la triple répétition. En mode `Symmetric`, une position et son image miroir (`color_flipped`) sont en outre regroupées, pour repérer les positions recopiées du point de vue de l'autre camp. */
+
+use crate::error::FenError;
+use crate::parser::parse_fen;
+use crate::types::ChessPosition;
+use std::collections::HashMap;
+
+/* Ce qui fait de deux positions des doublons */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DedupMode {
+    /* Même position, aux compteurs et à la prise en passant inutilisable près */
+    #[default]
+    Exact,
+    /* Même position ou image miroir, couleurs et trait échangés */
+    Symmetric,
+}
+
+/* Résultat de `find_duplicates` */
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DedupReport {
+    /* Indices des entrées décrivant une même position, par groupes d'au moins deux, dans
+     * l'ordre de leur première entrée */
+    pub groups: Vec<Vec<usize>>,
+    /* Entrées illisibles, avec leur indice */
+    pub failures: Vec<(usize, FenError)>,
+    /* Nombre de positions distinctes parmi les entrées lisibles */
+    pub distinct: usize,
+}
+
+impl DedupReport {
+    /* Nombre d'entrées en trop : celles qui répètent une position déjà vue */
+    pub fn redundant(&self) -> usize {
+        self.groups.iter().map(|group| group.len() - 1).sum()
+    }
+}
+
+/* Position sans sa case de prise en passant quand aucun coup légal ne l'utilise */
+fn without_dead_en_passant(position: &ChessPosition) -> ChessPosition {
+    let mut position = position.clone();
+    if let Some(target) = position.en_passant_square() {
+        let origins = position.en_passant_capture_origins();
+        let capturable = position
+            .legal_moves()
+            .iter()
+            .any(|mv| mv.to == target && origins.contains(mv.from));
+        if !capturable {
+            position.en_passant = None;
+        }
+    }
+    position
+}
+
+/* Clé de regroupement d'une position dans un mode */
+pub(crate) fn dedup_key(position: &ChessPosition, mode: DedupMode) -> u64 {
+    let position = without_dead_en_passant(position);
+    let key = position.repetition_key();
+    match mode {
+        DedupMode::Exact => key,
+        DedupMode::Symmetric => key.min(position.color_flipped().repetition_key()),
+    }
+}
+
+/* Doublons exacts d'une collection de FEN, indices à partir de 0 */
+pub fn find_duplicates<'a>(fens: impl Iterator<Item = &'a str>) -> DedupReport {
+    find_duplicates_with(fens, DedupMode::Exact)
+}
+
+/* Doublons d'une collection de FEN dans le mode demandé */
+pub fn find_duplicates_with<'a>(
+    fens: impl Iterator<Item = &'a str>,
+    mode: DedupMode,
+) -> DedupReport {
+    let mut failures = Vec::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut by_key: HashMap<u64, usize> = HashMap::new();
+    for (index, fen) in fens.enumerate() {
+        match parse_fen(fen) {
+            Ok(position) => {
+                let key = dedup_key(&position, mode);
+                let group = *by_key.entry(key).or_insert_with(|| {
+                    groups.push(Vec::new());
+                    groups.len() - 1
+                });
+                groups[group].push(index);
+            }
+            Err(error) => failures.push((index, error)),
+        }
+    }
+    let distinct = groups.len();
+    groups.retain(|group| group.len() > 1);
+    DedupReport {
+        groups,
+        failures,
+        distinct,
+    }
+}
@@ -0,0 +1,64 @@
+/* Diagnostics détaillés pour les erreurs de parsing FEN.
+
+Ce module rend une erreur sous la forme d'un rapport sur plusieurs lignes : la FEN est recopiée, le fragment fautif souligné, et une aide propre au champ et à la nature de l'erreur est ajoutée. */
+
+use crate::error::{FenError, FenErrorKind, FenField};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+impl FenError {
+    /* Aide associée à l'erreur, selon le champ et la nature du problème */
+    pub fn help(&self) -> Option<&'static str> {
+        let help = match (self.field, self.kind) {
+            (_, FenErrorKind::Missing) => "a FEN has six space-separated fields: placement, color, castling, en passant, halfmove clock and fullmove number",
+            (FenField::PiecePlacement, FenErrorKind::WrongLength) => "piece placement needs 8 ranks of 8 squares each, separated by '/'",
+            (FenField::PiecePlacement, _) => "ranks use KQRBNP for White, kqrbnp for Black and digits 1-8 for empty squares",
+            (FenField::ActiveColor, _) => "active color is 'w' for White or 'b' for Black",
+            (FenField::CastlingRights, FenErrorKind::Duplicate) => "each castling right may appear only once",
+            (FenField::CastlingRights, FenErrorKind::WrongLength) => "castling field is '-' or up to four letters among K, Q, k and q",
+            (FenField::CastlingRights, _) => "castling field may only contain K, Q, k, q or -",
+            (FenField::EnPassant, _) => "en passant field is '-' or a square on rank 3 or 6, such as e3",
+            (FenField::HalfmoveClock, _) | (FenField::FullmoveNumber, _) => "move counters are non-negative integers",
+            (FenField::Whole, _) => return None,
+        };
+        Some(help)
+    }
+
+    /* Rapport complet : message, FEN soulignée sous le fragment fautif et aide éventuelle */
+    pub fn diagnostic(&self, fen: &str) -> String {
+        let title = match self.kind {
+            FenErrorKind::Missing => format!("missing {}", self.field.name()),
+            kind => format!("invalid {}: {}", self.field.name(), kind.description()),
+        };
+        let label = if self.expected.is_empty() {
+            self.kind.description().to_string()
+        } else {
+            format!("expected {}, found '{}'", self.expected, self.found)
+        };
+
+        /* Colonnes en caractères, pour rester aligné avec les caractères multioctets */
+        let (column, width) = match &self.span {
+            Some(span) if span.end <= fen.len() && fen.is_char_boundary(span.start) => (
+                fen[..span.start].chars().count(),
+                self.snippet.chars().count().max(1),
+            ),
+            _ => (fen.chars().count(), 1),
+        };
+
+        let mut out = format!("error: {}\n", title);
+        out.push_str(&format!("  --> fen:1:{}\n", column + 1));
+        out.push_str("   |\n");
+        out.push_str(&format!(" 1 | {}\n", fen));
+        out.push_str(&format!(
+            "   | {}{} {}\n",
+            " ".repeat(column),
+            "^".repeat(width),
+            label
+        ));
+        if let Some(help) = self.help() {
+            out.push_str("   |\n");
+            out.push_str(&format!("   = help: {}\n", help));
+        }
+        out
+    }
+}
@@ -0,0 +1,244 @@
+/* Lecture d'un diagramme texte, inverse de `render_board`.
+
+Le diagramme compte huit lignes de cases, de la huitième rangée à la première (ou l'inverse quand les coordonnées montrent le plateau vu des noirs). Chaque case est une lettre FEN, un symbole Unicode d'échecs ou une marque de case vide (`·`, `.` ou une espace) ; les cases peuvent être séparées par des espaces ou accolées. Les coordonnées, les cadres ASCII et les grilles Unicode, les crochets de mise en évidence, les séquences de couleur ANSI et la ligne de matériel produits par `render_board` sont ignorés. Le thème creux par défaut (`PieceTheme::HOLLOW`) ne distinguant pas les couleurs, ses pièces se relisent toutes blanches. Le diagramme ne dit rien du trait, des roques ni des compteurs : `DiagramMeta` les fournit. */
+
+use crate::display::PieceTheme;
+use crate::error::{FenError, FenErrorKind, FenField};
+use crate::types::{CastlingRights, ChessPosition, Color, Piece, PieceKind, Square};
+
+/* Traits verticaux des cadres et des grilles */
+const BORDERS: &[char] = &['|', '│', '┃'];
+/* Caractères des lignes de cadre, qui ne portent aucune case */
+const RULES: &[char] = &[
+    '+', '-', '─', '━', '╭', '┬', '╮', '├', '┼', '┤', '╰', '┴', '╯', '┏', '┳', '┓', '┣', '╋', '┫',
+    '┗', '┻', '┛',
+];
+/* Crochets de mise en évidence, à la place des séparateurs */
+const BRACKETS: &[char] = &['[', ']', '(', ')', '{', '}', '<', '>', '*'];
+const EMPTY_MARKS: &[char] = &['·', '.', ' '];
+
+/* Champs d'une position absents du diagramme */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiagramMeta {
+    pub active_color: Color,
+    pub castling_rights: CastlingRights,
+    pub en_passant: Option<Square>,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+}
+
+/* Blancs au trait, sans roque ni prise en passant, compteurs à 0 et 1 */
+impl Default for DiagramMeta {
+    fn default() -> Self {
+        DiagramMeta {
+            active_color: Color::White,
+            castling_rights: CastlingRights::none(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+}
+
+impl DiagramMeta {
+    /* Champs hors plateau d'une position */
+    pub fn of(position: &ChessPosition) -> Self {
+        DiagramMeta {
+            active_color: position.active_color,
+            castling_rights: position.castling_rights,
+            en_passant: position.en_passant_square(),
+            halfmove_clock: position.halfmove_clock,
+            fullmove_number: position.fullmove_number,
+        }
+    }
+}
+
+/* Retire les séquences de couleur ANSI (`ESC [ ... m`) */
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/* Contenu d'une case, `Err(())` pour un caractère inconnu */
+fn cell(c: char) -> Result<Option<Piece>, ()> {
+    if EMPTY_MARKS.contains(&c) {
+        return Ok(None);
+    }
+    if let Some(piece) = Piece::from_fen_char(c) {
+        return Ok(Some(piece));
+    }
+    [Color::White, Color::Black]
+        .into_iter()
+        .flat_map(|color| PieceKind::ALL.map(|kind| Piece { color, kind }))
+        .find(|&piece| PieceTheme::UNICODE.glyph(piece).chars().eq([c]))
+        .map(Some)
+        .ok_or(())
+}
+
+/* Retire un numéro de rangée en tête de ligne ; renvoie le numéro et le reste */
+fn leading_label(line: &str) -> (Option<u8>, &str) {
+    let mut chars = line.chars();
+    match (chars.next(), chars.next()) {
+        (Some(digit @ '1'..='8'), next)
+            if next.is_none_or(|c| c == ' ' || BORDERS.contains(&c)) =>
+        {
+            (Some(digit as u8 - b'0'), &line[1..])
+        }
+        _ => (None, line),
+    }
+}
+
+/* Retire un numéro de rangée en fin de ligne (coordonnées sur les quatre côtés) */
+fn trailing_label(line: &str) -> &str {
+    let mut chars = line.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some('1'..='8'), Some(c)) if c == ' ' || BORDERS.contains(&c) => &line[..line.len() - 1],
+        _ => line,
+    }
+}
+
+/* Retire un bord de ligne : une espace, un trait vertical, puis une espace de marge */
+fn strip_edge<'a>(line: &'a str, strip: impl Fn(&'a str, char) -> Option<&'a str>) -> &'a str {
+    let mut line = strip(line, ' ').unwrap_or(line);
+    if let Some(rest) = BORDERS.iter().find_map(|&border| strip(line, border)) {
+        line = strip(rest, ' ').unwrap_or(rest);
+    }
+    line
+}
+
+/* Caractères des cases d'une ligne, sans coordonnées ni cadre ; le numéro de rangée est
+ * renvoyé s'il figure en tête */
+fn row_cells(line: &str) -> (Option<u8>, Vec<char>) {
+    let line: String = line
+        .chars()
+        .map(|c| if BRACKETS.contains(&c) { ' ' } else { c })
+        .collect();
+    let line = line.trim();
+    let (label, line) = leading_label(line);
+    let line = strip_edge(line, |line, c| line.strip_prefix(c));
+    let line = trailing_label(line);
+    let line = strip_edge(line, |line, c| line.strip_suffix(c));
+    let line: String = line
+        .chars()
+        .map(|c| if BORDERS.contains(&c) { ' ' } else { c })
+        .collect();
+
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let cells = if tokens.len() == 8 && tokens.iter().all(|token| token.chars().count() == 1) {
+        tokens.iter().flat_map(|token| token.chars()).collect()
+    } else if tokens.len() == 1 && tokens[0].chars().count() >= 8 {
+        tokens[0].chars().collect()
+    } else {
+        /* Espaces pour les cases vides, accolées ou séparées par une espace */
+        let chars: Vec<char> = line.trim_end().chars().collect();
+        let spaced = chars.len() > 8 && chars.iter().skip(1).step_by(2).all(|&c| c == ' ');
+        let mut cells: Vec<char> = if spaced {
+            chars.into_iter().step_by(2).collect()
+        } else {
+            chars
+        };
+        if cells.len() < 8 {
+            cells.resize(8, ' ');
+        }
+        cells
+    };
+    (label, cells)
+}
+
+/* Erreur de placement propre au diagramme */
+fn diagram_error(kind: FenErrorKind, expected: &'static str, found: String) -> FenError {
+    FenError {
+        expected,
+        found,
+        ..FenError::from((FenField::PiecePlacement, kind))
+    }
+}
+
+impl ChessPosition {
+    /* Position décrite par un diagramme texte, complétée par `meta` ; une erreur situe un
+     * caractère inconnu par sa ligne de cases et sa colonne, à partir de 1 */
+    pub fn from_board_diagram(diagram: &str, meta: DiagramMeta) -> Result<Self, FenError> {
+        let mut rows: Vec<(Option<u8>, Vec<char>)> = Vec::new();
+        let mut black_view = None;
+        for line in diagram.lines() {
+            let line = strip_ansi(line);
+            let trimmed = line.trim();
+            if trimmed.is_empty()
+                || trimmed.chars().all(|c| c == ' ' || RULES.contains(&c))
+                || trimmed.starts_with("White:")
+            {
+                continue;
+            }
+            let letters: String = trimmed.split_whitespace().collect();
+            if letters == "abcdefgh" || letters == "hgfedcba" {
+                black_view = Some(letters.starts_with('h'));
+                continue;
+            }
+            rows.push(row_cells(&line));
+        }
+
+        if rows.len() != 8 {
+            return Err(diagram_error(
+                FenErrorKind::WrongLength,
+                "8 rows of squares",
+                format!("{} rows", rows.len()),
+            ));
+        }
+        let black_view = black_view.unwrap_or(rows[0].0 == Some(1));
+
+        let mut pieces = [[None; 8]; 8];
+        for (row, (_, cells)) in rows.iter().enumerate() {
+            let squares = cells
+                .iter()
+                .enumerate()
+                .map(|(column, &c)| {
+                    cell(c).map_err(|()| {
+                        diagram_error(
+                            FenErrorKind::UnexpectedChar,
+                            "a piece letter, a chess symbol or an empty square",
+                            format!("{} (row {}, column {})", c, row + 1, column + 1),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if squares.len() != 8 {
+                return Err(diagram_error(
+                    FenErrorKind::WrongLength,
+                    "8 squares per row",
+                    format!("{} squares in row {}", squares.len(), row + 1),
+                ));
+            }
+            for (column, piece) in squares.into_iter().enumerate() {
+                let (rank, file) = if black_view {
+                    (row, 7 - column)
+                } else {
+                    (7 - row, column)
+                };
+                pieces[rank][file] = piece;
+            }
+        }
+
+        Ok(ChessPosition {
+            pieces,
+            active_color: meta.active_color,
+            castling_rights: meta.castling_rights,
+            en_passant: meta
+                .en_passant
+                .map(|square| (square.file().index() as u8, square.rank().index() as u8)),
+            halfmove_clock: meta.halfmove_clock,
+            fullmove_number: meta.fullmove_number,
+        })
+    }
+}
@@ -0,0 +1,251 @@
+/* Variantes d'écriture de la notation FEN.
+
+Outre la FEN classique, on rencontre la Shredder-FEN et la X-FEN, qui désignent la tour de roque par la lettre de sa colonne (utile aux échecs 960), l'EPD, qui omet les compteurs de coups, et la FEN à quatre champs. Les droits de roque de `ChessPosition` restent deux booléens par camp : un droit vaut pour la tour la plus extérieure du côté concerné, comme `KQkq` en X-FEN, et une lettre désignant une tour intérieure est refusée puisqu'elle ne pourrait être conservée. */
+
+use crate::epd::{EpdError, EpdRecord};
+use crate::error::{FenError, FenField};
+use crate::parser::parse_fen;
+use crate::types::{ChessPosition, Color, File, PieceKind, Rank, Square};
+use thiserror::Error;
+
+/* Variante de FEN, en lecture comme en écriture */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FenDialect {
+    /* FEN classique à six champs, roques `KQkq` */
+    Fen,
+    /* Shredder-FEN : roques écrits avec la colonne de la tour (`HAha`) */
+    Shredder,
+    /* X-FEN : `KQkq` pour la tour extérieure, la colonne sinon */
+    Xfen,
+    /* Quatre champs de position, suivis d'opérations EPD en lecture */
+    Epd,
+    /* Quatre premiers champs de la FEN, sans compteurs */
+    Fen4,
+}
+
+/* Erreur de lecture ou d'écriture dans une variante */
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum DialectError {
+    #[error("{0}")]
+    Fen(#[from] FenError),
+    #[error("{0}")]
+    Epd(#[from] EpdError),
+    #[error("expected {expected} fields, found {found}")]
+    FieldCount { expected: usize, found: usize },
+    #[error("castling right '{right}' {reason}")]
+    InvalidCastling { right: char, reason: &'static str },
+    #[error("the {color} {side} castling right has no rook to castle with")]
+    MissingRook {
+        color: &'static str,
+        side: &'static str,
+    },
+}
+
+/* Côté d'un roque, repéré par rapport au roi */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    King,
+    Queen,
+}
+
+impl FenDialect {
+    /* Toutes les variantes, dans l'ordre de l'aide */
+    pub const ALL: [FenDialect; 5] = [
+        FenDialect::Fen,
+        FenDialect::Shredder,
+        FenDialect::Xfen,
+        FenDialect::Epd,
+        FenDialect::Fen4,
+    ];
+
+    /* Nom de la variante (`fen`, `shredder`, `xfen`, `epd`, `fen4`) */
+    pub fn name(self) -> &'static str {
+        match self {
+            FenDialect::Fen => "fen",
+            FenDialect::Shredder => "shredder",
+            FenDialect::Xfen => "xfen",
+            FenDialect::Epd => "epd",
+            FenDialect::Fen4 => "fen4",
+        }
+    }
+
+    /* Variante portant ce nom */
+    pub fn from_name(name: &str) -> Option<Self> {
+        FenDialect::ALL
+            .into_iter()
+            .find(|dialect| dialect.name() == name)
+    }
+
+    /* Lit une position écrite dans cette variante ; les champs absents prennent leur valeur
+     * par défaut (compteurs à 0 et 1) */
+    pub fn parse(self, input: &str) -> Result<ChessPosition, DialectError> {
+        let input = input.trim();
+        match self {
+            FenDialect::Fen => Ok(parse_fen(input)?),
+            FenDialect::Epd => Ok(EpdRecord::parse(input)?.position),
+            FenDialect::Fen4 => {
+                let found = input.split_whitespace().count();
+                if found != 4 {
+                    return Err(DialectError::FieldCount { expected: 4, found });
+                }
+                Ok(parse_fen(&format!("{} 0 1", input))?)
+            }
+            FenDialect::Shredder | FenDialect::Xfen => {
+                let mut fields: Vec<&str> = input.split_whitespace().collect();
+                let Some(&castling) = fields.get(2) else {
+                    return Err(FenError::missing(FenField::FIELDS[fields.len()]).into());
+                };
+                fields[2] = "-";
+                let mut position = parse_fen(&fields.join(" "))?;
+                if castling != "-" {
+                    for right in castling.chars() {
+                        set_right(&mut position, right)?;
+                    }
+                }
+                Ok(position)
+            }
+        }
+    }
+
+    /* Écrit la position dans cette variante ; Shredder et X-FEN exigent une tour pour
+     * chaque droit de roque */
+    pub fn write(self, position: &ChessPosition) -> Result<String, DialectError> {
+        let fen = position.to_fen();
+        let fields: Vec<&str> = fen.split(' ').collect();
+        match self {
+            FenDialect::Fen => Ok(fen),
+            FenDialect::Epd | FenDialect::Fen4 => Ok(fields[..4].join(" ")),
+            FenDialect::Shredder | FenDialect::Xfen => {
+                let castling = castling_field(position, self == FenDialect::Shredder)?;
+                let mut fields = fields;
+                fields[2] = &castling;
+                Ok(fields.join(" "))
+            }
+        }
+    }
+}
+
+/* Rangée de départ d'un camp */
+fn back_rank(color: Color) -> Rank {
+    match color {
+        Color::White => Rank::First,
+        Color::Black => Rank::Eighth,
+    }
+}
+
+/* Colonne du roi d'un camp sur sa rangée de départ */
+fn king_file(position: &ChessPosition, color: Color) -> Option<File> {
+    File::ALL.into_iter().find(|&file| {
+        position
+            .piece_at(Square::new(file, back_rank(color)))
+            .is_some_and(|piece| piece.color == color && piece.kind == PieceKind::King)
+    })
+}
+
+/* Tour la plus extérieure d'un côté du roi, sur la rangée de départ */
+fn outer_rook(position: &ChessPosition, color: Color, side: Side) -> Option<File> {
+    let king = king_file(position, color)?.index();
+    let is_rook = |file: &File| {
+        position
+            .piece_at(Square::new(*file, back_rank(color)))
+            .is_some_and(|piece| piece.color == color && piece.kind == PieceKind::Rook)
+    };
+    match side {
+        Side::King => File::ALL[king + 1..].iter().rev().find(|f| is_rook(f)),
+        Side::Queen => File::ALL[..king].iter().find(|f| is_rook(f)),
+    }
+    .copied()
+}
+
+/* Droit de roque correspondant à un côté */
+fn right_mut(position: &mut ChessPosition, color: Color, side: Side) -> &mut bool {
+    let rights = &mut position.castling_rights;
+    match (color, side) {
+        (Color::White, Side::King) => &mut rights.white_kingside,
+        (Color::White, Side::Queen) => &mut rights.white_queenside,
+        (Color::Black, Side::King) => &mut rights.black_kingside,
+        (Color::Black, Side::Queen) => &mut rights.black_queenside,
+    }
+}
+
+/* Accorde le droit désigné par une lettre Shredder ou X-FEN */
+fn set_right(position: &mut ChessPosition, right: char) -> Result<(), DialectError> {
+    let invalid = |reason| DialectError::InvalidCastling { right, reason };
+    let color = if right.is_ascii_uppercase() {
+        Color::White
+    } else {
+        Color::Black
+    };
+    let side = match right.to_ascii_lowercase() {
+        'k' => Side::King,
+        'q' => Side::Queen,
+        letter @ 'a'..='h' => {
+            let file = File::ALL[(letter as u8 - b'a') as usize];
+            let king = king_file(position, color)
+                .ok_or_else(|| invalid("has no king on the back rank"))?;
+            let side = if file > king { Side::King } else { Side::Queen };
+            if outer_rook(position, color, side) != Some(file) {
+                return Err(invalid(if file == king {
+                    "names the king's file"
+                } else if position
+                    .piece_at(Square::new(file, back_rank(color)))
+                    .is_some_and(|piece| piece.color == color && piece.kind == PieceKind::Rook)
+                {
+                    "names an inner rook, which cannot be represented"
+                } else {
+                    "has no rook on that file"
+                }));
+            }
+            side
+        }
+        _ => return Err(invalid("is not a castling letter")),
+    };
+    let slot = right_mut(position, color, side);
+    if *slot {
+        return Err(invalid("is repeated"));
+    }
+    *slot = true;
+    Ok(())
+}
+
+/* Champ des roques en Shredder-FEN (colonnes) ou en X-FEN (`KQkq`, la tour étant
+ * toujours la plus extérieure) */
+fn castling_field(position: &ChessPosition, shredder: bool) -> Result<String, DialectError> {
+    let rights = position.castling_rights;
+    let mut field = String::new();
+    for (set, color, side) in [
+        (rights.white_kingside, Color::White, Side::King),
+        (rights.white_queenside, Color::White, Side::Queen),
+        (rights.black_kingside, Color::Black, Side::King),
+        (rights.black_queenside, Color::Black, Side::Queen),
+    ] {
+        if !set {
+            continue;
+        }
+        let Some(file) = outer_rook(position, color, side) else {
+            return Err(DialectError::MissingRook {
+                color: color.name(),
+                side: match side {
+                    Side::King => "kingside",
+                    Side::Queen => "queenside",
+                },
+            });
+        };
+        let letter = if shredder {
+            file.to_char()
+        } else if side == Side::King {
+            'k'
+        } else {
+            'q'
+        };
+        field.push(match color {
+            Color::White => letter.to_ascii_uppercase(),
+            Color::Black => letter,
+        });
+    }
+    if field.is_empty() {
+        field.push('-');
+    }
+    Ok(field)
+}
@@ -0,0 +1,178 @@
+/* Comparaison de deux positions d'échecs.
+
+Ce module calcule les différences entre deux positions (cases et métadonnées) et les présente côte à côte. */
+
+use crate::display::{DisplayOptions, HighlightKind};
+use crate::types::{CastlingRights, ChessPosition, Color, Piece, Square};
+
+/* Changement d'une case entre deux positions */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SquareChange {
+    pub square: Square,
+    pub before: Option<Piece>,
+    pub after: Option<Piece>,
+}
+
+/* Ensemble des différences entre deux positions */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionDiff {
+    /* Cases dont le contenu diffère, de a1 à h8 */
+    pub squares: Vec<SquareChange>,
+    /* Couleur active (avant, après) si elle diffère */
+    pub active_color: Option<(Color, Color)>,
+    /* Droits de roque (avant, après) s'ils diffèrent */
+    pub castling_rights: Option<(CastlingRights, CastlingRights)>,
+    /* Case de prise en passant (avant, après) si elle diffère */
+    pub en_passant: Option<(Option<Square>, Option<Square>)>,
+    /* Compteur de demi-coups (avant, après) s'il diffère */
+    pub halfmove_clock: Option<(u32, u32)>,
+    /* Numéro du tour (avant, après) s'il diffère */
+    pub fullmove_number: Option<(u32, u32)>,
+}
+
+/* Renvoie la paire (avant, après) si les deux valeurs diffèrent */
+fn changed<T: PartialEq>(before: T, after: T) -> Option<(T, T)> {
+    if before != after {
+        Some((before, after))
+    } else {
+        None
+    }
+}
+
+impl PositionDiff {
+    /* Calcule les différences entre deux positions */
+    pub fn between(a: &ChessPosition, b: &ChessPosition) -> Self {
+        let squares = (0..64)
+            .filter_map(Square::from_index)
+            .filter_map(|square| {
+                let (before, after) = (a.piece_at(square), b.piece_at(square));
+                changed(before, after).map(|_| SquareChange {
+                    square,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+
+        Self {
+            squares,
+            active_color: changed(a.active_color, b.active_color),
+            castling_rights: changed(a.castling_rights, b.castling_rights),
+            en_passant: changed(a.en_passant_square(), b.en_passant_square()),
+            halfmove_clock: changed(a.halfmove_clock, b.halfmove_clock),
+            fullmove_number: changed(a.fullmove_number, b.fullmove_number),
+        }
+    }
+
+    /* Vrai si les deux positions sont identiques */
+    pub fn is_empty(&self) -> bool {
+        self.squares.is_empty()
+            && self.active_color.is_none()
+            && self.castling_rights.is_none()
+            && self.en_passant.is_none()
+            && self.halfmove_clock.is_none()
+            && self.fullmove_number.is_none()
+    }
+
+    /* Liste textuelle des changements, une ligne par différence */
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .squares
+            .iter()
+            .map(|change| {
+                format!(
+                    "{}: {} -> {}",
+                    change.square,
+                    describe_piece(change.before),
+                    describe_piece(change.after)
+                )
+            })
+            .collect();
+
+        if let Some((before, after)) = self.active_color {
+            lines.push(format!(
+                "Active color: {} -> {}",
+                color_name(before),
+                color_name(after)
+            ));
+        }
+        if let Some((before, after)) = self.castling_rights {
+            lines.push(format!(
+                "Castling rights: {} -> {}",
+                before.to_fen(),
+                after.to_fen()
+            ));
+        }
+        if let Some((before, after)) = self.en_passant {
+            lines.push(format!(
+                "En passant: {} -> {}",
+                describe_square(before),
+                describe_square(after)
+            ));
+        }
+        if let Some((before, after)) = self.halfmove_clock {
+            lines.push(format!("Halfmove clock: {} -> {}", before, after));
+        }
+        if let Some((before, after)) = self.fullmove_number {
+            lines.push(format!("Fullmove number: {} -> {}", before, after));
+        }
+        lines
+    }
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "White",
+        Color::Black => "Black",
+    }
+}
+
+fn describe_piece(piece: Option<Piece>) -> String {
+    match piece {
+        Some(piece) => piece.to_fen_char().to_string(),
+        None => "empty".to_string(),
+    }
+}
+
+fn describe_square(square: Option<Square>) -> String {
+    match square {
+        Some(square) => square.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/* Rend deux positions côte à côte, cases différentes marquées par `*`, suivies de la liste des changements */
+pub fn render_diff(a: &ChessPosition, b: &ChessPosition) -> String {
+    let diff = PositionDiff::between(a, b);
+
+    let mut options = DisplayOptions::default();
+    for change in &diff.squares {
+        options.highlight(change.square, HighlightKind::Changed);
+    }
+    let left: Vec<String> = a.render_board(&options).lines().map(String::from).collect();
+    let right: Vec<String> = b.render_board(&options).lines().map(String::from).collect();
+    let width = left
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    out.push_str(&format!("{:<width$}   {}\n", "Position A", "Position B"));
+    for (l, r) in left.iter().zip(&right) {
+        let line = format!("{:<width$}   {}", l, r);
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+
+    out.push('\n');
+    if diff.is_empty() {
+        out.push_str("Positions are identical\n");
+    } else {
+        out.push_str("Differences:\n");
+        for line in diff.describe() {
+            out.push_str(&format!("  {}\n", line));
+        }
+    }
+    out
+}
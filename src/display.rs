@@ -2,40 +2,629 @@
 
 Ce module fournit des méthodes pour visualiser une position ChessPosition sous forme textuelle dans un terminal. */
 
-use crate::types::{ChessPosition, PieceKind};
+use crate::types::{ChessPosition, Color, Move, Piece, PieceKind, Square, SquareOrder};
+
+/* Orientation du plateau à l'affichage */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Orientation {
+    /* Les blancs en bas (affichage standard) */
+    #[default]
+    White,
+    /* Les noirs en bas */
+    Black,
+    /* Le camp ayant le trait en bas */
+    SideToMove,
+}
+
+impl Orientation {
+    /* Détermine la couleur affichée en bas du plateau */
+    pub fn bottom_color(self, active_color: Color) -> Color {
+        match self {
+            Orientation::White => Color::White,
+            Orientation::Black => Color::Black,
+            Orientation::SideToMove => active_color,
+        }
+    }
+}
+
+/* Style de bordure autour du plateau */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BorderStyle {
+    /* Aucune bordure */
+    None,
+    /* Cadre ASCII simple en `+---+` et `|` */
+    #[default]
+    Ascii,
+    /* Grille Unicode à coins arrondis (`╭─┬─╮`), un trait entre chaque case */
+    Rounded,
+    /* Grille Unicode en traits épais (`┏━┳━┓`), un trait entre chaque case */
+    Heavy,
+}
+
+/* Caractères de tracé d'une grille : traits puis coins et jonctions */
+struct GridChars {
+    horizontal: &'static str,
+    vertical: &'static str,
+    /* Gauche, milieu et droite des lignes du haut, intermédiaires et du bas */
+    top: [&'static str; 3],
+    middle: [&'static str; 3],
+    bottom: [&'static str; 3],
+}
+
+impl BorderStyle {
+    /* Caractères de la grille, pour les styles qui séparent chaque case */
+    fn grid_chars(self) -> Option<GridChars> {
+        match self {
+            BorderStyle::None | BorderStyle::Ascii => None,
+            BorderStyle::Rounded => Some(GridChars {
+                horizontal: "─",
+                vertical: "│",
+                top: ["╭", "┬", "╮"],
+                middle: ["├", "┼", "┤"],
+                bottom: ["╰", "┴", "╯"],
+            }),
+            BorderStyle::Heavy => Some(GridChars {
+                horizontal: "━",
+                vertical: "┃",
+                top: ["┏", "┳", "┓"],
+                middle: ["┣", "╋", "┫"],
+                bottom: ["┗", "┻", "┛"],
+            }),
+        }
+    }
+}
+
+/* Côtés du plateau portant les coordonnées */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CoordinateSides {
+    /* Rangées à gauche, colonnes en bas */
+    #[default]
+    LeftBottom,
+    /* Coordonnées sur les quatre côtés */
+    All,
+}
+
+/* Jeu de glyphes associant chaque pièce (couleur, type) à sa représentation */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PieceTheme {
+    /* Glyphes blancs, dans l'ordre roi, dame, tour, fou, cavalier, pion */
+    pub white: [&'static str; 6],
+    /* Glyphes noirs, dans le même ordre */
+    pub black: [&'static str; 6],
+}
+
+impl PieceTheme {
+    /* Symboles Unicode : pièces blanches creuses, noires pleines */
+    pub const UNICODE: PieceTheme = PieceTheme {
+        white: ["♔", "♕", "♖", "♗", "♘", "♙"],
+        black: ["♚", "♛", "♜", "♝", "♞", "♟"],
+    };
+
+    /* Lettres FEN : majuscules pour les blancs, minuscules pour les noirs */
+    pub const ASCII: PieceTheme = PieceTheme {
+        white: ["K", "Q", "R", "B", "N", "P"],
+        black: ["k", "q", "r", "b", "n", "p"],
+    };
+
+    /* Symboles Unicode inversés, pour les terminaux à fond sombre */
+    pub const UNICODE_INVERTED: PieceTheme = PieceTheme {
+        white: ["♚", "♛", "♜", "♝", "♞", "♟"],
+        black: ["♔", "♕", "♖", "♗", "♘", "♙"],
+    };
+
+    /* Symboles Unicode creux pour les deux camps, comme l'affichage historique : les couleurs
+     * ne s'y distinguent pas */
+    pub const HOLLOW: PieceTheme = PieceTheme {
+        white: ["♔", "♕", "♖", "♗", "♘", "♙"],
+        black: ["♔", "♕", "♖", "♗", "♘", "♙"],
+    };
+
+    /* Renvoie le glyphe d'une pièce */
+    pub fn glyph(&self, piece: Piece) -> &'static str {
+        let glyphs = match piece.color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        };
+        let index = match piece.kind {
+            PieceKind::King => 0,
+            PieceKind::Queen => 1,
+            PieceKind::Rook => 2,
+            PieceKind::Bishop => 3,
+            PieceKind::Knight => 4,
+            PieceKind::Pawn => 5,
+        };
+        glyphs[index]
+    }
+}
+
+/* Le thème creux est utilisé par défaut, pour garder l'affichage historique */
+impl Default for PieceTheme {
+    fn default() -> Self {
+        PieceTheme::HOLLOW
+    }
+}
+
+/* Style visuel du plateau : coordonnées, bordure et espacement */
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardStyle {
+    /* Affiche les numéros de rangées et lettres de colonnes */
+    pub show_coordinates: bool,
+    /* Côtés sur lesquels les coordonnées sont affichées */
+    pub coordinate_sides: CoordinateSides,
+    /* Style du cadre */
+    pub border: BorderStyle,
+    /* Largeur d'une case en caractères (la pièce est centrée) */
+    pub square_width: usize,
+    /* Caractère utilisé pour une case vide */
+    pub empty_square_char: char,
+    /* Glyphes des pièces */
+    pub theme: PieceTheme,
+    /* Espaces de part et d'autre de la pièce dans les grilles Unicode.
+     * Les glyphes d'échecs comptent pour une colonne mais certains terminaux
+     * les affichent sur deux : la grille reste alignée avec des pièces ASCII,
+     * et une marge nulle compense l'élargissement sur ces terminaux. */
+    pub square_padding: usize,
+    /* Ajoute sous le plateau une ligne résumant le matériel de chaque camp */
+    pub show_material: bool,
+}
+
+/* Style par défaut, identique à l'affichage historique */
+impl Default for BoardStyle {
+    fn default() -> Self {
+        Self {
+            show_coordinates: true,
+            coordinate_sides: CoordinateSides::LeftBottom,
+            border: BorderStyle::Ascii,
+            square_width: 1,
+            empty_square_char: '·',
+            theme: PieceTheme::HOLLOW,
+            square_padding: 1,
+            show_material: false,
+        }
+    }
+}
+
+/* Mode de rendu des cases */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DisplayMode {
+    /* Texte brut, sans séquence d'échappement */
+    #[default]
+    Plain,
+    /* Damier coloré via des séquences ANSI (SGR) */
+    Color,
+}
+
+/* Séquences SGR utilisées par le mode coloré */
+pub const ANSI_LIGHT_SQUARE: &str = "\x1b[48;5;180m";
+pub const ANSI_DARK_SQUARE: &str = "\x1b[48;5;94m";
+pub const ANSI_WHITE_PIECE: &str = "\x1b[1;97m";
+pub const ANSI_BLACK_PIECE: &str = "\x1b[2;30m";
+pub const ANSI_RESET: &str = "\x1b[0m";
+
+/* Nature d'une case mise en évidence */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightKind {
+    /* Case de départ ou d'arrivée du dernier coup */
+    LastMove,
+    /* Roi en échec */
+    Check,
+    /* Case cible de prise en passant */
+    EnPassant,
+    /* Marque générique */
+    Marked,
+    /* Case différente entre deux positions comparées */
+    Changed,
+}
+
+impl HighlightKind {
+    /* Crochets entourant la case en mode texte brut */
+    pub fn brackets(self) -> (char, char) {
+        match self {
+            HighlightKind::LastMove => ('[', ']'),
+            HighlightKind::Check => ('(', ')'),
+            HighlightKind::EnPassant => ('<', '>'),
+            HighlightKind::Marked => ('{', '}'),
+            HighlightKind::Changed => ('*', '*'),
+        }
+    }
+
+    /* Couleur de fond de la case en mode coloré */
+    pub fn ansi_background(self) -> &'static str {
+        match self {
+            HighlightKind::LastMove => ANSI_LAST_MOVE_SQUARE,
+            HighlightKind::Check => ANSI_CHECK_SQUARE,
+            HighlightKind::EnPassant => ANSI_EN_PASSANT_SQUARE,
+            HighlightKind::Marked | HighlightKind::Changed => ANSI_MARKED_SQUARE,
+        }
+    }
+}
+
+/* Fonds des cases mises en évidence en mode coloré */
+pub const ANSI_LAST_MOVE_SQUARE: &str = "\x1b[48;5;143m";
+pub const ANSI_CHECK_SQUARE: &str = "\x1b[48;5;160m";
+pub const ANSI_EN_PASSANT_SQUARE: &str = "\x1b[48;5;74m";
+pub const ANSI_MARKED_SQUARE: &str = "\x1b[48;5;71m";
+
+/* Options d'affichage d'une position */
+#[derive(Debug, Clone, Default)]
+pub struct DisplayOptions {
+    /* Orientation du plateau */
+    pub orientation: Orientation,
+    /* Style visuel du plateau */
+    pub style: BoardStyle,
+    /* Texte brut ou damier coloré */
+    pub mode: DisplayMode,
+    /* Cases mises en évidence */
+    pub highlights: Vec<(Square, HighlightKind)>,
+}
+
+impl DisplayOptions {
+    /* Met une case en évidence (remplace une marque existante sur la même case) */
+    pub fn highlight(&mut self, square: Square, kind: HighlightKind) {
+        self.highlights.retain(|(s, _)| *s != square);
+        self.highlights.push((square, kind));
+    }
+
+    /* Met en évidence les cases de départ et d'arrivée d'un coup */
+    pub fn highlight_move(&mut self, mv: Move) {
+        self.highlight(mv.from, HighlightKind::LastMove);
+        self.highlight(mv.to, HighlightKind::LastMove);
+    }
+
+    /* Renvoie la marque d'une case, s'il y en a une */
+    pub fn highlight_at(&self, square: Square) -> Option<HighlightKind> {
+        self.highlights
+            .iter()
+            .find(|(s, _)| *s == square)
+            .map(|(_, kind)| *kind)
+    }
+}
+
+/* Centre un glyphe dans une case de la largeur donnée */
+fn centered(glyph: &str, width: usize) -> String {
+    let padding = width.saturating_sub(glyph.chars().count());
+    let left = padding / 2;
+    let right = padding - left;
+    format!("{}{}{}", " ".repeat(left), glyph, " ".repeat(right))
+}
+
+/* Séparateur entre deux cases, portant les crochets des cases mises en évidence */
+fn slot(left: Option<HighlightKind>, right: Option<HighlightKind>, default: &str) -> String {
+    match (left, right) {
+        (Some(left), Some(right)) if left.brackets().1 == right.brackets().0 => {
+            left.brackets().1.to_string()
+        }
+        (Some(_), Some(_)) => "|".to_string(),
+        (Some(kind), None) => kind.brackets().1.to_string(),
+        (None, Some(kind)) => kind.brackets().0.to_string(),
+        (None, None) => default.to_string(),
+    }
+}
 
-/* Affiche la position en ASCII/Unicode dans le terminal */
 impl ChessPosition {
-    pub fn display_ascii(&self) {
-        println!("  +-----------------+");
-        /* Convertit une pièce en caractère Unicode */
-        for rank in (0..8).rev() {
-            print!("{} | ", rank + 1);
-            for file in 0..8 {
-                let c = match self.pieces[rank as usize][file as usize] {
-                    Some(piece) => match (piece.color, piece.kind) {
-                        (_, PieceKind::King) => '♔',
-                        (_, PieceKind::Queen) => '♕',
-                        (_, PieceKind::Rook) => '♖',
-                        (_, PieceKind::Bishop) => '♗',
-                        (_, PieceKind::Knight) => '♘',
-                        (_, PieceKind::Pawn) => '♙',
-                    },
-                    None => '·',
+    /* Rend le plateau sous forme de chaîne selon les options données */
+    pub fn render_board(&self, options: &DisplayOptions) -> String {
+        let style = &options.style;
+        let mut out = match style.border.grid_chars() {
+            Some(chars) => self.render_grid(options, &chars),
+            None => self.render_frame(options),
+        };
+        if style.show_material {
+            out.push_str(&self.material_footer(&style.theme));
+            out.push('\n');
+        }
+        out
+    }
+
+    /* Ligne de matériel, par exemple `White: ♕×1 ♙×8  |  Black: ♛×1 (+8 pawns for White)` */
+    pub fn material_footer(&self, theme: &PieceTheme) -> String {
+        let material = self.material();
+        let side = |color: Color| {
+            let pieces: Vec<String> = PieceKind::ALL
+                .iter()
+                .filter(|&&kind| kind != PieceKind::King)
+                .filter(|&&kind| material.count(color, kind) > 0)
+                .map(|&kind| {
+                    let glyph = theme.glyph(Piece { color, kind });
+                    format!("{}×{}", glyph, material.count(color, kind))
+                })
+                .collect();
+            if pieces.is_empty() {
+                "-".to_string()
+            } else {
+                pieces.join(" ")
+            }
+        };
+        let imbalance = material.imbalance();
+        let summary = match imbalance {
+            0 => "material is equal".to_string(),
+            _ => {
+                let leader = if imbalance > 0 { "White" } else { "Black" };
+                let pawns = imbalance.unsigned_abs();
+                let unit = if pawns == 1 { "pawn" } else { "pawns" };
+                format!("+{} {} for {}", pawns, unit, leader)
+            }
+        };
+        format!(
+            "White: {}  |  Black: {} ({})",
+            side(Color::White),
+            side(Color::Black),
+            summary
+        )
+    }
+
+    /* Rend le plateau sans grille, avec ou sans cadre ASCII */
+    fn render_frame(&self, options: &DisplayOptions) -> String {
+        let style = &options.style;
+        let (ranks, files) = self.display_order(options);
+
+        let framed = style.border == BorderStyle::Ascii;
+        let all_sides = style.show_coordinates && style.coordinate_sides == CoordinateSides::All;
+        let label_pad = if style.show_coordinates { "  " } else { "" };
+        let width = style.square_width.max(1);
+
+        /* En mode coloré, les cases sont des blocs contigus avec une marge interne */
+        let colored = options.mode == DisplayMode::Color;
+        let cell_width = if colored { width + 2 } else { width };
+        let separator = if colored { "" } else { " " };
+        let inner_width = 8 * cell_width + 7 * separator.len() + 2;
+
+        /* Sans cadre ni coordonnées, la marge gauche n'existe que si un crochet l'occupe */
+        let first_file = files[0] as u8;
+        let leading_margin = framed
+            || style.show_coordinates
+            || (!colored
+                && options
+                    .highlights
+                    .iter()
+                    .any(|(square, _)| square.file().index() as u8 == first_file));
+
+        /* Ligne des lettres de colonnes, alignée sur le centre des cases */
+        let file_row = {
+            let letters: Vec<String> = files
+                .iter()
+                .map(|&file| centered(&((b'a' + file as u8) as char).to_string(), cell_width))
+                .collect();
+            let line = format!(
+                "{}{}{}",
+                label_pad,
+                if framed { "  " } else { "" },
+                letters.join(separator)
+            );
+            format!("{}\n", line.trim_end())
+        };
+        let border_line = format!("{}+{}+\n", label_pad, "-".repeat(inner_width));
+
+        let mut out = String::new();
+        if all_sides {
+            out.push_str(&file_row);
+        }
+        if framed {
+            out.push_str(&border_line);
+        }
+        for &rank in &ranks {
+            let marks: Vec<Option<HighlightKind>> = files
+                .iter()
+                .map(|&file| {
+                    Square::from_coords(file as u8, rank as u8)
+                        .and_then(|square| options.highlight_at(square))
+                })
+                .collect();
+            let cells: Vec<String> = files
+                .iter()
+                .zip(&marks)
+                .map(|(&file, mark)| {
+                    let square = self.pieces[rank][file];
+                    let empty = style.empty_square_char.to_string();
+                    let glyph = match square {
+                        Some(piece) => style.theme.glyph(piece),
+                        None => empty.as_str(),
+                    };
+                    if !colored {
+                        return centered(glyph, width);
+                    }
+                    /* a1 est une case sombre : (colonne + rangée) paire */
+                    let background = match mark {
+                        Some(kind) => kind.ansi_background(),
+                        None if (file + rank) % 2 == 0 => ANSI_DARK_SQUARE,
+                        None => ANSI_LIGHT_SQUARE,
+                    };
+                    let foreground = match square {
+                        Some(piece) if piece.color == Color::White => ANSI_WHITE_PIECE,
+                        Some(_) => ANSI_BLACK_PIECE,
+                        None => "",
+                    };
+                    format!(
+                        "{}{}{}{}",
+                        background,
+                        foreground,
+                        centered(glyph, cell_width),
+                        ANSI_RESET
+                    )
+                })
+                .collect();
+
+            /* Les crochets de mise en évidence n'existent qu'en mode texte brut */
+            let mark_at = |index: usize| {
+                if colored {
+                    None
+                } else {
+                    marks.get(index).copied().flatten()
+                }
+            };
+
+            let mut line = String::new();
+            if style.show_coordinates {
+                line.push_str(&(rank + 1).to_string());
+                if framed {
+                    line.push_str(" |");
+                }
+            } else if framed {
+                line.push('|');
+            }
+            let margin = if leading_margin { " " } else { "" };
+            line.push_str(&slot(None, mark_at(0), margin));
+            for (i, cell) in cells.iter().enumerate() {
+                line.push_str(cell);
+                let default = if i == 7 { " " } else { separator };
+                line.push_str(&slot(mark_at(i), mark_at(i + 1), default));
+            }
+            if framed {
+                line.push('|');
+            }
+            if all_sides {
+                line.push_str(&format!(" {}", rank + 1));
+            }
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        if framed {
+            out.push_str(&border_line);
+        }
+        if style.show_coordinates {
+            out.push_str(&file_row);
+        }
+        out
+    }
+
+    /* Rangées (de haut en bas) et colonnes (de gauche à droite) à afficher */
+    fn display_order(&self, options: &DisplayOptions) -> (Vec<usize>, Vec<usize>) {
+        /* Ordre de lecture FEN, renversé quand les noirs sont en bas : h1 en haut à gauche */
+        let squares: Vec<Square> =
+            if options.orientation.bottom_color(self.active_color) == Color::Black {
+                Square::iter(SquareOrder::Fen).rev().collect()
+            } else {
+                Square::iter(SquareOrder::Fen).collect()
+            };
+        let ranks = squares
+            .iter()
+            .step_by(8)
+            .map(|square| square.rank().index())
+            .collect();
+        let files = squares[..8]
+            .iter()
+            .map(|square| square.file().index())
+            .collect();
+        (ranks, files)
+    }
+
+    /* Rend le plateau dans une grille Unicode, chaque case entourée de traits */
+    fn render_grid(&self, options: &DisplayOptions, chars: &GridChars) -> String {
+        let style = &options.style;
+        let (ranks, files) = self.display_order(options);
+        let all_sides = style.show_coordinates && style.coordinate_sides == CoordinateSides::All;
+        let label_pad = if style.show_coordinates { "  " } else { "" };
+        let width = style.square_width.max(1);
+        let padding = style.square_padding;
+        let cell_width = width + 2 * padding;
+        let colored = options.mode == DisplayMode::Color;
+
+        let rule = |[left, middle, right]: [&str; 3]| {
+            let segments = vec![chars.horizontal.repeat(cell_width); 8];
+            format!("{}{}{}{}\n", label_pad, left, segments.join(middle), right)
+        };
+        let file_row = {
+            let letters: Vec<String> = files
+                .iter()
+                .map(|&file| centered(&((b'a' + file as u8) as char).to_string(), cell_width))
+                .collect();
+            let line = format!("{} {}", label_pad, letters.join(" "));
+            format!("{}\n", line.trim_end())
+        };
+
+        let mut out = String::new();
+        if all_sides {
+            out.push_str(&file_row);
+        }
+        out.push_str(&rule(chars.top));
+        for (row, &rank) in ranks.iter().enumerate() {
+            if row > 0 {
+                out.push_str(&rule(chars.middle));
+            }
+            let mut line = String::new();
+            if style.show_coordinates {
+                line.push_str(&format!("{} ", rank + 1));
+            }
+            line.push_str(chars.vertical);
+            for &file in &files {
+                let square = self.pieces[rank][file];
+                let empty = style.empty_square_char.to_string();
+                let glyph = match square {
+                    Some(piece) => style.theme.glyph(piece),
+                    None => empty.as_str(),
                 };
-                print!("{} ", c);
+                let mark = Square::from_coords(file as u8, rank as u8)
+                    .and_then(|square| options.highlight_at(square));
+                let content = centered(glyph, width);
+                if colored {
+                    let background = match mark {
+                        Some(kind) => kind.ansi_background(),
+                        None if (file + rank) % 2 == 0 => ANSI_DARK_SQUARE,
+                        None => ANSI_LIGHT_SQUARE,
+                    };
+                    let foreground = match square {
+                        Some(piece) if piece.color == Color::White => ANSI_WHITE_PIECE,
+                        Some(_) => ANSI_BLACK_PIECE,
+                        None => "",
+                    };
+                    line.push_str(&format!(
+                        "{}{}{}{}",
+                        background,
+                        foreground,
+                        centered(&content, cell_width),
+                        ANSI_RESET
+                    ));
+                } else {
+                    /* Les crochets prennent la place de la marge quand elle existe */
+                    let margin = " ".repeat(padding.saturating_sub(1));
+                    match mark {
+                        Some(kind) if padding > 0 => {
+                            let (open, close) = kind.brackets();
+                            line.push_str(&format!(
+                                "{}{}{}{}{}",
+                                open, margin, content, margin, close
+                            ));
+                        }
+                        _ => line.push_str(&centered(&content, cell_width)),
+                    }
+                }
+                line.push_str(chars.vertical);
             }
-            println!("|");
+            if all_sides {
+                line.push_str(&format!(" {}", rank + 1));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out.push_str(&rule(chars.bottom));
+        if style.show_coordinates {
+            out.push_str(&file_row);
         }
-        println!("  +-----------------+");
-        println!("    a b c d e f g h");
+        out
+    }
+
+    /* Affiche la position en ASCII/Unicode dans le terminal */
+    pub fn display_ascii(&self) {
+        self.display_with_options(&DisplayOptions::default());
+    }
+
+    /* Affiche la position en mettant en évidence les cases du dernier coup */
+    pub fn display_with_last_move(&self, mv: Move) {
+        let mut options = DisplayOptions::default();
+        options.highlight_move(mv);
+        self.display_with_options(&options);
+    }
+
+    /* Affiche la position selon les options données */
+    pub fn display_with_options(&self, options: &DisplayOptions) {
+        print!("{}", self.render_board(options));
 
         /* Formate la couleur active */
         println!(
             "\nActive color: {}",
             match self.active_color {
-                crate::types::Color::White => "White",
-                crate::types::Color::Black => "Black",
+                Color::White => "White",
+                Color::Black => "Black",
             }
         );
 
@@ -67,8 +656,8 @@ impl ChessPosition {
         /* Formate la case de prise en passant */
         println!(
             "En passant: {}",
-            match self.en_passant {
-                Some((file, rank)) => format!("{}{}", (b'a' + file) as char, rank + 1),
+            match self.en_passant_square() {
+                Some(square) => square.to_string(),
                 None => "-".to_string(),
             }
         );
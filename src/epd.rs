@@ -0,0 +1,143 @@
+/* Lecture des enregistrements EPD (Extended Position Description).
+
+Un enregistrement EPD reprend les quatre premiers champs d'une FEN (placement, trait, roques, prise en passant), suivis d'opérations `opcode opérandes;` telles que `bm Qg6; id "WAC.001";`. Les compteurs de coups n'y figurent pas : ils valent 0 et 1 sauf si les opérations `hmvc` et `fmvn` les précisent. */
+
+use crate::error::{FenError, FenField};
+use crate::parser::parse_fen;
+use crate::types::ChessPosition;
+use thiserror::Error;
+
+/* Erreur de lecture d'un enregistrement EPD */
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum EpdError {
+    #[error("{0}")]
+    Fen(#[from] FenError),
+    #[error("malformed operation '{0}'")]
+    MalformedOperation(String),
+    #[error("unterminated string in operation '{0}'")]
+    UnterminatedString(String),
+    #[error("invalid {opcode} operand '{value}': expected a non-negative integer")]
+    InvalidCounter { opcode: &'static str, value: String },
+}
+
+/* Enregistrement EPD : position et opérations, dans l'ordre du texte */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpdRecord {
+    /* Quatre champs de position, tels qu'écrits */
+    pub fields: String,
+    /* Position complète, compteurs tirés de `hmvc` et `fmvn` */
+    pub position: ChessPosition,
+    /* Opérations (opcode, opérandes), guillemets retirés */
+    pub operations: Vec<(String, String)>,
+}
+
+/* Découpe les quatre premiers champs ; renvoie (champs, reste de la ligne) */
+fn split_fields(line: &str) -> (&str, &str) {
+    let mut end = 0;
+    let mut count = 0;
+    let mut in_token = false;
+    for (index, c) in line.char_indices() {
+        if c.is_whitespace() {
+            if in_token {
+                count += 1;
+                in_token = false;
+                end = index;
+                if count == 4 {
+                    break;
+                }
+            }
+        } else {
+            in_token = true;
+            end = index + c.len_utf8();
+        }
+    }
+    (line[..end].trim(), line[end..].trim())
+}
+
+/* Découpe les opérations `opcode opérandes;` ; un `;` entre guillemets n'en termine pas une */
+fn parse_operations(text: &str) -> Result<Vec<(String, String)>, EpdError> {
+    let mut operations = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chunks = Vec::new();
+    for c in text.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ';' if !quoted => chunks.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        return Err(EpdError::UnterminatedString(current.trim().to_string()));
+    }
+    /* Le `;` final est souvent omis sur la dernière opération */
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+
+    for chunk in chunks {
+        let chunk = chunk.trim();
+        let (opcode, operands) = chunk.split_once(char::is_whitespace).unwrap_or((chunk, ""));
+        let valid_opcode = opcode
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic())
+            && opcode
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_opcode {
+            return Err(EpdError::MalformedOperation(chunk.to_string()));
+        }
+        let operands = operands.trim().replace('"', "");
+        operations.push((opcode.to_string(), operands));
+    }
+    Ok(operations)
+}
+
+impl EpdRecord {
+    /* Lit un enregistrement EPD d'une ligne */
+    pub fn parse(line: &str) -> Result<Self, EpdError> {
+        let (fields, rest) = split_fields(line.trim());
+        let count = fields.split_whitespace().count();
+        if count < 4 {
+            return Err(FenError::missing(FenField::FIELDS[count]).into());
+        }
+        let operations = parse_operations(rest)?;
+
+        let counter = |opcode: &'static str, default: u32| -> Result<u32, EpdError> {
+            match operations.iter().find(|(name, _)| name == opcode) {
+                Some((_, value)) => value.parse().map_err(|_| EpdError::InvalidCounter {
+                    opcode,
+                    value: value.clone(),
+                }),
+                None => Ok(default),
+            }
+        };
+        let halfmove = counter("hmvc", 0)?;
+        let fullmove = counter("fmvn", 1)?;
+        let position = parse_fen(&format!("{} {} {}", fields, halfmove, fullmove))?;
+
+        Ok(Self {
+            fields: fields.to_string(),
+            position,
+            operations,
+        })
+    }
+
+    /* Opérandes d'une opération, si elle est présente */
+    pub fn operation(&self, opcode: &str) -> Option<&str> {
+        self.operations
+            .iter()
+            .find(|(name, _)| name == opcode)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /* FEN à six champs de la position décrite */
+    pub fn to_fen(&self) -> String {
+        self.position.to_fen()
+    }
+}
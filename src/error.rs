@@ -1,41 +1,210 @@
 /* Module de gestion des erreurs pour le parseur FEN.
 
-Définit le type `FenError` et ses variantes pour représenter les différentes erreurs pouvant survenir lors du parsing FEN. */
+Définit le type `FenError`, qui combine le champ concerné (`FenField`) et la nature du problème (`FenErrorKind`). */
 
-use thiserror::Error;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use core::ops::Range;
 
-/* Enumération des erreurs spécifiques au parsing FEN */
-#[derive(Debug, Error)]
-pub enum FenError {
-    /* Erreur de format général de la chaîne FEN */
-    #[error("Invalid FEN format: {0}")]
-    InvalidFormat(String),
+/* Nombre maximal de caractères de contexte conservés de chaque côté du fragment fautif */
+const CONTEXT_WIDTH: usize = 12;
 
-    /* Erreur dans la partie placement des pièces */
-    #[error("Invalid piece placement: {0}")]
-    InvalidPiecePlacement(String),
+/* Composant de la FEN concerné par une erreur */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FenField {
+    PiecePlacement,
+    ActiveColor,
+    CastlingRights,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+    /* La chaîne entière, quand l'erreur ne relève d'aucun champ précis */
+    Whole,
+}
+
+impl FenField {
+    /* Les six champs d'une FEN, dans l'ordre */
+    pub const FIELDS: [FenField; 6] = [
+        FenField::PiecePlacement,
+        FenField::ActiveColor,
+        FenField::CastlingRights,
+        FenField::EnPassant,
+        FenField::HalfmoveClock,
+        FenField::FullmoveNumber,
+    ];
+
+    /* Nom du champ tel qu'il apparaît dans les messages */
+    pub fn name(self) -> &'static str {
+        match self {
+            FenField::PiecePlacement => "piece placement",
+            FenField::ActiveColor => "active color",
+            FenField::CastlingRights => "castling rights",
+            FenField::EnPassant => "en passant square",
+            FenField::HalfmoveClock => "halfmove clock",
+            FenField::FullmoveNumber => "fullmove number",
+            FenField::Whole => "FEN",
+        }
+    }
+}
+
+/* Nature du problème rencontré */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FenErrorKind {
+    /* Caractère inattendu à cet endroit */
+    UnexpectedChar,
+    /* Nombre de rangées ou de cases incorrect */
+    WrongLength,
+    /* Élément répété (droit de roque présent deux fois, ...) */
+    Duplicate,
+    /* Valeur hors de l'intervalle permis */
+    OutOfRange,
+    /* Champ absent */
+    Missing,
+}
+
+impl FenErrorKind {
+    /* Description courte utilisée dans les messages */
+    pub fn description(self) -> &'static str {
+        match self {
+            FenErrorKind::UnexpectedChar => "unexpected character",
+            FenErrorKind::WrongLength => "wrong length",
+            FenErrorKind::Duplicate => "duplicate",
+            FenErrorKind::OutOfRange => "out of range",
+            FenErrorKind::Missing => "missing",
+        }
+    }
+}
+
+/* Erreur de parsing FEN.
+
+Outre le champ et la nature du problème, l'erreur cite le fragment fautif (`snippet`, situé par `span`
+en octets dans la FEN) et l'extrait qui l'entoure (`context`), ainsi que ce que le parseur attendait
+(`expected`) et ce qu'il a trouvé à la place (`found`). La structure est `non_exhaustive` : elle peut
+gagner des champs sans rupture de compatibilité. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FenError {
+    pub field: FenField,
+    pub kind: FenErrorKind,
+    pub span: Option<Range<usize>>,
+    pub snippet: String,
+    pub context: String,
+    pub expected: &'static str,
+    pub found: String,
+}
 
-    /* Erreur dans la couleur active */
-    #[error("Invalid active color: {0}")]
-    InvalidActiveColor(String),
+/* Erreur sans position, construite à partir de ses deux dimensions */
+impl From<(FenField, FenErrorKind)> for FenError {
+    fn from((field, kind): (FenField, FenErrorKind)) -> Self {
+        FenError {
+            field,
+            kind,
+            span: None,
+            snippet: String::new(),
+            context: String::new(),
+            expected: "",
+            found: String::new(),
+        }
+    }
+}
+
+impl FenError {
+    /* Erreur signalant un champ absent */
+    pub(crate) fn missing(field: FenField) -> Self {
+        FenError::from((field, FenErrorKind::Missing))
+    }
+
+    /* Message composant le champ, la nature du problème et le détail attendu/trouvé */
+    fn message(&self) -> String {
+        if self.kind == FenErrorKind::Missing {
+            return format!("missing {}", self.field.name());
+        }
+        let mut message = format!("invalid {}", self.field.name());
+        if self.span.is_some() {
+            message.push_str(&format!(" '{}' in \"{}\"", self.snippet, self.context));
+        }
+        message.push_str(&format!(": {}", self.kind.description()));
+        if !self.expected.is_empty() {
+            message.push_str(&format!(
+                ", expected {}, found '{}'",
+                self.expected, self.found
+            ));
+        }
+        message
+    }
+
+    /* Aides de transition pour le code qui testait les anciennes variantes de l'énumération */
+    #[deprecated(note = "compare `field` and `kind` instead")]
+    pub fn is_invalid_format(&self) -> bool {
+        self.kind == FenErrorKind::Missing || self.field == FenField::Whole
+    }
+
+    #[deprecated(note = "compare `field` with `FenField::PiecePlacement` instead")]
+    pub fn is_invalid_piece_placement(&self) -> bool {
+        self.field == FenField::PiecePlacement && self.kind != FenErrorKind::Missing
+    }
+
+    #[deprecated(note = "compare `field` with `FenField::ActiveColor` instead")]
+    pub fn is_invalid_active_color(&self) -> bool {
+        self.field == FenField::ActiveColor && self.kind != FenErrorKind::Missing
+    }
 
-    /* Erreur dans les droits de roque */
-    #[error("Invalid castling rights: {0}")]
-    InvalidCastlingRights(String),
+    #[deprecated(note = "compare `field` with `FenField::CastlingRights` instead")]
+    pub fn is_invalid_castling_rights(&self) -> bool {
+        self.field == FenField::CastlingRights && self.kind != FenErrorKind::Missing
+    }
 
-    /* Erreur dans la case de prise en passant */
-    #[error("Invalid en passant square: {0}")]
-    InvalidEnPassant(String),
+    #[deprecated(note = "compare `field` with `FenField::EnPassant` instead")]
+    pub fn is_invalid_en_passant(&self) -> bool {
+        self.field == FenField::EnPassant && self.kind != FenErrorKind::Missing
+    }
+
+    #[deprecated(note = "compare `field` with `FenField::HalfmoveClock` instead")]
+    pub fn is_invalid_halfmove_clock(&self) -> bool {
+        self.field == FenField::HalfmoveClock && self.kind != FenErrorKind::Missing
+    }
+
+    #[deprecated(note = "compare `field` with `FenField::FullmoveNumber` instead")]
+    pub fn is_invalid_fullmove_number(&self) -> bool {
+        self.field == FenField::FullmoveNumber && self.kind != FenErrorKind::Missing
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
 
-    /* Erreur dans le compteur de demi-coups */
-    #[error("Invalid halfmove clock: {0}")]
-    InvalidHalfmoveClock(String),
+impl core::error::Error for FenError {}
 
-    /* Erreur dans le numéro du tour */
-    #[error("Invalid fullmove number: {0}")]
-    InvalidFullmoveNumber(String),
+/* Extrait de `source` autour de l'intervalle d'octets `start..end`, tronqué par des points de suspension */
+pub(crate) fn context_around(source: &str, start: usize, end: usize) -> String {
+    /* Seuls les caractères voisins sont parcourus, quelle que soit la longueur de `source` */
+    let before = &source[..start];
+    let after = &source[end..];
+    let from = before
+        .char_indices()
+        .rev()
+        .nth(CONTEXT_WIDTH - 1)
+        .map_or(0, |(index, _)| index);
+    let to = after
+        .char_indices()
+        .nth(CONTEXT_WIDTH)
+        .map_or(after.len(), |(index, _)| index);
 
-    /* Erreur inconnue */
-    #[error("Unknown parsing error")]
-    Unknown,
+    let mut context = String::new();
+    if from > 0 {
+        context.push_str("...");
+    }
+    context.push_str(&before[from..]);
+    context.push_str(&source[start..end]);
+    context.push_str(&after[..to]);
+    if to < after.len() {
+        context.push_str("...");
+    }
+    context
 }
@@ -0,0 +1,58 @@
+/* Explication lisible des champs d'une chaîne FEN.
+
+Ce module décrit chacun des six champs d'une position pour les utilisateurs découvrant la notation FEN. */
+
+use crate::types::{ChessPosition, Color};
+
+/* Décrit les droits de roque d'un camp en une phrase */
+fn castling_sentence(side: &str, kingside: bool, queenside: bool) -> String {
+    match (kingside, queenside) {
+        (true, true) => format!("{} can castle kingside and queenside", side),
+        (true, false) => format!("{} can castle kingside only", side),
+        (false, true) => format!("{} can castle queenside only", side),
+        (false, false) => format!("{} cannot castle", side),
+    }
+}
+
+impl ChessPosition {
+    /* Explique les six champs FEN sur des lignes étiquetées */
+    pub fn explain(&self) -> String {
+        let rights = &self.castling_rights;
+        let (color_char, color_text) = match self.active_color {
+            Color::White => ('w', "White to move"),
+            Color::Black => ('b', "Black to move"),
+        };
+        let en_passant = match self.en_passant_square() {
+            Some(square) => format!(
+                "{} (the pawn that just advanced two squares can be captured en passant)",
+                square
+            ),
+            None => "none".to_string(),
+        };
+        let half_moves = if self.halfmove_clock == 1 {
+            "half-move"
+        } else {
+            "half-moves"
+        };
+
+        let mut out = String::new();
+        out.push_str(&format!("Piece placement: {}\n", self.placement_to_fen()));
+        out.push_str(&format!(
+            "Active color:    {} ({})\n",
+            color_char, color_text
+        ));
+        out.push_str(&format!(
+            "Castling rights: {} ({}; {})\n",
+            rights.to_fen(),
+            castling_sentence("White", rights.white_kingside, rights.white_queenside),
+            castling_sentence("Black", rights.black_kingside, rights.black_queenside)
+        ));
+        out.push_str(&format!("En passant:      {}\n", en_passant));
+        out.push_str(&format!(
+            "Halfmove clock:  {} (50-move rule: {} {} since last capture/pawn move)\n",
+            self.halfmove_clock, self.halfmove_clock, half_moves
+        ));
+        out.push_str(&format!("Fullmove number: {}\n", self.fullmove_number));
+        out
+    }
+}
@@ -0,0 +1,204 @@
+/* Interface C du parseur.
+
+Les fonctions `extern "C"` de ce module sont déclarées dans `include/fen_parser.h`, qui en précise les contrats : pointeurs non nuls, chaînes terminées par un zéro, tampon d'au moins `len` octets. La position est échangée sous la forme de `FenPositionC`, structure `#[repr(C)]` de taille fixe. */
+
+#![allow(clippy::missing_safety_doc)]
+
+use crate::error::{FenError, FenErrorKind, FenField};
+use crate::parser::parse_fen;
+use crate::types::{CastlingRights, ChessPosition, Color, Piece, PieceKind, Square};
+use std::ffi::{c_char, CStr};
+
+/* Codes de retour */
+pub const FEN_OK: i32 = 0;
+pub const FEN_ERR_NULL_POINTER: i32 = -1;
+pub const FEN_ERR_INVALID_UTF8: i32 = -2;
+pub const FEN_ERR_INVALID_POSITION: i32 = -3;
+pub const FEN_ERR_PIECE_PLACEMENT: i32 = 1;
+pub const FEN_ERR_ACTIVE_COLOR: i32 = 2;
+pub const FEN_ERR_CASTLING_RIGHTS: i32 = 3;
+pub const FEN_ERR_EN_PASSANT: i32 = 4;
+pub const FEN_ERR_HALFMOVE_CLOCK: i32 = 5;
+pub const FEN_ERR_FULLMOVE_NUMBER: i32 = 6;
+pub const FEN_ERR_MISSING_FIELD: i32 = 7;
+pub const FEN_ERR_OTHER: i32 = 8;
+
+/* Case sans prise en passant */
+pub const FEN_NO_EN_PASSANT: u8 = 0xFF;
+
+/* Position vue du C.
+ * `board` va de a1 (0) à h8 (63) : 0 vide, 1 à 6 roi, dame, tour, fou, cavalier et pion
+ * blancs, 7 à 12 les mêmes pièces noires. `castling` combine K = 1, Q = 2, k = 4, q = 8. */
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FenPositionC {
+    pub board: [u8; 64],
+    /* 0 pour les blancs, 1 pour les noirs */
+    pub active_color: u8,
+    pub castling: u8,
+    /* Indice de la case de prise en passant, ou `FEN_NO_EN_PASSANT` */
+    pub en_passant: u8,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+}
+
+/* Code de retour correspondant à une erreur de parsing */
+pub fn error_code(error: &FenError) -> i32 {
+    if error.kind == FenErrorKind::Missing {
+        return FEN_ERR_MISSING_FIELD;
+    }
+    match error.field {
+        FenField::PiecePlacement => FEN_ERR_PIECE_PLACEMENT,
+        FenField::ActiveColor => FEN_ERR_ACTIVE_COLOR,
+        FenField::CastlingRights => FEN_ERR_CASTLING_RIGHTS,
+        FenField::EnPassant => FEN_ERR_EN_PASSANT,
+        FenField::HalfmoveClock => FEN_ERR_HALFMOVE_CLOCK,
+        FenField::FullmoveNumber => FEN_ERR_FULLMOVE_NUMBER,
+        _ => FEN_ERR_OTHER,
+    }
+}
+
+impl From<&ChessPosition> for FenPositionC {
+    fn from(position: &ChessPosition) -> Self {
+        let mut board = [0; 64];
+        for square in (0..64).filter_map(Square::from_index) {
+            board[square.index()] = position.piece_at(square).map_or(0, |piece| {
+                let kind = PieceKind::ALL
+                    .iter()
+                    .position(|&kind| kind == piece.kind)
+                    .unwrap_or(0) as u8;
+                match piece.color {
+                    Color::White => 1 + kind,
+                    Color::Black => 7 + kind,
+                }
+            });
+        }
+        let rights = position.castling_rights;
+        FenPositionC {
+            board,
+            active_color: u8::from(position.active_color == Color::Black),
+            castling: u8::from(rights.white_kingside)
+                | (u8::from(rights.white_queenside) << 1)
+                | (u8::from(rights.black_kingside) << 2)
+                | (u8::from(rights.black_queenside) << 3),
+            en_passant: position
+                .en_passant_square()
+                .map_or(FEN_NO_EN_PASSANT, |square| square.index() as u8),
+            halfmove_clock: position.halfmove_clock,
+            fullmove_number: position.fullmove_number,
+        }
+    }
+}
+
+impl FenPositionC {
+    /* Position décrite par la structure, ou `None` si un champ est hors des valeurs permises */
+    pub fn to_position(&self) -> Option<ChessPosition> {
+        let mut pieces = [[None; 8]; 8];
+        for square in (0..64).filter_map(Square::from_index) {
+            let piece = match self.board[square.index()] {
+                0 => None,
+                code @ 1..=6 => Some(Piece {
+                    color: Color::White,
+                    kind: PieceKind::ALL[code as usize - 1],
+                }),
+                code @ 7..=12 => Some(Piece {
+                    color: Color::Black,
+                    kind: PieceKind::ALL[code as usize - 7],
+                }),
+                _ => return None,
+            };
+            pieces[square.rank().index()][square.file().index()] = piece;
+        }
+        let active_color = match self.active_color {
+            0 => Color::White,
+            1 => Color::Black,
+            _ => return None,
+        };
+        if self.castling > 0x0F {
+            return None;
+        }
+        let en_passant = match self.en_passant {
+            FEN_NO_EN_PASSANT => None,
+            index => {
+                let square = Square::from_index(index as usize)?;
+                Some((square.file().index() as u8, square.rank().index() as u8))
+            }
+        };
+        Some(ChessPosition {
+            pieces,
+            active_color,
+            castling_rights: CastlingRights {
+                white_kingside: self.castling & 1 != 0,
+                white_queenside: self.castling & 2 != 0,
+                black_kingside: self.castling & 4 != 0,
+                black_queenside: self.castling & 8 != 0,
+            },
+            en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+        })
+    }
+}
+
+/* Lit la FEN `fen` dans `*out` ; renvoie `FEN_OK` ou un code d'erreur, `*out` n'étant
+ * modifié qu'en cas de succès */
+#[no_mangle]
+pub unsafe extern "C" fn fen_parse(fen: *const c_char, out: *mut FenPositionC) -> i32 {
+    if fen.is_null() || out.is_null() {
+        return FEN_ERR_NULL_POINTER;
+    }
+    let Ok(fen) = CStr::from_ptr(fen).to_str() else {
+        return FEN_ERR_INVALID_UTF8;
+    };
+    match parse_fen(fen) {
+        Ok(position) => {
+            *out = FenPositionC::from(&position);
+            FEN_OK
+        }
+        Err(error) => error_code(&error),
+    }
+}
+
+/* Écrit la FEN de `*position` dans `buf`, tronquée à `len - 1` octets et terminée par un
+ * zéro ; renvoie la longueur complète de la FEN, comme `snprintf`, ou un code négatif */
+#[no_mangle]
+pub unsafe extern "C" fn fen_to_string(
+    position: *const FenPositionC,
+    buf: *mut c_char,
+    len: usize,
+) -> i32 {
+    if position.is_null() || (buf.is_null() && len > 0) {
+        return FEN_ERR_NULL_POINTER;
+    }
+    let Some(position) = (*position).to_position() else {
+        return FEN_ERR_INVALID_POSITION;
+    };
+    let fen = position.to_fen();
+    if len > 0 {
+        let copied = fen.len().min(len - 1);
+        std::ptr::copy_nonoverlapping(fen.as_ptr(), buf.cast::<u8>(), copied);
+        *buf.add(copied) = 0;
+    }
+    fen.len() as i32
+}
+
+/* Message décrivant un code de retour ; la chaîne est statique et ne doit pas être libérée */
+#[no_mangle]
+pub extern "C" fn fen_error_message(code: i32) -> *const c_char {
+    let message: &'static CStr = match code {
+        FEN_OK => c"success",
+        FEN_ERR_NULL_POINTER => c"null pointer argument",
+        FEN_ERR_INVALID_UTF8 => c"FEN is not valid UTF-8",
+        FEN_ERR_INVALID_POSITION => c"position structure holds an invalid value",
+        FEN_ERR_PIECE_PLACEMENT => c"invalid piece placement",
+        FEN_ERR_ACTIVE_COLOR => c"invalid active color",
+        FEN_ERR_CASTLING_RIGHTS => c"invalid castling rights",
+        FEN_ERR_EN_PASSANT => c"invalid en passant square",
+        FEN_ERR_HALFMOVE_CLOCK => c"invalid halfmove clock",
+        FEN_ERR_FULLMOVE_NUMBER => c"invalid fullmove number",
+        FEN_ERR_MISSING_FIELD => c"missing FEN field",
+        FEN_ERR_OTHER => c"invalid FEN",
+        _ => c"unknown error code",
+    };
+    message.as_ptr()
+}
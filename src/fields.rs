@@ -0,0 +1,126 @@
+/* Découpage d'une FEN en champs, sans les analyser.
+
+`split_fen_fields` ne vérifie que le nombre de champs et leurs séparateurs (espaces ou tabulations, éventuellement répétés, comme pour `parse_fen`) et renvoie des tranches de la chaîne d'origine, sans copie ni allocation. Il suffit quand un seul champ intéresse, le trait par exemple, sur un grand nombre de FEN.
+
+Quatre champs sont acceptés, comme dans l'EPD : les deux compteurs manquent alors, et le numéro de coup seul avec cinq champs. Au-delà de six champs, la FEN est refusée alors que `parse_fen` ignore la suite ; une FEN qui commence par un séparateur est refusée comme par `parse_fen`. */
+
+use crate::error::{context_around, FenError, FenErrorKind, FenField};
+use crate::parser::SEPARATORS;
+use alloc::string::ToString;
+
+/* Champs d'une FEN, empruntés à la chaîne d'origine */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FenFields<'a> {
+    placement: &'a str,
+    active_color: &'a str,
+    castling: &'a str,
+    en_passant: &'a str,
+    halfmove_clock: Option<&'a str>,
+    fullmove_number: Option<&'a str>,
+}
+
+impl<'a> FenFields<'a> {
+    pub fn placement(&self) -> &'a str {
+        self.placement
+    }
+
+    pub fn active_color(&self) -> &'a str {
+        self.active_color
+    }
+
+    /* Premier caractère du trait (`w` ou `b` dans une FEN valide) ; le champ n'est jamais vide */
+    pub fn active_color_char(&self) -> char {
+        self.active_color.chars().next().unwrap_or_default()
+    }
+
+    pub fn castling(&self) -> &'a str {
+        self.castling
+    }
+
+    pub fn en_passant(&self) -> &'a str {
+        self.en_passant
+    }
+
+    pub fn halfmove_clock(&self) -> Option<&'a str> {
+        self.halfmove_clock
+    }
+
+    pub fn fullmove_number(&self) -> Option<&'a str> {
+        self.fullmove_number
+    }
+
+    /* Champ demandé ; `None` pour un compteur absent et pour `FenField::Whole` */
+    pub fn get(&self, field: FenField) -> Option<&'a str> {
+        match field {
+            FenField::PiecePlacement => Some(self.placement),
+            FenField::ActiveColor => Some(self.active_color),
+            FenField::CastlingRights => Some(self.castling),
+            FenField::EnPassant => Some(self.en_passant),
+            FenField::HalfmoveClock => self.halfmove_clock,
+            FenField::FullmoveNumber => self.fullmove_number,
+            FenField::Whole => None,
+        }
+    }
+}
+
+/* Erreur située sur `start..end` de `fen` */
+fn located(
+    fen: &str,
+    field: FenField,
+    start: usize,
+    end: usize,
+    expected: &'static str,
+) -> FenError {
+    FenError {
+        span: Some(start..end),
+        snippet: fen[start..end].to_string(),
+        context: context_around(fen, start, end),
+        expected,
+        found: fen[start..end].to_string(),
+        ..FenError::from((field, FenErrorKind::UnexpectedChar))
+    }
+}
+
+/* Découpe une FEN en quatre à six champs */
+pub fn split_fen_fields(fen: &str) -> Result<FenFields<'_>, FenError> {
+    if fen.starts_with(SEPARATORS) {
+        return Err(located(
+            fen,
+            FenField::PiecePlacement,
+            0,
+            1,
+            "the piece placement at the start of the FEN",
+        ));
+    }
+    let mut parts = fen.split(SEPARATORS).filter(|part| !part.is_empty());
+    let mut fields = [""; 6];
+    let mut count = 0;
+    for part in parts.by_ref().take(6) {
+        fields[count] = part;
+        count += 1;
+    }
+    if count < 4 {
+        return Err(FenError::missing(FenField::FIELDS[count]));
+    }
+    if let Some(extra) = parts.next() {
+        /* `extra` est une tranche de `fen` : sa position s'en déduit */
+        let start = extra.as_ptr() as usize - fen.as_ptr() as usize;
+        let end = fen.trim_end_matches(SEPARATORS).len();
+        return Err(located(
+            fen,
+            FenField::Whole,
+            start,
+            end,
+            "at most six fields",
+        ));
+    }
+    let [placement, active_color, castling, en_passant, halfmove, fullmove] = fields;
+    Ok(FenFields {
+        placement,
+        active_color,
+        castling,
+        en_passant,
+        halfmove_clock: (count > 4).then_some(halfmove),
+        fullmove_number: (count > 5).then_some(fullmove),
+    })
+}
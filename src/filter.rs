@@ -0,0 +1,166 @@
+/* Sélection de positions par leur matériel.
+
+Un filtre est une suite de conditions séparées par des espaces ou des virgules, toutes requises :
+
+- une signature `KRPvKR`, chaque camp écrit du roi au pion ; un `*` admet des pions en plus de ceux écrits (`KR*vKR`). Elle vaut quel que soit le camp qui a les pièces du premier côté, sauf si elle commence par `^` : le premier côté est alors celui des blancs (`^KRvK`) ;
+- une contrainte de nombre, lettre de pièce, opérateur et entier (`B>=2`, `q=0`) : majuscule pour les blancs, minuscule pour les noirs, et l'un des opérateurs `=`, `!=`, `<`, `<=`, `>`, `>=`.
+
+« Les blancs ont deux fous, les noirs aucun » s'écrit ainsi `B=2 b=0`. */
+
+use crate::analysis::{parse_signature_side, MaterialCount};
+use crate::types::{ChessPosition, Color, Piece};
+use thiserror::Error;
+
+/* Erreur de lecture d'un filtre */
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum FilterError {
+    #[error("empty material filter")]
+    Empty,
+    #[error("invalid signature '{0}': expected two sides such as KRPvKR, each starting with K")]
+    InvalidSignature(String),
+    #[error(
+        "invalid condition '{0}': expected a signature (KRvK) or a piece count such as B>=2 or q=0"
+    )]
+    InvalidCondition(String),
+}
+
+/* Comparaison d'une contrainte de nombre */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /* Opérateurs reconnus, les plus longs d'abord pour que `<=` ne soit pas lu `<` */
+    const SYMBOLS: [(&'static str, Comparison); 7] = [
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("=", Comparison::Equal),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    fn holds(self, found: u32, expected: u32) -> bool {
+        match self {
+            Comparison::Equal => found == expected,
+            Comparison::NotEqual => found != expected,
+            Comparison::Less => found < expected,
+            Comparison::LessOrEqual => found <= expected,
+            Comparison::Greater => found > expected,
+            Comparison::GreaterOrEqual => found >= expected,
+        }
+    }
+}
+
+/* Condition d'un filtre */
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Condition {
+    /* Côtés lus par `parse_signature_side` ; `anchored` fixe les blancs au premier */
+    Signature {
+        first: ([u32; 6], bool),
+        second: ([u32; 6], bool),
+        anchored: bool,
+    },
+    Count {
+        piece: Piece,
+        comparison: Comparison,
+        value: u32,
+    },
+}
+
+impl Condition {
+    fn parse(text: &str) -> Result<Self, FilterError> {
+        if let Some(condition) = Condition::parse_count(text) {
+            return Ok(condition);
+        }
+        let (anchored, signature) = match text.strip_prefix('^') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let Some((first, second)) = signature.split_once('v') else {
+            return Err(FilterError::InvalidCondition(text.to_string()));
+        };
+        match (parse_signature_side(first), parse_signature_side(second)) {
+            (Some(first), Some(second)) => Ok(Condition::Signature {
+                first,
+                second,
+                anchored,
+            }),
+            _ => Err(FilterError::InvalidSignature(text.to_string())),
+        }
+    }
+
+    /* Contrainte de nombre (`B>=2`), ou `None` si le texte n'en est pas une */
+    fn parse_count(text: &str) -> Option<Self> {
+        let mut chars = text.chars();
+        let piece = Piece::from_fen_char(chars.next()?)?;
+        let rest = chars.as_str();
+        let (symbol, comparison) = Comparison::SYMBOLS
+            .into_iter()
+            .find(|(symbol, _)| rest.starts_with(symbol))?;
+        let value = rest[symbol.len()..].parse().ok()?;
+        Some(Condition::Count {
+            piece,
+            comparison,
+            value,
+        })
+    }
+
+    fn holds(&self, material: &MaterialCount) -> bool {
+        match *self {
+            Condition::Signature {
+                first,
+                second,
+                anchored,
+            } => {
+                let oriented = |strong: Color| {
+                    material.side_matches(strong, first)
+                        && material.side_matches(strong.opposite(), second)
+                };
+                oriented(Color::White) || (!anchored && oriented(Color::Black))
+            }
+            Condition::Count {
+                piece,
+                comparison,
+                value,
+            } => comparison.holds(material.count(piece.color, piece.kind), value),
+        }
+    }
+}
+
+/* Filtre sur le matériel, lu par `MaterialFilter::parse` */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaterialFilter {
+    conditions: Vec<Condition>,
+}
+
+impl MaterialFilter {
+    /* Lit un filtre : `KR*vKR`, `^KQvK`, `B>=2 b=0`... */
+    pub fn parse(text: &str) -> Result<Self, FilterError> {
+        let conditions = text
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(Condition::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if conditions.is_empty() {
+            return Err(FilterError::Empty);
+        }
+        Ok(MaterialFilter { conditions })
+    }
+
+    /* Vrai si la position remplit toutes les conditions */
+    pub fn matches(&self, position: &ChessPosition) -> bool {
+        let material = position.material();
+        self.conditions
+            .iter()
+            .all(|condition| condition.holds(&material))
+    }
+}
@@ -0,0 +1,212 @@
+/* Génération pseudo-aléatoire de positions.
+
+Ce module produit des positions reproductibles à partir d'une graine, pour les démonstrations, les mesures de performance et pour éprouver les outils qui consomment des FEN. Le générateur (SplitMix64) est interne et volontairement simple : il ne prétend à aucune qualité cryptographique, mais une graine donne la même suite d'une version de Rust à l'autre. */
+
+use crate::types::{CastlingRights, ChessPosition, Color, File, Piece, PieceKind, Rank, Square};
+
+/* Générateur de positions déterminé par sa graine ; comme itérateur, il produit sans fin des
+ * positions vraisemblables */
+#[derive(Debug, Clone)]
+pub struct RandomPositionGenerator {
+    state: u64,
+}
+
+impl RandomPositionGenerator {
+    /* Crée un générateur ; une même graine produit toujours la même suite de positions */
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /* Entier pseudo-aléatoire suivant (SplitMix64) */
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /* Entier dans l'intervalle [0, bound) */
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    fn color(&mut self) -> Color {
+        if self.below(2) == 0 {
+            Color::White
+        } else {
+            Color::Black
+        }
+    }
+
+    /* Position sans autre contrainte que la syntaxe : chaque case reçoit une pièce
+     * quelconque avec une probabilité d'un tiers */
+    pub fn random_board(&mut self) -> ChessPosition {
+        let mut position = empty_position(self.color());
+        for square in (0..64).filter_map(Square::from_index) {
+            if self.below(3) == 0 {
+                let kind = PieceKind::ALL[self.below(6) as usize];
+                let piece = Piece {
+                    color: self.color(),
+                    kind,
+                };
+                position.pieces[square.rank().index()][square.file().index()] = Some(piece);
+            }
+        }
+        position
+    }
+
+    /* Position vraisemblable : un roi par camp, au plus le matériel initial, aucun pion sur
+     * les rangées extrêmes, des droits de roque et une prise en passant cohérents avec le
+     * placement, et le camp qui n'a pas le trait hors d'échec ; elle passe donc `validate()` */
+    pub fn random_plausible(&mut self) -> ChessPosition {
+        loop {
+            let mut position = empty_position(self.color());
+            /* Rois et tours de roque d'abord, pour que leurs cases d'origine restent libres */
+            let mut rooks = [0; 2];
+            let mut castles = [false; 2];
+            for ((color, rooks), castles) in [Color::White, Color::Black]
+                .into_iter()
+                .zip(&mut rooks)
+                .zip(&mut castles)
+            {
+                *rooks = self.below(3) as usize;
+                /* Une fois sur trois, roi et tours restent sur leurs cases d'origine */
+                *castles = self.below(3) == 0;
+                if *castles {
+                    *rooks -= self.castling_setup(&mut position, color, *rooks);
+                }
+            }
+            for (color, castles) in [Color::White, Color::Black].into_iter().zip(castles) {
+                if !castles {
+                    self.place(&mut position, color, PieceKind::King, 1);
+                }
+            }
+            for (color, rooks) in [Color::White, Color::Black].into_iter().zip(rooks) {
+                let pawns = self.below(9) as usize;
+                self.place(&mut position, color, PieceKind::Pawn, pawns);
+                let queens = self.below(2) as usize;
+                self.place(&mut position, color, PieceKind::Queen, queens);
+                self.place(&mut position, color, PieceKind::Rook, rooks);
+                for kind in [PieceKind::Bishop, PieceKind::Knight] {
+                    let count = self.below(3) as usize;
+                    self.place(&mut position, color, kind, count);
+                }
+            }
+            if self.below(2) == 0 {
+                self.en_passant(&mut position);
+            }
+            let waiting = position.active_color.opposite();
+            let exposed = position
+                .king_square(waiting)
+                .is_some_and(|king| position.is_attacked(king, position.active_color));
+            if !exposed {
+                return position;
+            }
+        }
+    }
+
+    /* Roi sur sa case d'origine et jusqu'à `rooks` tours dans les coins, chacune gardant le
+     * plus souvent son droit de roque ; renvoie le nombre de tours posées */
+    fn castling_setup(
+        &mut self,
+        position: &mut ChessPosition,
+        color: Color,
+        rooks: usize,
+    ) -> usize {
+        let rank = match color {
+            Color::White => Rank::First,
+            Color::Black => Rank::Eighth,
+        };
+        set(position, Square::new(File::E, rank), color, PieceKind::King);
+        let mut placed = 0;
+        for file in [File::H, File::A] {
+            if placed == rooks || self.below(2) == 0 {
+                continue;
+            }
+            set(position, Square::new(file, rank), color, PieceKind::Rook);
+            placed += 1;
+            if self.below(4) != 0 {
+                let rights = &mut position.castling_rights;
+                match (color, file) {
+                    (Color::White, File::H) => rights.white_kingside = true,
+                    (Color::White, _) => rights.white_queenside = true,
+                    (Color::Black, File::H) => rights.black_kingside = true,
+                    (Color::Black, _) => rights.black_queenside = true,
+                }
+            }
+        }
+        placed
+    }
+
+    /* Case de prise en passant derrière un pion adverse qui a pu avancer de deux cases */
+    fn en_passant(&mut self, position: &mut ChessPosition) {
+        let (pawn_rank, target_rank, origin_rank) = match position.active_color {
+            Color::White => (Rank::Fifth, Rank::Sixth, Rank::Seventh),
+            Color::Black => (Rank::Fourth, Rank::Third, Rank::Second),
+        };
+        let pawn = Piece {
+            color: position.active_color.opposite(),
+            kind: PieceKind::Pawn,
+        };
+        let candidates: Vec<File> = File::ALL
+            .into_iter()
+            .filter(|&file| {
+                position.piece_at(Square::new(file, pawn_rank)) == Some(pawn)
+                    && position.piece_at(Square::new(file, target_rank)).is_none()
+                    && position.piece_at(Square::new(file, origin_rank)).is_none()
+            })
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        let file = candidates[self.below(candidates.len() as u64) as usize];
+        position.en_passant = Some((file.index() as u8, target_rank.index() as u8));
+    }
+
+    /* Pose `count` pièces sur des cases libres tirées au hasard ; les pions évitent
+     * les rangées extrêmes */
+    fn place(&mut self, position: &mut ChessPosition, color: Color, kind: PieceKind, count: usize) {
+        let range = if kind == PieceKind::Pawn {
+            8..56
+        } else {
+            0..64
+        };
+        let mut placed = 0;
+        while placed < count {
+            let index = range.start + self.below((range.end - range.start) as u64) as usize;
+            let Some(square) = Square::from_index(index) else {
+                continue;
+            };
+            if position.piece_at(square).is_none() {
+                set(position, square, color, kind);
+                placed += 1;
+            }
+        }
+    }
+}
+
+impl Iterator for RandomPositionGenerator {
+    type Item = ChessPosition;
+
+    fn next(&mut self) -> Option<ChessPosition> {
+        Some(self.random_plausible())
+    }
+}
+
+/* Pose une pièce sur une case */
+fn set(position: &mut ChessPosition, square: Square, color: Color, kind: PieceKind) {
+    position.pieces[square.rank().index()][square.file().index()] = Some(Piece { color, kind });
+}
+
+/* Échiquier vide, sans roque ni prise en passant, au premier coup */
+fn empty_position(active_color: Color) -> ChessPosition {
+    ChessPosition {
+        pieces: [[None; 8]; 8],
+        active_color,
+        castling_rights: CastlingRights::none(),
+        en_passant: None,
+        halfmove_clock: 0,
+        fullmove_number: 1,
+    }
+}
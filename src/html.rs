@@ -0,0 +1,68 @@
+/* Rendu HTML d'une position d'échecs.
+
+Ce module produit une table HTML accessible, une cellule par case, à intégrer dans des rapports. */
+
+use crate::display::PieceTheme;
+use crate::svg::xml_escape;
+use crate::types::{ChessPosition, Square};
+
+impl ChessPosition {
+    /* Rend la position sous forme de table HTML avec le thème Unicode */
+    pub fn to_html(&self) -> String {
+        self.to_html_with_theme(&PieceTheme::UNICODE)
+    }
+
+    /* Rend la position sous forme de table HTML avec le thème donné */
+    pub fn to_html_with_theme(&self, theme: &PieceTheme) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "<table class=\"chessboard\" data-fen=\"{}\" aria-label=\"Chess position\">\n",
+            xml_escape(&self.to_fen())
+        ));
+
+        /* En-tête des colonnes */
+        out.push_str("  <thead>\n    <tr><th></th>");
+        for file in 0..8u8 {
+            out.push_str(&format!("<th scope=\"col\">{}</th>", (b'a' + file) as char));
+        }
+        out.push_str("</tr>\n  </thead>\n  <tbody>\n");
+
+        for rank in (0..8u8).rev() {
+            out.push_str(&format!("    <tr><th scope=\"row\">{}</th>", rank + 1));
+            for file in 0..8u8 {
+                let Some(square) = Square::from_coords(file, rank) else {
+                    continue;
+                };
+                let shade = if (file + rank) % 2 == 0 {
+                    "dark"
+                } else {
+                    "light"
+                };
+                match self.piece_at(square) {
+                    Some(piece) => {
+                        let color = piece.color.name();
+                        out.push_str(&format!(
+                            "<td class=\"square {} piece-{}\" data-square=\"{}\" data-piece=\"{}\" aria-label=\"{} {} {}\">{}</td>",
+                            shade,
+                            color,
+                            square,
+                            piece.kind.name(),
+                            square,
+                            color,
+                            piece.kind.name(),
+                            xml_escape(theme.glyph(piece))
+                        ));
+                    }
+                    None => out.push_str(&format!(
+                        "<td class=\"square {}\" data-square=\"{}\" aria-label=\"{} empty\"></td>",
+                        shade, square, square
+                    )),
+                }
+            }
+            out.push_str("</tr>\n");
+        }
+
+        out.push_str("  </tbody>\n</table>\n");
+        out
+    }
+}
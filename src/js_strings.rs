@@ -0,0 +1,41 @@
+/* Fonctions à chaînes destinées à des liaisons JavaScript.
+
+Ces fonctions ne reçoivent et ne renvoient que des chaînes, ce qui correspond aux valeurs que des liaisons `wasm-bindgen` échangeraient avec le navigateur : JSON pour les structures, message pour les erreurs. Aucune n'écrit sur la sortie standard. Le module ne dépend pas de `wasm-bindgen` et n'exporte rien vers JavaScript : un paquet de liaisons doit envelopper chaque fonction dans une fonction `#[wasm_bindgen]`, qui convertit l'`Err` en exception. */
+
+use crate::json::json_string;
+use crate::parser::{parse_fen, parse_fen_all_errors};
+use crate::svg::SvgOptions;
+
+/* Position au format JSON de `to_json`, ou message d'erreur */
+pub fn parse_fen_js(fen: &str) -> Result<String, String> {
+    parse_fen(fen)
+        .map(|position| position.to_json())
+        .map_err(|error| error.to_string())
+}
+
+/* Rapport de validation en JSON : `{"valid":..,"errors":[..]}`, erreurs de syntaxe comprises */
+pub fn validate_fen(fen: &str) -> String {
+    let errors: Vec<String> = match parse_fen_all_errors(fen) {
+        Err(errors) => errors.iter().map(|error| error.to_string()).collect(),
+        Ok(position) => match position.validate() {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.iter().map(|error| error.to_string()).collect(),
+        },
+    };
+    let errors: Vec<String> = errors.iter().map(|error| json_string(error)).collect();
+    format!(
+        "{{\"valid\":{},\"errors\":[{}]}}",
+        errors.is_empty(),
+        errors.join(",")
+    )
+}
+
+/* Diagramme SVG de la position, vue des noirs si `flipped` */
+pub fn render_svg(fen: &str, flipped: bool) -> Result<String, String> {
+    let position = parse_fen(fen).map_err(|error| error.to_string())?;
+    let options = SvgOptions {
+        flipped,
+        ..SvgOptions::default()
+    };
+    Ok(position.render_svg(&options))
+}
@@ -0,0 +1,66 @@
+/* Rendu JSON d'une position d'échecs.
+
+Ce module produit une représentation structurée d'une position, destinée aux interfaces web et aux scripts. */
+
+use crate::types::{ChessPosition, Square};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/* Encode une chaîne au format JSON, guillemets compris */
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl ChessPosition {
+    /* Représentation JSON de la position, sur une seule ligne */
+    pub fn to_json(&self) -> String {
+        let board: Vec<String> = (0..64)
+            .filter_map(Square::from_index)
+            .filter_map(|square| {
+                self.piece_at(square).map(|piece| {
+                    format!(
+                        "{}:{{\"color\":{},\"kind\":{}}}",
+                        json_string(&square.to_string()),
+                        json_string(piece.color.name()),
+                        json_string(piece.kind.name())
+                    )
+                })
+            })
+            .collect();
+
+        let rights = &self.castling_rights;
+        let en_passant = match self.en_passant_square() {
+            Some(square) => json_string(&square.to_string()),
+            None => "null".to_string(),
+        };
+
+        format!(
+            "{{\"fen\":{},\"board\":{{{}}},\"active_color\":{},\"castling\":{{\"white_kingside\":{},\"white_queenside\":{},\"black_kingside\":{},\"black_queenside\":{}}},\"en_passant\":{},\"halfmove_clock\":{},\"fullmove_number\":{}}}",
+            json_string(&self.to_fen()),
+            board.join(","),
+            json_string(self.active_color.name()),
+            rights.white_kingside,
+            rights.white_queenside,
+            rights.black_kingside,
+            rights.black_queenside,
+            en_passant,
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+}
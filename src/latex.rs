@@ -0,0 +1,55 @@
+/* Export LaTeX pour les paquets skak/xskak et chessboard.
+
+Ce module produit un extrait prêt à inclure dans un document LaTeX, à partir de la chaîne FEN de la position. */
+
+use crate::types::{ChessPosition, Square};
+
+/* Forme de l'extrait LaTeX produit */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LatexForm {
+    /* `\fenboard{...}` suivi de `\showboard` (skak/xskak) */
+    #[default]
+    FenBoard,
+    /* `\chessboard[setfen=...]` en une seule commande (chessboard) */
+    Chessboard,
+}
+
+/* Options de l'export LaTeX */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatexOptions {
+    /* Forme de l'extrait */
+    pub form: LatexForm,
+    /* Cases marquées (option `markfields`, forme chessboard uniquement) */
+    pub marked_squares: Vec<Square>,
+    /* Noirs en bas */
+    pub flipped: bool,
+}
+
+impl ChessPosition {
+    /* Rend la position sous forme d'extrait LaTeX */
+    pub fn to_latex(&self, opts: &LatexOptions) -> String {
+        let fen = self.to_fen();
+        match opts.form {
+            LatexForm::FenBoard => {
+                let show = if opts.flipped {
+                    "\\showinverseboard"
+                } else {
+                    "\\showboard"
+                };
+                format!("\\fenboard{{{}}}\n{}\n", fen, show)
+            }
+            LatexForm::Chessboard => {
+                let mut keys = vec![format!("setfen={{{}}}", fen)];
+                if !opts.marked_squares.is_empty() {
+                    let squares: Vec<String> =
+                        opts.marked_squares.iter().map(Square::to_string).collect();
+                    keys.push(format!("markfields={{{}}}", squares.join(",")));
+                }
+                if opts.flipped {
+                    keys.push("inverse".to_string());
+                }
+                format!("\\chessboard[{}]\n", keys.join(", "))
+            }
+        }
+    }
+}
@@ -1,9 +1,139 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod bitboard;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod compact;
+#[cfg(feature = "std")]
+pub mod dedup;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostic;
+#[cfg(feature = "std")]
+pub mod diagram;
+#[cfg(feature = "std")]
+pub mod dialect;
+#[cfg(feature = "std")]
+pub mod diff;
+#[cfg(feature = "std")]
 pub mod display;
+#[cfg(feature = "std")]
+pub mod epd;
 pub mod error;
+#[cfg(feature = "std")]
+pub mod explain;
+#[cfg(feature = "capi")]
+pub mod ffi;
+pub mod fields;
+#[cfg(feature = "std")]
+pub mod filter;
+#[cfg(feature = "std")]
+pub mod generator;
+#[cfg(feature = "std")]
+pub mod html;
+#[cfg(feature = "js-strings")]
+pub mod js_strings;
+pub mod json;
+#[cfg(feature = "std")]
+pub mod latex;
+#[cfg(feature = "std")]
+pub mod markdown;
+#[cfg(feature = "std")]
+pub mod movegen;
+#[cfg(feature = "std")]
+pub mod openings;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 pub mod parser;
+#[cfg(feature = "std")]
+pub mod pgn;
+#[cfg(feature = "std")]
+pub mod piece_list;
+#[cfg(feature = "std")]
+pub mod positions;
+#[cfg(feature = "eval")]
+mod pst;
+#[cfg(feature = "py-model")]
+pub mod py_model;
+#[cfg(feature = "std")]
+pub mod repair;
+#[cfg(feature = "std")]
+pub mod repl;
+#[cfg(feature = "strategies")]
+pub mod strategies;
+#[cfg(feature = "std")]
+pub mod svg;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 pub mod types;
+#[cfg(feature = "std")]
+pub mod validation;
+pub mod writer;
+pub mod zobrist;
 
 // Ré-exporter les types principaux
-pub use error::FenError;
-pub use parser::parse_fen;
-pub use types::{CastlingRights, ChessPosition, Color, Piece, PieceKind};
+pub use analysis::{MaterialCount, ReachabilityWarning};
+pub use bitboard::{Bitboards, ByColor, ByPieceKind, PositionError};
+pub use error::{FenError, FenErrorKind, FenField};
+pub use fields::{split_fen_fields, FenFields};
+pub use parser::{
+    parse_fen, parse_fen_all_errors, parse_fen_into, parse_fen_nom, parse_fen_with_mode, ParseMode,
+    ParseWarning, ParsedFen,
+};
+pub use types::{
+    CastlingRights, CastlingSide, ChessPosition, Color, Direction, File, Move, Piece, PieceKind,
+    Rank, Square, SquareColor, SquareOrder, SquareSet,
+};
+
+#[cfg(feature = "std")]
+pub use batch::{BatchEntry, BatchSummary};
+#[cfg(feature = "std")]
+pub use cache::FenCache;
+#[cfg(feature = "std")]
+pub use compact::{CompactError, CompactPosition};
+#[cfg(feature = "std")]
+pub use dedup::{DedupMode, DedupReport};
+#[cfg(feature = "std")]
+pub use diagram::DiagramMeta;
+#[cfg(feature = "std")]
+pub use dialect::{DialectError, FenDialect};
+#[cfg(feature = "std")]
+pub use diff::{render_diff, PositionDiff};
+#[cfg(feature = "std")]
+pub use display::{
+    BoardStyle, BorderStyle, CoordinateSides, DisplayMode, DisplayOptions, HighlightKind,
+    Orientation, PieceTheme,
+};
+#[cfg(feature = "std")]
+pub use epd::{EpdError, EpdRecord};
+#[cfg(feature = "std")]
+pub use filter::{FilterError, MaterialFilter};
+#[cfg(feature = "std")]
+pub use generator::RandomPositionGenerator;
+#[cfg(feature = "std")]
+pub use latex::{LatexForm, LatexOptions};
+#[cfg(feature = "std")]
+pub use markdown::MarkdownStyle;
+#[cfg(feature = "std")]
+pub use movegen::{MoveError, MoveKind};
+#[cfg(feature = "std")]
+pub use openings::{OpeningBook, OpeningBookError, OpeningEntry};
+#[cfg(feature = "parallel")]
+pub use parallel::{par_parse_reader, parse_fens_par};
+#[cfg(feature = "std")]
+pub use pgn::{final_position, PgnError, San};
+#[cfg(feature = "std")]
+pub use repair::{repair_fen, RepairWarning};
+#[cfg(feature = "std")]
+pub use repl::ReplSession;
+#[cfg(feature = "std")]
+pub use svg::SvgOptions;
+#[cfg(feature = "std")]
+pub use validation::ValidationError;
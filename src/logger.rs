@@ -0,0 +1,75 @@
+/* Diagnostics du programme sur la sortie d'erreur.
+
+Par défaut, les messages sont écrits pour être lus : diagnostic souligné, rapport sur plusieurs lignes. Avec `--quiet` ou `--log-format json`, chaque message devient un enregistrement d'une seule ligne, `niveau: message` ou objet JSON, que les scripts peuvent filtrer sans ambiguïté. */
+
+use fen_parser::json::json_string;
+
+/* Forme des enregistrements choisie par `--log-format` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+/* Gravité d'un message */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Info,
+}
+
+impl Level {
+    fn name(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Info => "info",
+        }
+    }
+}
+
+/* Destination des diagnostics, selon `--quiet` et `--log-format` */
+#[derive(Debug, Clone, Copy)]
+pub struct Logger {
+    quiet: bool,
+    format: Option<LogFormat>,
+}
+
+impl Logger {
+    pub fn new(quiet: bool, format: Option<LogFormat>) -> Self {
+        Self { quiet, format }
+    }
+
+    /* Vrai si seule la sortie demandée doit atteindre la sortie standard */
+    pub fn quiet(&self) -> bool {
+        self.quiet
+    }
+
+    /* Vrai si les messages sont des enregistrements d'une ligne plutôt que du texte libre */
+    pub fn structured(&self) -> bool {
+        self.quiet || self.format.is_some()
+    }
+
+    /* Écrit un message ; en mode silencieux, les messages d'information sont omis */
+    pub fn log(&self, level: Level, message: &str) {
+        if self.quiet && level == Level::Info {
+            return;
+        }
+        match (self.structured(), self.format) {
+            (false, _) => eprintln!("{}", message),
+            (true, Some(LogFormat::Json)) => eprintln!(
+                "{{\"level\":{},\"message\":{}}}",
+                json_string(level.name()),
+                json_string(message)
+            ),
+            (true, _) => eprintln!("{}: {}", level.name(), message.replace('\n', " ")),
+        }
+    }
+
+    pub fn error(&self, message: &str) {
+        self.log(Level::Error, message);
+    }
+
+    pub fn info(&self, message: &str) {
+        self.log(Level::Info, message);
+    }
+}
@@ -1,23 +1,807 @@
 /* Programme principal pour visualiser des positions FEN. */
 
-use fen_parser::{ChessPosition, FenError};
+mod args;
+mod logger;
+mod stats;
+
+use args::{ColorChoice, OutputFormat};
+use fen_parser::{
+    batch, parse_fen_all_errors, render_diff, BatchSummary, ChessPosition, Color, DialectError,
+    DisplayMode, DisplayOptions, EpdRecord, FenDialect, FenError, Move, PieceTheme, PositionDiff,
+    RandomPositionGenerator, ReplSession, SvgOptions,
+};
+use logger::Logger;
 use std::env;
+use std::fmt;
+use std::io::{BufRead, IsTerminal, Read, Write};
+
+/* Le damier coloré n'est activé que sur un terminal et si NO_COLOR est absent */
+fn color_enabled() -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && std::io::stdout().is_terminal()
+}
+
+/* Bilan du mode `validate`, converti en code de sortie */
+#[derive(Default)]
+struct ValidationOutcome {
+    syntax: bool,
+    semantic: bool,
+    io: bool,
+}
+
+impl ValidationOutcome {
+    /* 0 si tout est valide, 1 pour une erreur de syntaxe, 2 pour une position incohérente et
+     * 3 pour une erreur d'entrée-sortie ; en cas de cumul, l'entrée-sortie puis la syntaxe priment */
+    fn exit_code(&self) -> i32 {
+        if self.io {
+            3
+        } else if self.syntax {
+            1
+        } else if self.semantic {
+            2
+        } else {
+            0
+        }
+    }
+}
+
+/* Vérifie la syntaxe puis la cohérence d'une FEN ; seul un échec est affiché, sauf en mode verbeux */
+fn validate(
+    label: &str,
+    fen: &str,
+    verbose: bool,
+    outcome: &mut ValidationOutcome,
+    logger: &Logger,
+) {
+    match parse_fen_all_errors(fen) {
+        Err(errors) => {
+            outcome.syntax = true;
+            print_report(label, "syntax error", &errors, logger);
+        }
+        Ok(position) => match position.validate() {
+            Err(errors) => {
+                outcome.semantic = true;
+                print_report(label, "validation error", &errors, logger);
+            }
+            Ok(()) => {
+                if verbose && !logger.quiet() {
+                    println!("{}: OK", label);
+                }
+            }
+        },
+    }
+}
+
+/* Rapport complet d'une FEN refusée : le nombre d'erreurs puis chacune, indentée ; en
+ * sortie structurée, un enregistrement par erreur sur la sortie d'erreur */
+fn print_report<E: fmt::Display>(label: &str, what: &str, errors: &[E], logger: &Logger) {
+    if logger.structured() {
+        for error in errors {
+            logger.error(&format!("{}: {}", label, error));
+        }
+        return;
+    }
+    let plural = if errors.len() == 1 { "" } else { "s" };
+    println!("{}: {} {}{}", label, errors.len(), what, plural);
+    for error in errors {
+        println!("  {}", error);
+    }
+}
+
+/* Valide chaque ligne d'un flux ; une erreur de lecture interrompt le flux */
+fn validate_stream<R: BufRead>(
+    path: &str,
+    input: R,
+    verbose: bool,
+    outcome: &mut ValidationOutcome,
+    logger: &Logger,
+) {
+    for entry in batch::entries(input) {
+        match entry {
+            Ok(entry) => {
+                let label = format!("{}:{}", path, entry.line);
+                validate(&label, &entry.fen, verbose, outcome, logger);
+            }
+            Err(error) => {
+                logger.error(&format!("{}: {}", path, error));
+                outcome.io = true;
+                break;
+            }
+        }
+    }
+}
 
-fn main() -> Result<(), FenError> {
+fn main() {
     let args: Vec<String> = env::args().collect();
+    let cli = match args::parse_args(&args[1..]) {
+        Ok(cli) => cli,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+    let logger = Logger::new(cli.quiet, cli.log_format);
+
+    if cli.help || cli.command == "help" {
+        let topic = if cli.help {
+            Some(cli.command).filter(|_| cli.explicit_command)
+        } else {
+            cli.positionals.first().map(String::as_str)
+        };
+        match topic {
+            None => print!("{}", args::general_help()),
+            Some(command) => match args::command_help(command) {
+                Some(help) => print!("{}", help),
+                None => {
+                    logger.error(&format!("Unknown command '{}'", command));
+                    std::process::exit(2);
+                }
+            },
+        }
+        return;
+    }
+
+    /* `convert` produit une FEN par défaut, les autres commandes un plateau */
+    let format = cli.format.unwrap_or(if cli.command == "convert" {
+        OutputFormat::Fen
+    } else {
+        OutputFormat::Unicode
+    });
+
+    let mut options = DisplayOptions::default();
+    /* `auto` ne colore que sur un terminal, hors NO_COLOR ; `always` colore même redirigé */
+    if cli.color == ColorChoice::Always || (cli.color == ColorChoice::Auto && color_enabled()) {
+        options.mode = DisplayMode::Color;
+    }
+    /* L'orientation n'a de sens que pour les formats qui dessinent le plateau */
+    if let Some((orientation, flag)) = cli.orientation {
+        if !format.is_board() {
+            logger.error(&format!(
+                "{} only applies to the unicode and ascii formats",
+                flag
+            ));
+            std::process::exit(2);
+        }
+        options.orientation = orientation;
+    }
+
+    let code = match cli.command {
+        "validate" => run_validate(&cli, &logger),
+        "diff" => run_diff(&cli.positionals, &logger),
+        "apply" => run_apply(&cli, format, &options, &logger),
+        "random" => run_random(&cli),
+        "svg" => run_svg(&cli, &options, &logger),
+        "epd" => run_epd(&cli, &logger),
+        "stats" => run_stats(&cli, &logger),
+        "perft" => run_perft(&cli, &logger),
+        "convert" if cli.to.is_some() || cli.from != FenDialect::Fen => {
+            run_convert(&cli, format, &options, &logger)
+        }
+        "repl" => run_repl(options, &logger),
+        /* Sans aucun argument sur un terminal, une session interactive s'ouvre */
+        _ if args.len() == 1 && std::io::stdin().is_terminal() => run_repl(options, &logger),
+        command => run_show(&cli, command == "explain", format, &options, &logger),
+    };
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/* `validate` contrôle les FEN données, le fichier `--file` ou chaque ligne de l'entrée
+ * standard, et ne communique que par son rapport d'échec et son code de sortie */
+fn run_validate(cli: &args::Cli, logger: &Logger) -> i32 {
+    let mut outcome = ValidationOutcome::default();
+    match cli.file.as_deref() {
+        Some("-") => validate_stream(
+            "<stdin>",
+            std::io::stdin().lock(),
+            cli.verbose,
+            &mut outcome,
+            logger,
+        ),
+        Some(path) => match std::fs::File::open(path) {
+            Ok(handle) => {
+                let input = std::io::BufReader::new(handle);
+                validate_stream(path, input, cli.verbose, &mut outcome, logger);
+            }
+            Err(error) => {
+                logger.error(&format!("{}: {}", path, error));
+                outcome.io = true;
+            }
+        },
+        None if !cli.positionals.is_empty() => {
+            for (index, fen) in cli.positionals.iter().enumerate() {
+                let label = format!("argument {}", index + 1);
+                validate(&label, fen, cli.verbose, &mut outcome, logger);
+            }
+        }
+        None => validate_stream(
+            "<stdin>",
+            std::io::stdin().lock(),
+            cli.verbose,
+            &mut outcome,
+            logger,
+        ),
+    }
+    outcome.exit_code()
+}
+
+/* `diff` compare deux positions : 0 si identiques, 1 si différentes, 2 si l'une est invalide */
+fn run_diff(fens: &[String], logger: &Logger) -> i32 {
+    if fens.len() != 2 {
+        logger.error("diff expects exactly two FEN strings");
+        return 2;
+    }
+    let mut positions = Vec::new();
+    for fen in fens {
+        match ChessPosition::from_fen(fen) {
+            Ok(position) => positions.push(position),
+            Err(error) => {
+                report_error(fen, &error, logger);
+                return 2;
+            }
+        }
+    }
+    if !logger.quiet() {
+        print!("{}", render_diff(&positions[0], &positions[1]));
+    }
+    let identical = PositionDiff::between(&positions[0], &positions[1]).is_empty();
+    if identical {
+        0
+    } else {
+        1
+    }
+}
+
+/* `apply` joue des coups UCI depuis une FEN (ou `startpos`) ; le premier coup refusé
+ * interrompt la série en indiquant la position atteinte */
+fn run_apply(
+    cli: &args::Cli,
+    format: OutputFormat,
+    options: &DisplayOptions,
+    logger: &Logger,
+) -> i32 {
+    let Some((start, moves)) = cli.positionals.split_first() else {
+        logger.error("apply expects a FEN (or startpos) followed by UCI moves");
+        return 2;
+    };
+    let mut position = if start.as_str() == "startpos" {
+        ChessPosition::default()
+    } else {
+        match ChessPosition::from_fen(start) {
+            Ok(position) => position,
+            Err(error) => {
+                report_error(start, &error, logger);
+                return 1;
+            }
+        }
+    };
+    for (index, uci) in moves.iter().enumerate() {
+        let result = match Move::from_uci(uci) {
+            Some(mv) => position.make_move(mv).map_err(|error| error.to_string()),
+            None => Err(format!("'{}' is not a valid UCI move", uci)),
+        };
+        if let Err(reason) = result {
+            logger.error(&format!("move {}: {}", index + 1, reason));
+            logger.error(&format!("position reached: {}", position.to_fen()));
+            return 1;
+        }
+        if cli.trace {
+            println!("{}", position.to_fen());
+        }
+    }
+    if !cli.trace {
+        render(&position, false, format, options, logger);
+    }
+    0
+}
+
+/* `perft` compte les feuilles de l'arbre des coups, avec la mise en page de `go perft` de
+ * Stockfish ; la durée et la vitesse vont sur la sortie d'erreur */
+fn run_perft(cli: &args::Cli, logger: &Logger) -> i32 {
+    let [fen] = cli.positionals.as_slice() else {
+        logger.error("perft expects one FEN (or startpos)");
+        return 2;
+    };
+    let Some(depth) = cli.depth else {
+        logger.error("perft expects --depth <n>");
+        return 2;
+    };
+    let depth = u32::try_from(depth).unwrap_or(u32::MAX);
+    let position = if fen.as_str() == "startpos" {
+        ChessPosition::default()
+    } else {
+        match ChessPosition::from_fen(fen) {
+            Ok(position) => position,
+            Err(error) => {
+                report_error(fen, &error, logger);
+                return 1;
+            }
+        }
+    };
+
+    let started = std::time::Instant::now();
+    let nodes = if cli.divide {
+        let moves = position.divide(depth);
+        for (mv, count) in &moves {
+            println!("{}: {}", mv, count);
+        }
+        println!();
+        moves.iter().map(|(_, count)| count).sum()
+    } else {
+        position.perft(depth)
+    };
+    let elapsed = started.elapsed();
+    println!("Nodes searched: {}", nodes);
+    let speed = (nodes as f64 / elapsed.as_secs_f64().max(1e-9)) as u64;
+    logger.info(&format!(
+        "time: {} ms, {} nodes/s",
+        elapsed.as_millis(),
+        speed
+    ));
+    0
+}
+
+/* `random` affiche des FEN générées à partir d'une graine, une par ligne */
+fn run_random(cli: &args::Cli) -> i32 {
+    let seed = cli.seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+    });
+    let mut generator = RandomPositionGenerator::new(seed);
+    for _ in 0..cli.count {
+        let position = if cli.legal_ish {
+            generator.random_plausible()
+        } else {
+            generator.random_board()
+        };
+        println!("{}", position.to_fen());
+    }
+    0
+}
+
+/* `svg` écrit le diagramme d'une position dans un fichier, sans jamais en écraser un
+ * existant sauf avec `--force` ; une erreur d'entrée-sortie donne le code 3 */
+fn run_svg(cli: &args::Cli, options: &DisplayOptions, logger: &Logger) -> i32 {
+    let [fen] = cli.positionals.as_slice() else {
+        logger.error("svg expects exactly one FEN string");
+        return 2;
+    };
+    let position = match ChessPosition::from_fen(fen) {
+        Ok(position) => position,
+        Err(error) => {
+            report_error(fen, &error, logger);
+            return 1;
+        }
+    };
+    let svg_options = SvgOptions {
+        square_size: (cli.size.unwrap_or(360) / 8).clamp(1, u32::MAX as u64) as u32,
+        coordinates: cli.coordinates,
+        flipped: options.orientation.bottom_color(position.active_color) == Color::Black,
+        ..SvgOptions::default()
+    };
+    let svg = position.render_svg(&svg_options);
+
+    let path = cli.output.as_deref().unwrap_or("-");
+    if path == "-" {
+        print!("{}", svg);
+        return 0;
+    }
+    let file = if cli.force {
+        std::fs::File::create(path)
+    } else {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+    };
+    let result = file.and_then(|mut file| file.write_all(svg.as_bytes()));
+    match result {
+        Ok(()) => 0,
+        Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {
+            logger.error(&format!(
+                "{}: file already exists (use --force to overwrite it)",
+                path
+            ));
+            3
+        }
+        Err(error) => {
+            logger.error(&format!("{}: {}", path, error));
+            3
+        }
+    }
+}
+
+/* `epd` lit une suite de test EPD enregistrement par enregistrement, en extrait au besoin
+ * une colonne ou des FEN, puis affiche le bilan ; 1 si une ligne est invalide, 3 en cas
+ * d'erreur d'entrée-sortie */
+fn run_epd(cli: &args::Cli, logger: &Logger) -> i32 {
+    let [path] = cli.positionals.as_slice() else {
+        logger.error("epd expects one file path ('-' for standard input)");
+        return 2;
+    };
+    if cli.print_field.is_some() && cli.to_fen {
+        logger.error("--print-field and --to-fen cannot be combined");
+        return 2;
+    }
+    let (summary, io_error) = if path == "-" {
+        epd_stream("<stdin>", std::io::stdin().lock(), cli, logger)
+    } else {
+        match std::fs::File::open(path) {
+            Ok(handle) => epd_stream(path, std::io::BufReader::new(handle), cli, logger),
+            Err(error) => {
+                logger.error(&format!("{}: {}", path, error));
+                return 3;
+            }
+        }
+    };
+    logger.info(&summary.to_string());
+    if io_error {
+        3
+    } else if summary.all_ok() {
+        0
+    } else {
+        1
+    }
+}
+
+/* Traite un flux EPD ligne à ligne ; renvoie le bilan et vrai si la lecture a échoué */
+fn epd_stream<R: BufRead>(
+    path: &str,
+    input: R,
+    cli: &args::Cli,
+    logger: &Logger,
+) -> (BatchSummary, bool) {
+    let mut summary = BatchSummary::default();
+    for entry in batch::entries(input) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                logger.error(&format!("{}: {}", path, error));
+                return (summary, true);
+            }
+        };
+        match EpdRecord::parse(&entry.fen) {
+            Ok(record) => {
+                match cli.print_field.as_deref() {
+                    Some("fen") => println!("{}", record.fields),
+                    Some(opcode) => println!("{}", record.operation(opcode).unwrap_or("")),
+                    None if cli.to_fen => println!("{}", record.to_fen()),
+                    None => {}
+                }
+                summary.record(true);
+            }
+            Err(error) => {
+                logger.error(&format!("{}:{}: {}", path, entry.line, error));
+                summary.record(false);
+            }
+        }
+    }
+    (summary, false)
+}
+
+/* `stats` résume un corpus lu en une passe ; une ligne invalide est signalée et comptée,
+ * seule une erreur d'entrée-sortie fait échouer la commande (code 3) */
+fn run_stats(cli: &args::Cli, logger: &Logger) -> i32 {
+    if !cli.positionals.is_empty() {
+        logger.error("stats reads --file or standard input, not FEN arguments");
+        return 2;
+    }
+    let top = cli.top.unwrap_or(5) as usize;
+    let result = match cli.file.as_deref() {
+        None | Some("-") => stats_stream(cli, "<stdin>", std::io::stdin().lock(), logger),
+        Some(path) => match std::fs::File::open(path) {
+            Ok(handle) => stats_stream(cli, path, std::io::BufReader::new(handle), logger),
+            Err(error) => Err(format!("{}: {}", path, error)),
+        },
+    };
+    match result {
+        Ok(stats) => {
+            print!("{}", stats.report(top));
+            0
+        }
+        Err(message) => {
+            logger.error(&message);
+            3
+        }
+    }
+}
+
+/* Accumule les statistiques d'un flux ; l'erreur est le message d'entrée-sortie */
+fn stats_stream<R: BufRead>(
+    cli: &args::Cli,
+    path: &str,
+    input: R,
+    logger: &Logger,
+) -> Result<stats::CorpusStats, String> {
+    let mut stats = stats::CorpusStats::default();
+    for entry in batch::entries(input) {
+        let entry = entry.map_err(|error| format!("{}: {}", path, error))?;
+        match ChessPosition::from_fen(&entry.fen) {
+            Ok(position) if !material_matches(cli, &position) => {}
+            Ok(position) => stats.record(&position),
+            Err(error) => {
+                logger.info(&format!("{}:{}: {}", path, entry.line, error));
+                stats.record_failure();
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/* `repl` ouvre une session interactive ; l'invite n'apparaît que sur un terminal */
+fn run_repl(options: DisplayOptions, logger: &Logger) -> i32 {
+    let interactive = std::io::stdin().is_terminal();
+    let mut session = ReplSession::with_options(options);
+    let stdout = std::io::stdout();
+    match session.run(std::io::stdin().lock(), &mut stdout.lock(), interactive) {
+        Ok(()) => 0,
+        Err(error) => {
+            logger.error(&error.to_string());
+            3
+        }
+    }
+}
+
+/* `show`, `explain` et `convert` rendent les FEN données, celles du fichier `--file`,
+ * ou celle lue sur l'entrée standard quand elle est redirigée */
+fn run_show(
+    cli: &args::Cli,
+    explain: bool,
+    format: OutputFormat,
+    options: &DisplayOptions,
+    logger: &Logger,
+) -> i32 {
+    if let Some(path) = &cli.file {
+        let summary = if path == "-" {
+            let stdin = std::io::stdin();
+            let input = stdin.lock();
+            run_batch(cli, "<stdin>", input, explain, format, options, logger)
+        } else {
+            match std::fs::File::open(path) {
+                Ok(handle) => {
+                    let input = std::io::BufReader::new(handle);
+                    run_batch(cli, path, input, explain, format, options, logger)
+                }
+                Err(error) => {
+                    logger.error(&format!("{}: {}", path, error));
+                    return 3;
+                }
+            }
+        };
+        logger.info(&summary.to_string());
+        return if summary.all_ok() { 0 } else { 1 };
+    }
+
+    let mut fens = cli.positionals.clone();
+    let stdin = std::io::stdin();
+    let mut piped = String::new();
+    if fens.is_empty() && !stdin.is_terminal() && stdin.lock().read_to_string(&mut piped).is_ok() {
+        let piped = piped.trim();
+        if !piped.is_empty() {
+            fens.push(piped.to_string());
+        }
+    }
 
-    if args.len() != 2 {
-        println!("Usage: {} <FEN_string>", args[0]);
-        println!(
-            "Example: {} \"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\"",
-            args[0]
-        );
-        return Ok(());
+    if fens.is_empty() {
+        print!("{}", args::general_help());
+        return 0;
     }
 
-    let fen = &args[1];
-    let position = ChessPosition::from_fen(fen)?;
-    position.display_ascii();
+    /* Plusieurs FEN sont rendues l'une après l'autre, chacune précédée d'un en-tête ;
+     * un échec est signalé sans interrompre les suivantes */
+    let mut failed = false;
+    for (index, fen) in fens.iter().enumerate() {
+        if fens.len() > 1 && !logger.quiet() {
+            print_header(
+                index,
+                &format!("[{}/{}] {}", index + 1, fens.len(), fen),
+                format,
+            );
+        }
+        match ChessPosition::from_fen(fen) {
+            Ok(position) => render(&position, explain, format, options, logger),
+            Err(error) => {
+                report_error(fen, &error, logger);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        1
+    } else {
+        0
+    }
+}
+
+/* `convert --from/--to` lit chaque position dans une variante de FEN et la réécrit dans une
+ * autre, ou l'affiche au format `--format` sans `--to` */
+fn run_convert(
+    cli: &args::Cli,
+    format: OutputFormat,
+    options: &DisplayOptions,
+    logger: &Logger,
+) -> i32 {
+    if cli.to.is_some() && cli.format.is_some() {
+        logger.error("--format does not apply with --to");
+        return 2;
+    }
+    /* Convertit une entrée ; une erreur est signalée à l'emplacement donné (`chemin:ligne`),
+     * ou avec la FEN elle-même pour un argument */
+    let convert = |text: &str, location: Option<String>| {
+        let result = match cli.from.parse(text) {
+            Ok(position) => match cli.to {
+                Some(dialect) => dialect.write(&position).map(|out| println!("{}", out)),
+                None => {
+                    render(&position, false, format, options, logger);
+                    Ok(())
+                }
+            },
+            Err(error) => Err(error),
+        };
+        match (result, location) {
+            (Ok(()), _) => true,
+            (Err(error), Some(location)) => {
+                logger.error(&format!("{}: {}", location, error));
+                false
+            }
+            (Err(DialectError::Fen(error)), None) => {
+                report_error(text, &error, logger);
+                false
+            }
+            (Err(error), None) => {
+                logger.error(&format!("error: {} (in {:?})", error, text));
+                false
+            }
+        }
+    };
+
+    let Some(path) = &cli.file else {
+        if cli.positionals.is_empty() {
+            logger.error("convert expects FEN arguments or --file");
+            return 2;
+        }
+        let mut failed = false;
+        for text in &cli.positionals {
+            failed |= !convert(text, None);
+        }
+        return i32::from(failed);
+    };
+
+    let input: Box<dyn BufRead> = if path == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        match std::fs::File::open(path) {
+            Ok(handle) => Box::new(std::io::BufReader::new(handle)),
+            Err(error) => {
+                logger.error(&format!("{}: {}", path, error));
+                return 3;
+            }
+        }
+    };
+    let label = if path == "-" {
+        "<stdin>"
+    } else {
+        path.as_str()
+    };
+    let mut summary = BatchSummary::default();
+    for entry in batch::entries(input) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                logger.error(&format!("{}: {}", label, error));
+                summary.record(false);
+                break;
+            }
+        };
+        let location = format!("{}:{}", label, entry.line);
+        summary.record(convert(&entry.fen, Some(location)));
+    }
+    logger.info(&summary.to_string());
+    if summary.all_ok() {
+        0
+    } else {
+        1
+    }
+}
+
+/* Lit un flux de FEN ligne à ligne, affiche chacune et renvoie le bilan.
+ * Les erreurs sont signalées sous la forme `chemin:ligne: message`. */
+fn run_batch<R: BufRead>(
+    cli: &args::Cli,
+    path: &str,
+    input: R,
+    explain: bool,
+    format: OutputFormat,
+    options: &DisplayOptions,
+    logger: &Logger,
+) -> BatchSummary {
+    let mut summary = BatchSummary::default();
+    for entry in batch::entries(input) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                logger.error(&format!("{}: {}", path, error));
+                summary.record(false);
+                break;
+            }
+        };
+        match ChessPosition::from_fen(&entry.fen) {
+            Ok(position) if !material_matches(cli, &position) => {}
+            Ok(position) => {
+                if !logger.quiet() {
+                    let header = format!("{}:{}: {}", path, entry.line, entry.fen);
+                    print_header(summary.ok, &header, format);
+                }
+                render(&position, explain, format, options, logger);
+                summary.record(true);
+            }
+            Err(error) => {
+                logger.error(&format!("{}:{}: {}", path, entry.line, error));
+                summary.record(false);
+            }
+        }
+    }
+    summary
+}
+
+/* Vrai sans `--material`, ou si la position remplit le filtre */
+fn material_matches(cli: &args::Cli, position: &ChessPosition) -> bool {
+    cli.material
+        .as_ref()
+        .is_none_or(|filter| filter.matches(position))
+}
+
+/* En-tête précédant chaque position d'une série ; JSON et FEN restent à une ligne par position */
+fn print_header(index: usize, header: &str, format: OutputFormat) {
+    if matches!(format, OutputFormat::Json | OutputFormat::Fen) {
+        return;
+    }
+    if index > 0 {
+        println!();
+    }
+    println!("{}", header);
+}
+
+/* Affiche une position dans le format demandé ; en mode silencieux, le plateau décoratif est omis */
+fn render(
+    position: &ChessPosition,
+    explain: bool,
+    format: OutputFormat,
+    options: &DisplayOptions,
+    logger: &Logger,
+) {
+    if logger.quiet() && format.is_board() && !explain {
+        return;
+    }
+    if explain {
+        print!("{}", position.explain());
+    } else {
+        match format {
+            OutputFormat::Unicode => position.display_with_options(options),
+            OutputFormat::Ascii => {
+                let mut options = options.clone();
+                options.style.theme = PieceTheme::ASCII;
+                options.style.empty_square_char = '.';
+                position.display_with_options(&options);
+            }
+            OutputFormat::Fen => println!("{}", position.to_fen()),
+            OutputFormat::Json => println!("{}", position.to_json()),
+            OutputFormat::Markdown => print!("{}", position.to_markdown()),
+        }
+    }
+}
 
-    Ok(())
+/* Signale une FEN invalide sur la sortie d'erreur ; avec les diagnostics, la FEN est
+ * recopiée et soulignée sous le fragment fautif, sauf en sortie structurée */
+fn report_error(fen: &str, error: &FenError, logger: &Logger) {
+    if logger.structured() {
+        logger.error(&error.to_string());
+        return;
+    }
+    #[cfg(feature = "diagnostics")]
+    eprint!("{}", error.diagnostic(fen));
+    #[cfg(not(feature = "diagnostics"))]
+    eprintln!("error: {} (in {:?})", error, fen);
 }
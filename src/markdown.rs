@@ -0,0 +1,51 @@
+/* Rendu Markdown d'une position d'échecs.
+
+Ce module produit soit une table Markdown (une cellule par case), soit un bloc de code contenant le rendu texte. */
+
+use crate::display::{DisplayOptions, PieceTheme};
+use crate::types::{ChessPosition, Square};
+
+/* Forme du rendu Markdown */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkdownStyle {
+    /* Table avec les colonnes en en-tête et les rangées en première colonne */
+    #[default]
+    Table,
+    /* Bloc de code contenant le plateau texte */
+    CodeBlock,
+}
+
+impl ChessPosition {
+    /* Rend la position en table Markdown */
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(MarkdownStyle::Table)
+    }
+
+    /* Rend la position en Markdown dans la forme demandée */
+    pub fn to_markdown_with(&self, style: MarkdownStyle) -> String {
+        match style {
+            MarkdownStyle::Table => self.markdown_table(),
+            MarkdownStyle::CodeBlock => format!(
+                "```\n{}```\n",
+                self.render_board(&DisplayOptions::default())
+            ),
+        }
+    }
+
+    fn markdown_table(&self) -> String {
+        let theme = PieceTheme::UNICODE;
+        let mut out = String::from("|   | a | b | c | d | e | f | g | h |\n");
+        out.push_str("|---|---|---|---|---|---|---|---|---|\n");
+        for rank in (0..8u8).rev() {
+            out.push_str(&format!("| {} |", rank + 1));
+            for file in 0..8u8 {
+                let glyph = Square::from_coords(file, rank)
+                    .and_then(|square| self.piece_at(square))
+                    .map_or(" ", |piece| theme.glyph(piece));
+                out.push_str(&format!(" {} |", glyph));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
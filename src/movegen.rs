@@ -0,0 +1,627 @@
+/* Génération des coups légaux et application d'un coup.
+
+Ce module joue les coups des échecs classiques : déplacements des pièces, roques, prise en passant et promotions, en refusant tout coup qui laisserait son propre roi en échec. */
+
+use crate::bitboard::Bitboards;
+#[cfg(feature = "telemetry")]
+use crate::telemetry::{self, Level};
+use crate::types::{
+    CastlingSide, ChessPosition, Color, File, Move, Piece, PieceKind, Rank, Square, SquareSet,
+};
+use thiserror::Error;
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+const PROMOTIONS: [PieceKind; 4] = [
+    PieceKind::Queen,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+];
+
+/* Erreur renvoyée par `make_move` ; la position reste alors inchangée */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum MoveError {
+    #[error("no piece on {0}")]
+    EmptySquare(Square),
+    #[error("the piece on {0} does not belong to the side to move")]
+    WrongColor(Square),
+    #[error("{0} is not legal in this position")]
+    Illegal(Move),
+}
+
+/* Nature d'un coup dans la position où il va être joué, donnée par `classify_move` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MoveKind {
+    /* Prise d'une pièce adverse, prise en passant comprise */
+    pub capture: bool,
+    pub en_passant: bool,
+    /* Roque, reconnu au déplacement du roi de deux colonnes */
+    pub castle: Option<CastlingSide>,
+    pub promotion: Option<PieceKind>,
+    pub double_pawn_push: bool,
+    /* Coup de pion ou prise : le compteur de demi-coups repart de 0 */
+    pub resets_halfmove_clock: bool,
+}
+
+impl MoveKind {
+    /* Vrai pour un roque de cette aile */
+    pub fn is_castle(&self, side: CastlingSide) -> bool {
+        self.castle == Some(side)
+    }
+
+    /* Ni prise, ni roque, ni promotion */
+    pub fn is_quiet(&self) -> bool {
+        !self.capture && self.castle.is_none() && self.promotion.is_none()
+    }
+}
+
+/* Case décalée de (colonnes, rangées), si elle reste sur l'échiquier */
+fn offset(square: Square, files: i8, ranks: i8) -> Option<Square> {
+    let file = square.file().index() as i8 + files;
+    let rank = square.rank().index() as i8 + ranks;
+    if (0..8).contains(&file) && (0..8).contains(&rank) {
+        Square::from_coords(file as u8, rank as u8)
+    } else {
+        None
+    }
+}
+
+/* Sens de marche des pions d'un camp */
+fn pawn_direction(color: Color) -> i8 {
+    match color {
+        Color::White => 1,
+        Color::Black => -1,
+    }
+}
+
+/* Rangée de départ du roi et des tours d'un camp */
+fn home_rank(color: Color) -> Rank {
+    match color {
+        Color::White => Rank::First,
+        Color::Black => Rank::Eighth,
+    }
+}
+
+impl ChessPosition {
+    /* Place une pièce sur une case, ou la vide, en reportant le changement sur les masques */
+    fn set(&mut self, square: Square, piece: Option<Piece>, boards: Option<&mut Bitboards>) {
+        if let Some(boards) = boards {
+            boards.set(square, self.piece_at(square), piece);
+        }
+        self.pieces[square.rank().index()][square.file().index()] = piece;
+    }
+
+    /* Case du roi d'un camp, s'il est sur l'échiquier */
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        let king = Piece {
+            color,
+            kind: PieceKind::King,
+        };
+        (0..64)
+            .filter_map(Square::from_index)
+            .find(|&square| self.piece_at(square) == Some(king))
+    }
+
+    /* Vrai si une pièce du camp `by` attaque la case */
+    pub fn is_attacked(&self, square: Square, by: Color) -> bool {
+        let is = |target: Option<Square>, kinds: &[PieceKind]| {
+            target
+                .and_then(|target| self.piece_at(target))
+                .is_some_and(|piece| piece.color == by && kinds.contains(&piece.kind))
+        };
+
+        let behind = -pawn_direction(by);
+        if is(offset(square, -1, behind), &[PieceKind::Pawn])
+            || is(offset(square, 1, behind), &[PieceKind::Pawn])
+        {
+            return true;
+        }
+        if KNIGHT_OFFSETS
+            .iter()
+            .any(|&(df, dr)| is(offset(square, df, dr), &[PieceKind::Knight]))
+        {
+            return true;
+        }
+        if KING_OFFSETS
+            .iter()
+            .any(|&(df, dr)| is(offset(square, df, dr), &[PieceKind::King]))
+        {
+            return true;
+        }
+
+        let sliders = [
+            (ROOK_DIRECTIONS, PieceKind::Rook),
+            (BISHOP_DIRECTIONS, PieceKind::Bishop),
+        ];
+        for (directions, kind) in sliders {
+            for (df, dr) in directions {
+                let mut current = offset(square, df, dr);
+                while let Some(target) = current {
+                    if let Some(piece) = self.piece_at(target) {
+                        if piece.color == by
+                            && (piece.kind == kind || piece.kind == PieceKind::Queen)
+                        {
+                            return true;
+                        }
+                        break;
+                    }
+                    current = offset(target, df, dr);
+                }
+            }
+        }
+        false
+    }
+
+    /* Cases attaquées par le camp `by`, occupées ou non */
+    pub fn attack_map(&self, by: Color) -> SquareSet {
+        let boards = self.bitboards();
+        SquareSet::full()
+            .iter()
+            .filter(|&square| boards.is_attacked(square, by))
+            .collect()
+    }
+
+    /* Vrai si le camp ayant le trait est en échec */
+    pub fn in_check(&self) -> bool {
+        let color = self.active_color;
+        self.king_square(color)
+            .is_some_and(|king| self.is_attacked(king, color.opposite()))
+    }
+
+    /* Coups légaux du camp ayant le trait, en notation de case à case */
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let color = self.active_color;
+        let boards = self.bitboards();
+        self.pseudo_legal_moves()
+            .into_iter()
+            .filter(|&mv| {
+                let mut next = self.clone();
+                let mut next_boards = boards;
+                next.play_tracked(mv, Some(&mut next_boards));
+                /* Comme `king_square`, seul le premier roi trouvé compte */
+                !next_boards
+                    .bitboard(color, PieceKind::King)
+                    .iter()
+                    .next()
+                    .is_some_and(|king| next_boards.is_attacked(king, color.opposite()))
+            })
+            .collect()
+    }
+
+    /* Coup légal écrit en notation algébrique abrégée (SAN) : `e4`, `Nbd7`, `exd6`, `e8=Q`,
+     * `O-O-O`. Les marques d'échec et d'appréciation (`+`, `#`, `!`, `?`) sont ignorées, le
+     * `=` de la promotion est facultatif. `None` si aucun coup légal ne correspond, ou si
+     * plusieurs correspondent faute de précision suffisante. */
+    pub fn move_from_san(&self, san: &str) -> Option<Move> {
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+        let legal = self.legal_moves();
+        let castle = match san {
+            "O-O" | "0-0" => Some(CastlingSide::Kingside),
+            "O-O-O" | "0-0-0" => Some(CastlingSide::Queenside),
+            _ => None,
+        };
+        if let Some(side) = castle {
+            return legal
+                .into_iter()
+                .find(|&mv| self.classify_move(mv).is_castle(side));
+        }
+        if !san.is_ascii() {
+            return None;
+        }
+
+        let (kind, rest) = match san.chars().next()? {
+            c @ ('K' | 'Q' | 'R' | 'B' | 'N') => (Piece::from_fen_char(c)?.kind, &san[1..]),
+            _ => (PieceKind::Pawn, san),
+        };
+        let (rest, promotion) = match rest.chars().last()? {
+            c @ ('Q' | 'R' | 'B' | 'N') if kind == PieceKind::Pawn => (
+                rest[..rest.len() - 1].trim_end_matches('='),
+                Some(Piece::from_fen_char(c)?.kind),
+            ),
+            _ => (rest, None),
+        };
+        let split = rest.len().checked_sub(2)?;
+        let to = Square::from_name(&rest[split..])?;
+        /* Ce qui précède la case d'arrivée : colonne ou rangée de départ, et `x` d'une prise */
+        let (mut file, mut rank) = (None, None);
+        for c in rest[..split].trim_end_matches('x').chars() {
+            match c {
+                'a'..='h' => file = Some(c as usize - 'a' as usize),
+                '1'..='8' => rank = Some(c as usize - '1' as usize),
+                _ => return None,
+            }
+        }
+
+        let mut candidates = legal.into_iter().filter(|mv| {
+            mv.to == to
+                && mv.promotion == promotion
+                && self
+                    .piece_at(mv.from)
+                    .is_some_and(|piece| piece.kind == kind)
+                && file.is_none_or(|file| mv.from.file().index() == file)
+                && rank.is_none_or(|rank| mv.from.rank().index() == rank)
+        });
+        let found = candidates.next()?;
+        candidates.next().is_none().then_some(found)
+    }
+
+    /* Nombre de feuilles de l'arbre des coups légaux à la profondeur donnée (perft) */
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        moves
+            .into_iter()
+            .map(|mv| {
+                let mut next = self.clone();
+                next.play(mv);
+                next.perft(depth - 1)
+            })
+            .sum()
+    }
+
+    /* Perft détaillé par coup joué depuis la position, dans l'ordre UCI */
+    pub fn divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        let mut moves: Vec<(Move, u64)> = self
+            .legal_moves()
+            .into_iter()
+            .map(|mv| {
+                let mut next = self.clone();
+                next.play(mv);
+                (mv, next.perft(depth - 1))
+            })
+            .collect();
+        moves.sort_by_key(|(mv, _)| mv.to_string());
+        moves
+    }
+
+    /* Joue un coup légal ; un coup illégal est refusé sans modifier la position */
+    pub fn make_move(&mut self, mv: Move) -> Result<(), MoveError> {
+        #[cfg(feature = "telemetry")]
+        let _span = telemetry::span(Level::Debug, "make_move", || vec![("move", mv.to_string())]);
+        self.check_move(mv).inspect_err(|_error| {
+            #[cfg(feature = "telemetry")]
+            telemetry::event(Level::Error, "move rejected", || {
+                vec![("move", mv.to_string()), ("error", _error.to_string())]
+            });
+        })?;
+        self.play(mv);
+        Ok(())
+    }
+
+    /* Comme `make_move`, en tenant à jour des masques d'occupation gardés à côté de la
+     * position ; ils doivent décrire la position avant le coup */
+    pub fn make_move_tracked(&mut self, mv: Move, boards: &mut Bitboards) -> Result<(), MoveError> {
+        self.check_move(mv)?;
+        self.play_tracked(mv, Some(boards));
+        Ok(())
+    }
+
+    /* Erreur qu'entraînerait le coup, s'il n'est pas légal */
+    fn check_move(&self, mv: Move) -> Result<(), MoveError> {
+        match self.piece_at(mv.from) {
+            None => return Err(MoveError::EmptySquare(mv.from)),
+            Some(piece) if piece.color != self.active_color => {
+                return Err(MoveError::WrongColor(mv.from))
+            }
+            Some(_) => {}
+        }
+        if !self.legal_moves().contains(&mv) {
+            return Err(MoveError::Illegal(mv));
+        }
+        Ok(())
+    }
+
+    /* Coups respectant le déplacement des pièces, sans vérifier l'échec au roi */
+    fn pseudo_legal_moves(&self) -> Vec<Move> {
+        let color = self.active_color;
+        let mut moves = Vec::new();
+        for from in (0..64).filter_map(Square::from_index) {
+            let Some(piece) = self.piece_at(from) else {
+                continue;
+            };
+            if piece.color != color {
+                continue;
+            }
+            match piece.kind {
+                PieceKind::Pawn => self.pawn_moves(from, color, &mut moves),
+                PieceKind::Knight => self.step_moves(from, &KNIGHT_OFFSETS, &mut moves),
+                PieceKind::King => {
+                    self.step_moves(from, &KING_OFFSETS, &mut moves);
+                    self.castling_moves(from, color, &mut moves);
+                }
+                PieceKind::Bishop => self.slide_moves(from, &BISHOP_DIRECTIONS, &mut moves),
+                PieceKind::Rook => self.slide_moves(from, &ROOK_DIRECTIONS, &mut moves),
+                PieceKind::Queen => {
+                    self.slide_moves(from, &BISHOP_DIRECTIONS, &mut moves);
+                    self.slide_moves(from, &ROOK_DIRECTIONS, &mut moves);
+                }
+            }
+        }
+        moves
+    }
+
+    /* Vrai si la case est vide ou occupée par l'adversaire du camp ayant le trait */
+    fn can_land(&self, square: Square) -> bool {
+        self.piece_at(square)
+            .is_none_or(|piece| piece.color != self.active_color)
+    }
+
+    fn step_moves(&self, from: Square, offsets: &[(i8, i8)], moves: &mut Vec<Move>) {
+        for &(df, dr) in offsets {
+            if let Some(to) = offset(from, df, dr).filter(|&to| self.can_land(to)) {
+                moves.push(Move::new(from, to));
+            }
+        }
+    }
+
+    fn slide_moves(&self, from: Square, directions: &[(i8, i8)], moves: &mut Vec<Move>) {
+        for &(df, dr) in directions {
+            let mut current = offset(from, df, dr);
+            while let Some(to) = current {
+                match self.piece_at(to) {
+                    None => moves.push(Move::new(from, to)),
+                    Some(piece) => {
+                        if piece.color != self.active_color {
+                            moves.push(Move::new(from, to));
+                        }
+                        break;
+                    }
+                }
+                current = offset(to, df, dr);
+            }
+        }
+    }
+
+    fn pawn_moves(&self, from: Square, color: Color, moves: &mut Vec<Move>) {
+        let direction = pawn_direction(color);
+        let (start_rank, last_rank) = match color {
+            Color::White => (Rank::Second, Rank::Eighth),
+            Color::Black => (Rank::Seventh, Rank::First),
+        };
+        /* Une arrivée sur la dernière rangée se décline en quatre promotions */
+        let mut push = |to: Square| {
+            if to.rank() == last_rank {
+                for kind in PROMOTIONS {
+                    moves.push(Move {
+                        from,
+                        to,
+                        promotion: Some(kind),
+                    });
+                }
+            } else {
+                moves.push(Move::new(from, to));
+            }
+        };
+
+        if let Some(one) = offset(from, 0, direction).filter(|&to| self.piece_at(to).is_none()) {
+            push(one);
+            if from.rank() == start_rank {
+                if let Some(two) =
+                    offset(one, 0, direction).filter(|&to| self.piece_at(to).is_none())
+                {
+                    push(two);
+                }
+            }
+        }
+        for df in [-1, 1] {
+            let Some(to) = offset(from, df, direction) else {
+                continue;
+            };
+            let captures = self.piece_at(to).is_some_and(|piece| piece.color != color);
+            if captures || self.en_passant_target() == Some(to) {
+                push(to);
+            }
+        }
+    }
+
+    /* Roques autorisés : droits présents, cases intermédiaires vides, roi ni en échec
+     * ni traversant une case attaquée */
+    fn castling_moves(&self, from: Square, color: Color, moves: &mut Vec<Move>) {
+        let rank = home_rank(color);
+        if from != Square::new(File::E, rank) {
+            return;
+        }
+        let rights = self.castling_rights;
+        let (kingside, queenside) = match color {
+            Color::White => (rights.white_kingside, rights.white_queenside),
+            Color::Black => (rights.black_kingside, rights.black_queenside),
+        };
+        let rook = Some(Piece {
+            color,
+            kind: PieceKind::Rook,
+        });
+        let sides = [
+            (kingside, File::H, &[File::F, File::G][..], File::G),
+            (
+                queenside,
+                File::A,
+                &[File::B, File::C, File::D][..],
+                File::C,
+            ),
+        ];
+        for (allowed, rook_file, between, target) in sides {
+            if !allowed || self.piece_at(Square::new(rook_file, rank)) != rook {
+                continue;
+            }
+            if between
+                .iter()
+                .any(|&file| self.piece_at(Square::new(file, rank)).is_some())
+            {
+                continue;
+            }
+            /* Le roi part de e, traverse la case voisine et arrive sur la case cible */
+            let step = if target == File::G { File::F } else { File::D };
+            let safe = [File::E, step, target]
+                .iter()
+                .all(|&file| !self.is_attacked(Square::new(file, rank), color.opposite()));
+            if safe {
+                moves.push(Move::new(from, Square::new(target, rank)));
+            }
+        }
+    }
+
+    /* Case de prise en passant utilisable : sur la sixième rangée du camp au trait, derrière
+     * un pion adverse. Une case lue d'une FEN incohérente (e3 les blancs au trait, ou sans
+     * pion à prendre) n'ouvre aucune prise */
+    fn en_passant_target(&self) -> Option<Square> {
+        let (rank, victim_color) = match self.active_color {
+            Color::White => (Rank::Sixth, Color::Black),
+            Color::Black => (Rank::Third, Color::White),
+        };
+        let target = self
+            .en_passant_square()
+            .filter(|target| target.rank() == rank)?;
+        let victim = Some(Piece {
+            color: victim_color,
+            kind: PieceKind::Pawn,
+        });
+        (self.piece_at(self.en_passant_victim()?) == victim).then_some(target)
+    }
+
+    /* Nature d'un coup avant qu'il soit joué, sans contrôle de légalité ; un coup partant
+     * d'une case vide n'est de rien */
+    pub fn classify_move(&self, mv: Move) -> MoveKind {
+        let Some(piece) = self.piece_at(mv.from) else {
+            return MoveKind::default();
+        };
+        let pawn = piece.kind == PieceKind::Pawn;
+        let target = self.piece_at(mv.to);
+        let en_passant = pawn
+            && mv.from.file() != mv.to.file()
+            && target.is_none()
+            && self.en_passant_target() == Some(mv.to);
+        let capture = en_passant || target.is_some_and(|target| target.color != piece.color);
+        let files = mv.to.file().index() as i8 - mv.from.file().index() as i8;
+        let castle = match (piece.kind, files) {
+            (PieceKind::King, 2) => Some(CastlingSide::Kingside),
+            (PieceKind::King, -2) => Some(CastlingSide::Queenside),
+            _ => None,
+        };
+        MoveKind {
+            capture,
+            en_passant,
+            castle,
+            promotion: mv.promotion.filter(|_| pawn),
+            double_pawn_push: pawn && mv.from.rank_distance(mv.to) == 2,
+            resets_halfmove_clock: pawn || capture,
+        }
+    }
+
+    /* Applique un coup sans contrôle de légalité et met à jour tous les champs */
+    fn play(&mut self, mv: Move) {
+        self.play_tracked(mv, None);
+    }
+
+    /* `play`, en reportant chaque changement de case sur les masques fournis */
+    fn play_tracked(&mut self, mv: Move, mut boards: Option<&mut Bitboards>) {
+        let Some(piece) = self.piece_at(mv.from) else {
+            return;
+        };
+        let classified = self.classify_move(mv);
+        self.set(mv.from, None, boards.as_deref_mut());
+
+        if classified.en_passant {
+            if let Some(victim) = self.en_passant_victim() {
+                self.set(victim, None, boards.as_deref_mut());
+            }
+        }
+
+        /* La tour suit le roi du côté du roque */
+        if let Some(side) = classified.castle {
+            let rank = mv.from.rank();
+            let (rook_from, rook_to) = match side {
+                CastlingSide::Kingside => (File::H, File::F),
+                CastlingSide::Queenside => (File::A, File::D),
+            };
+            let rook = self.piece_at(Square::new(rook_from, rank));
+            self.set(Square::new(rook_from, rank), None, boards.as_deref_mut());
+            self.set(Square::new(rook_to, rank), rook, boards.as_deref_mut());
+        }
+
+        let kind = mv.promotion.unwrap_or(piece.kind);
+        self.set(
+            mv.to,
+            Some(Piece {
+                color: piece.color,
+                kind,
+            }),
+            boards,
+        );
+
+        self.update_castling_rights(piece, mv);
+
+        self.en_passant = if classified.double_pawn_push {
+            let rank = (mv.from.rank().index() + mv.to.rank().index()) / 2;
+            Some((mv.from.file().index() as u8, rank as u8))
+        } else {
+            None
+        };
+
+        if classified.resets_halfmove_clock {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock = self.halfmove_clock.saturating_add(1);
+        }
+        if self.active_color == Color::Black {
+            self.fullmove_number = self.fullmove_number.saturating_add(1);
+        }
+        self.active_color = self.active_color.opposite();
+    }
+
+    /* Un coup du roi retire ses deux roques ; un départ ou une arrivée sur une case
+     * de tour d'origine retire le roque correspondant */
+    fn update_castling_rights(&mut self, piece: Piece, mv: Move) {
+        let rights = &mut self.castling_rights;
+        if piece.kind == PieceKind::King {
+            match piece.color {
+                Color::White => {
+                    rights.white_kingside = false;
+                    rights.white_queenside = false;
+                }
+                Color::Black => {
+                    rights.black_kingside = false;
+                    rights.black_queenside = false;
+                }
+            }
+        }
+        for square in [mv.from, mv.to] {
+            match (square.file(), square.rank()) {
+                (File::H, Rank::First) => rights.white_kingside = false,
+                (File::A, Rank::First) => rights.white_queenside = false,
+                (File::H, Rank::Eighth) => rights.black_kingside = false,
+                (File::A, Rank::Eighth) => rights.black_queenside = false,
+                _ => {}
+            }
+        }
+    }
+}
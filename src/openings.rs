@@ -0,0 +1,137 @@
+/* Noms d'ouvertures retrouvés à partir de la position.
+
+`OpeningBook::from_tsv` lit une table fournie par l'utilisateur, une ouverture par ligne, colonnes séparées par des tabulations : la position, le nom, puis le code ECO s'il y en a un. Une première ligne d'en-tête nommant ses colonnes (`fen` ou `epd`, `name`, `eco`, sans égard à la casse) permet de les donner dans un autre ordre ; les lignes vides et les commentaires `#` sont ignorés. La position est une FEN complète, ou ses quatre premiers champs comme dans un EPD.
+
+Les entrées sont rangées sous la clé de `find_duplicates` en mode `Exact` : les compteurs n'y entrent pas et une case de prise en passant qu'aucun coup légal n'utilise est retirée, si bien que la position atteinte en jouant les coups retrouve celle de la table quelle que soit la façon dont elle y est écrite. Quand plusieurs lignes décrivent la même position, la première est gardée. */
+
+use crate::dedup::{dedup_key, DedupMode};
+use crate::error::FenError;
+use crate::parser::parse_fen;
+use crate::types::ChessPosition;
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use thiserror::Error;
+
+/* Erreur de lecture d'une table d'ouvertures ; `line` compte à partir de 1 */
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum OpeningBookError {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("line {line}: {error}")]
+    InvalidFen { line: usize, error: Box<FenError> },
+    #[error("line {line}: missing opening name")]
+    MissingName { line: usize },
+}
+
+/* Ouverture de la table */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningEntry {
+    pub name: String,
+    /* Code ECO (`B20`), si la table en donne un */
+    pub eco: Option<String>,
+}
+
+/* Colonnes de la position, du nom et du code ECO */
+#[derive(Debug, Clone, Copy)]
+struct Columns {
+    position: usize,
+    name: usize,
+    eco: Option<usize>,
+}
+
+impl Columns {
+    const DEFAULT: Columns = Columns {
+        position: 0,
+        name: 1,
+        eco: Some(2),
+    };
+
+    /* Colonnes nommées par une ligne d'en-tête, ou `None` si la ligne n'en est pas une */
+    fn from_header(line: &str) -> Option<Self> {
+        let find = |names: &[&str]| {
+            line.split('\t').position(|column| {
+                names
+                    .iter()
+                    .any(|name| column.trim().eq_ignore_ascii_case(name))
+            })
+        };
+        Some(Columns {
+            position: find(&["fen", "epd"])?,
+            name: find(&["name"])?,
+            eco: find(&["eco"]),
+        })
+    }
+}
+
+/* Position d'une FEN complète ou réduite à ses quatre premiers champs */
+fn parse_position(text: &str) -> Result<ChessPosition, FenError> {
+    if text.split_whitespace().count() == 4 {
+        parse_fen(&format!("{} 0 1", text))
+    } else {
+        parse_fen(text)
+    }
+}
+
+/* Table des ouvertures, indexée par position */
+#[derive(Debug, Clone, Default)]
+pub struct OpeningBook {
+    entries: HashMap<u64, OpeningEntry>,
+}
+
+impl OpeningBook {
+    /* Lit une table d'ouvertures ; la première ligne invalide arrête la lecture */
+    pub fn from_tsv<R: BufRead>(reader: R) -> Result<Self, OpeningBookError> {
+        let mut book = OpeningBook::default();
+        let mut layout = None;
+        for (index, text) in reader.lines().enumerate() {
+            let text = text?;
+            let line = index + 1;
+            if text.trim().is_empty() || text.trim_start().starts_with('#') {
+                continue;
+            }
+            let columns = match layout {
+                Some(columns) => columns,
+                None => match Columns::from_header(&text) {
+                    Some(header) => {
+                        layout = Some(header);
+                        continue;
+                    }
+                    None => *layout.insert(Columns::DEFAULT),
+                },
+            };
+            let cells: Vec<&str> = text.split('\t').map(str::trim).collect();
+            let cell = |column: usize| cells.get(column).copied().filter(|cell| !cell.is_empty());
+            let position =
+                parse_position(cell(columns.position).unwrap_or("")).map_err(|error| {
+                    OpeningBookError::InvalidFen {
+                        line,
+                        error: Box::new(error),
+                    }
+                })?;
+            let name = cell(columns.name).ok_or(OpeningBookError::MissingName { line })?;
+            let eco = columns.eco.and_then(cell).map(str::to_string);
+            book.entries
+                .entry(dedup_key(&position, DedupMode::Exact))
+                .or_insert_with(|| OpeningEntry {
+                    name: name.to_string(),
+                    eco,
+                });
+        }
+        Ok(book)
+    }
+
+    /* Ouverture de la table qui décrit cette position */
+    pub fn lookup(&self, position: &ChessPosition) -> Option<&OpeningEntry> {
+        self.entries.get(&dedup_key(position, DedupMode::Exact))
+    }
+
+    /* Nombre de positions distinctes de la table */
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
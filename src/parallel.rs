@@ -0,0 +1,131 @@
+/* Lecture de FEN en parallèle.
+
+Les FEN d'un gros fichier se lisent indépendamment les unes des autres : ce module répartit des tranches de lignes entre des fils d'exécution (`std::thread::scope`) et rassemble les résultats dans l'ordre de l'entrée, identiques à ceux d'une lecture séquentielle par `parse_fen`. */
+
+use crate::batch::{self, BatchEntries, BatchEntry};
+use crate::error::FenError;
+use crate::parser::parse_fen;
+#[cfg(feature = "telemetry")]
+use crate::telemetry::{self, Level};
+use crate::types::ChessPosition;
+use std::io::{self, BufRead};
+use std::num::NonZeroUsize;
+use std::thread;
+use std::vec;
+
+/* Lignes lues par bloc dans `par_parse_reader` */
+pub const DEFAULT_BLOCK_LINES: usize = 65_536;
+
+/* Nombre de fils disponibles, 1 si le système ne le précise pas */
+fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/* Lit chaque FEN de `lines` sur tous les cœurs disponibles ; le résultat d'indice `i` est
+ * celui de `parse_fen(lines[i])` */
+pub fn parse_fens_par(lines: &[&str]) -> Vec<Result<ChessPosition, FenError>> {
+    parse_fens_par_with(lines, default_threads())
+}
+
+/* Comme `parse_fens_par`, sur au plus `threads` fils (un seul si `threads` vaut 0 ou 1) */
+pub fn parse_fens_par_with(lines: &[&str], threads: usize) -> Vec<Result<ChessPosition, FenError>> {
+    let threads = threads.clamp(1, lines.len().max(1));
+    if threads == 1 {
+        return lines.iter().map(|line| parse_fen(line)).collect();
+    }
+    let chunk = lines.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let workers: Vec<_> = lines
+            .chunks(chunk)
+            .map(|part| {
+                scope.spawn(move || part.iter().map(|line| parse_fen(line)).collect::<Vec<_>>())
+            })
+            .collect();
+        let mut results = Vec::with_capacity(lines.len());
+        for worker in workers {
+            match worker.join() {
+                Ok(part) => results.extend(part),
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+        results
+    })
+}
+
+/* Itérateur sur les FEN d'un flux lues par blocs en parallèle */
+pub struct ParallelEntries<R> {
+    entries: BatchEntries<R>,
+    block_lines: usize,
+    threads: usize,
+    pending: vec::IntoIter<(BatchEntry, Result<ChessPosition, FenError>)>,
+    /* Erreur de lecture à rendre une fois le bloc en cours épuisé */
+    failure: Option<io::Error>,
+}
+
+impl<R: BufRead> ParallelEntries<R> {
+    /* Lit et analyse le bloc suivant ; faux quand le flux est épuisé */
+    fn fill(&mut self) -> bool {
+        let mut block = Vec::with_capacity(self.block_lines);
+        for entry in self.entries.by_ref() {
+            match entry {
+                Ok(entry) => block.push(entry),
+                Err(error) => {
+                    self.failure = Some(error);
+                    break;
+                }
+            }
+            if block.len() == self.block_lines {
+                break;
+            }
+        }
+        if block.is_empty() {
+            return false;
+        }
+        #[cfg(feature = "telemetry")]
+        let _span = telemetry::span(Level::Debug, "parse_block", || {
+            vec![
+                ("first_line", block[0].line.to_string()),
+                ("lines", block.len().to_string()),
+                ("threads", self.threads.to_string()),
+            ]
+        });
+        let lines: Vec<&str> = block.iter().map(|entry| entry.fen.as_str()).collect();
+        let results = parse_fens_par_with(&lines, self.threads);
+        self.pending = block
+            .into_iter()
+            .zip(results)
+            .collect::<Vec<_>>()
+            .into_iter();
+        true
+    }
+}
+
+impl<R: BufRead> Iterator for ParallelEntries<R> {
+    type Item = io::Result<(BatchEntry, Result<ChessPosition, FenError>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.next() {
+                return Some(Ok(item));
+            }
+            if let Some(error) = self.failure.take() {
+                return Some(Err(error));
+            }
+            if !self.fill() {
+                return None;
+            }
+        }
+    }
+}
+
+/* Lit les FEN d'un flux, comme `batch::entries`, par blocs de `block_lines` lignes analysés en
+ * parallèle ; chaque résultat garde la ligne d'origine, dans l'ordre du flux */
+pub fn par_parse_reader<R: BufRead>(reader: R, block_lines: usize) -> ParallelEntries<R> {
+    ParallelEntries {
+        entries: batch::entries(reader),
+        block_lines: block_lines.max(1),
+        threads: default_threads(),
+        pending: Vec::new().into_iter(),
+        failure: None,
+    }
+}
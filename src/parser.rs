@@ -2,29 +2,277 @@
 
 Ce module implémente le parsing d'une chaîne FEN selon la spécification standard. Il transforme une chaîne FEN en une structure ChessPosition. */
 
-use crate::error::FenError;
-use crate::types::{CastlingRights, ChessPosition, Color, Piece, PieceKind};
+use crate::error::{context_around, FenError, FenErrorKind, FenField};
+#[cfg(feature = "telemetry")]
+use crate::telemetry::{self, Level};
+use crate::types::{CastlingRights, ChessPosition, Color, Piece, PieceKind, Square, SquareOrder};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while1},
-    character::complete::{digit1, one_of, space1},
-    combinator::{map_res, opt, recognize},
-    multi::{many1, separated_list1},
-    sequence::{separated_pair, tuple},
+    character::complete::{anychar, char, digit1, one_of},
+    combinator::{cut, map, map_opt, recognize, value},
+    error::{ErrorKind, FromExternalError, ParseError},
+    multi::fold_many1,
+    sequence::separated_pair,
     IResult,
 };
 
+/* Erreur nom retenant ce que le parseur attendait à l'endroit de l'échec */
+#[derive(Debug)]
+struct FenParseError<'a> {
+    /* Reste de l'entrée au point d'échec */
+    input: &'a str,
+    /* Nature du problème */
+    kind: FenErrorKind,
+    /* Description de ce qui était attendu, vide tant qu'aucun parseur ne l'a précisée */
+    expected: &'static str,
+    /* Texte trouvé, quand il ne se réduit pas au caractère au point d'échec */
+    found: Option<String>,
+}
+
+impl<'a> FenParseError<'a> {
+    /* Échec définitif, avec attente et texte trouvé : les combinateurs ne reviennent pas en arrière */
+    fn failure<T>(
+        input: &'a str,
+        kind: FenErrorKind,
+        expected: &'static str,
+        found: String,
+    ) -> FenResult<'a, T> {
+        Err(nom::Err::Failure(FenParseError {
+            input,
+            kind,
+            expected,
+            found: Some(found),
+        }))
+    }
+}
+
+impl<'a> ParseError<&'a str> for FenParseError<'a> {
+    fn from_error_kind(input: &'a str, _kind: ErrorKind) -> Self {
+        FenParseError {
+            input,
+            kind: FenErrorKind::UnexpectedChar,
+            expected: "",
+            found: None,
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for FenParseError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _e: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+type FenResult<'a, T> = IResult<&'a str, T, FenParseError<'a>>;
+
+/* Précise l'attente d'un parseur, sauf si un parseur interne l'a déjà fait */
+fn expecting<'a, T>(
+    expected: &'static str,
+    mut parser: impl FnMut(&'a str) -> FenResult<'a, T>,
+) -> impl FnMut(&'a str) -> FenResult<'a, T> {
+    move |input| {
+        parser(input).map_err(|err| {
+            err.map(|err| FenParseError {
+                expected: if err.expected.is_empty() {
+                    expected
+                } else {
+                    err.expected
+                },
+                ..err
+            })
+        })
+    }
+}
+
+/* Attentes citées dans les messages d'erreur */
+const EXPECTED_PIECE: &str = "one of 'KQRBNPkqrbnp1-8'";
+const EXPECTED_SEPARATOR: &str = "' ' between fields";
+const EXPECTED_CASTLING: &str = "'-' or a combination of 'KQkq'";
+
+/* Degré de tolérance du parseur : `Strict` s'en tient à la spécification, `Lenient` accepte
+ * quelques écarts courants et les signale par des `ParseWarning` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ParseMode {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+/* Écart accepté en mode `Lenient` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /* Compteur écrit `-`, remplacé par sa valeur par défaut (0 demi-coup, coup numéro 1) */
+    DefaultedField { field: FenField, value: u32 },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::DefaultedField { field, value } => {
+                write!(f, "{} is '-', defaulted to {}", field.name(), value)
+            }
+        }
+    }
+}
+
+/* Position lue par `parse_fen_with_mode`, avec les écarts tolérés */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedFen {
+    pub position: ChessPosition,
+    pub warnings: Vec<ParseWarning>,
+}
+
 /* Parse une chaîne FEN complète en structure ChessPosition */
 pub fn parse_fen(fen: &str) -> Result<ChessPosition, FenError> {
-    let (_, (pieces, active_color, castling, en_passant, halfmove, fullmove)) = tuple((
-        parse_piece_placement,
+    let mut position = empty_position();
+    parse_fen_into(fen, &mut position)?;
+    Ok(position)
+}
+
+/* Échiquier vide, blancs au trait, que la lecture écrase */
+fn empty_position() -> ChessPosition {
+    ChessPosition {
+        pieces: [[None; 8]; 8],
+        active_color: Color::White,
+        castling_rights: CastlingRights::none(),
+        en_passant: None,
+        halfmove_clock: 0,
+        fullmove_number: 1,
+    }
+}
+
+/* Position initiale de `ChessPosition::default()`, lue en une passe sans passer par
+ * `parse_fen`, pour que la télémétrie n'en voie aucune lecture */
+pub(crate) fn start_position() -> ChessPosition {
+    let mut position = empty_position();
+    let read = parse_fen_fast(
+        b"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &mut position,
+    );
+    debug_assert!(read, "the starting FEN is canonical");
+    position
+}
+
+/* Lit une FEN dans une position existante, écrasée en entier (cases vides comprises).
+ * En cas d'erreur, `out` vaut `ChessPosition::default()`, jamais un état à moitié écrit.
+ * Une FEN canonique (séparateurs simples, rien après le numéro de coup) est lue en une seule
+ * passe sur les octets ; toute autre entrée, valide ou non, passe par le parseur nom, qui
+ * produit les erreurs détaillées. */
+pub fn parse_fen_into(fen: &str, out: &mut ChessPosition) -> Result<(), FenError> {
+    #[cfg(feature = "telemetry")]
+    let _span = telemetry::span(Level::Debug, "parse_fen", || {
+        vec![("input_len", fen.len().to_string())]
+    });
+    if parse_fen_fast(fen.as_bytes(), out) {
+        return Ok(());
+    }
+    match parse_fen_nom(fen) {
+        Ok(position) => {
+            *out = position;
+            Ok(())
+        }
+        Err(error) => {
+            #[cfg(feature = "telemetry")]
+            telemetry::parse_error(&error);
+            *out = ChessPosition::default();
+            Err(error)
+        }
+    }
+}
+
+/* Parseur de référence, construit avec nom : même résultat que `parse_fen`, plus lent sur les
+ * FEN canoniques. Il sert aux comparaisons et aux mesures. */
+pub fn parse_fen_nom(fen: &str) -> Result<ChessPosition, FenError> {
+    parse_fields(fen, ParseMode::Strict, &mut Vec::new())
+}
+
+/* Lit une FEN dans le mode demandé. En mode `Lenient`, un `-` à la place du compteur de
+ * demi-coups ou du numéro de coup, comme en écrivent certains outils dérivés de l'EPD, vaut
+ * 0 ou 1 et laisse un avertissement ; le mode `Strict` le refuse, comme `parse_fen`. */
+pub fn parse_fen_with_mode(fen: &str, mode: ParseMode) -> Result<ParsedFen, FenError> {
+    #[cfg(feature = "telemetry")]
+    let _span = telemetry::span(Level::Debug, "parse_fen", || {
+        vec![
+            ("input_len", fen.len().to_string()),
+            ("mode", format!("{:?}", mode)),
+        ]
+    });
+    let mut position = empty_position();
+    let mut warnings = Vec::new();
+    if !parse_fen_fast(fen.as_bytes(), &mut position) {
+        position = match parse_fields(fen, mode, &mut warnings) {
+            Ok(position) => position,
+            Err(error) => {
+                #[cfg(feature = "telemetry")]
+                telemetry::parse_error(&error);
+                return Err(error);
+            }
+        };
+    }
+    #[cfg(feature = "telemetry")]
+    for warning in &warnings {
+        let ParseWarning::DefaultedField { field, value } = *warning;
+        telemetry::event(Level::Warn, "lenient fixup", || {
+            vec![
+                ("field", field.name().to_string()),
+                ("value", value.to_string()),
+                ("warning", warning.to_string()),
+            ]
+        });
+    }
+    Ok(ParsedFen { position, warnings })
+}
+
+/* Analyse de `parse_fen_nom`, les écarts tolérés par `mode` étant notés dans `warnings` */
+fn parse_fields(
+    fen: &str,
+    mode: ParseMode,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<ChessPosition, FenError> {
+    /* Chaque champ est analysé séparément pour produire l'erreur qui lui correspond */
+    let (input, pieces) = field(fen, fen, FenField::PiecePlacement, parse_piece_placement)?;
+    let (input, active_color) = field(
+        fen,
+        skip_separator(input),
+        FenField::ActiveColor,
         parse_active_color,
+    )?;
+    let (input, castling) = field(
+        fen,
+        skip_separator(input),
+        FenField::CastlingRights,
         parse_castling,
+    )?;
+    let (input, en_passant) = field(
+        fen,
+        skip_separator(input),
+        FenField::EnPassant,
         parse_en_passant,
-        parse_number,
-        parse_number,
-    ))(fen)
-    .map_err(|_| FenError::InvalidFormat("Failed to parse FEN string".into()))?;
+    )?;
+    let (input, halfmove) = clock(
+        fen,
+        skip_separator(input),
+        FenField::HalfmoveClock,
+        mode,
+        warnings,
+    )?;
+    let (_, fullmove) = clock(
+        fen,
+        skip_separator(input),
+        FenField::FullmoveNumber,
+        mode,
+        warnings,
+    )?;
 
     Ok(ChessPosition {
         pieces,
@@ -36,68 +284,357 @@ pub fn parse_fen(fen: &str) -> Result<ChessPosition, FenError> {
     })
 }
 
-/* Parse le placement des pièces (1ère partie du FEN) */
-fn parse_piece_placement(input: &str) -> IResult<&str, [[Option<Piece>; 8]; 8]> {
-    let (input, ranks) = separated_list1(tag("/"), parse_rank)(input)?;
-    let (input, _) = space1(input)?;
+/* Lecture en une passe d'une FEN canonique, écrite directement dans `out` ; faux dès que
+ * l'entrée s'écarte de la forme canonique, `out` pouvant alors être à moitié écrit */
+fn parse_fen_fast(bytes: &[u8], out: &mut ChessPosition) -> bool {
+    fast_fields(bytes, out).is_some()
+}
 
-    if ranks.len() != 8 {
-        return Err(nom::Err::Error(nom::error::Error::new(
-            input,
-            nom::error::ErrorKind::LengthValue,
-        )));
+fn fast_fields(bytes: &[u8], out: &mut ChessPosition) -> Option<()> {
+    out.pieces = [[None; 8]; 8];
+    let pieces = &mut out.pieces;
+    let mut at = 0;
+    let mut rank = 7;
+    let mut file = 0;
+    /* Deux chiffres consécutifs sont refusés : la voie lente en rend l'erreur */
+    let mut after_digit = false;
+    loop {
+        let byte = *bytes.get(at)?;
+        at += 1;
+        match byte {
+            b'1'..=b'8' if !after_digit => {
+                file += (byte - b'0') as usize;
+                if file > 8 {
+                    return None;
+                }
+                after_digit = true;
+                continue;
+            }
+            b'/' if file == 8 && rank > 0 => {
+                rank -= 1;
+                file = 0;
+            }
+            b' ' if file == 8 && rank == 0 => break,
+            _ if file < 8 => {
+                pieces[rank][file] = Some(fast_piece(byte)?);
+                file += 1;
+            }
+            _ => return None,
+        }
+        after_digit = false;
+    }
+
+    let active_color = match (bytes.get(at)?, bytes.get(at + 1)?) {
+        (b'w', b' ') => Color::White,
+        (b'b', b' ') => Color::Black,
+        _ => return None,
+    };
+    at += 2;
+
+    let mut castling_rights = CastlingRights::none();
+    if bytes.get(at..at + 2)? == b"- " {
+        at += 2;
+    } else {
+        let mut seen = 0u8;
+        loop {
+            let bit = match *bytes.get(at)? {
+                b'K' => 1,
+                b'Q' => 2,
+                b'k' => 4,
+                b'q' => 8,
+                b' ' if seen != 0 => break,
+                _ => return None,
+            };
+            if seen & bit != 0 {
+                return None;
+            }
+            seen |= bit;
+            at += 1;
+        }
+        at += 1;
+        castling_rights.white_kingside = seen & 1 != 0;
+        castling_rights.white_queenside = seen & 2 != 0;
+        castling_rights.black_kingside = seen & 4 != 0;
+        castling_rights.black_queenside = seen & 8 != 0;
     }
 
+    let en_passant = match bytes.get(at..at + 3)? {
+        [b'-', b' ', _] => {
+            at += 2;
+            None
+        }
+        [file @ b'a'..=b'h', rank @ (b'3' | b'6'), b' '] => {
+            at += 3;
+            Some((file - b'a', rank - b'1'))
+        }
+        _ => return None,
+    };
+
+    let (halfmove_clock, rest) = fast_number(&bytes[at..])?;
+    let (fullmove_number, rest) = fast_number(rest.strip_prefix(b" ")?)?;
+    if !rest.is_empty() {
+        return None;
+    }
+
+    out.active_color = active_color;
+    out.castling_rights = castling_rights;
+    out.en_passant = en_passant;
+    out.halfmove_clock = halfmove_clock;
+    out.fullmove_number = fullmove_number;
+    Some(())
+}
+
+/* Pièce désignée par un octet de la FEN */
+fn fast_piece(byte: u8) -> Option<Piece> {
+    let kind = match byte | 0x20 {
+        b'k' => PieceKind::King,
+        b'q' => PieceKind::Queen,
+        b'r' => PieceKind::Rook,
+        b'b' => PieceKind::Bishop,
+        b'n' => PieceKind::Knight,
+        b'p' => PieceKind::Pawn,
+        _ => return None,
+    };
+    let color = if byte & 0x20 == 0 {
+        Color::White
+    } else {
+        Color::Black
+    };
+    Some(Piece { color, kind })
+}
+
+/* Entier décimal non vide tenant sur 32 bits, suivi du reste des octets */
+fn fast_number(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    let digits = bytes
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    let mut value: u32 = 0;
+    for &byte in &bytes[..digits] {
+        value = value.checked_mul(10)?.checked_add(u32::from(byte - b'0'))?;
+    }
+    Some((value, &bytes[digits..]))
+}
+
+/* Parse une chaîne FEN en relevant toutes les erreurs au lieu de s'arrêter à la première.
+ * Les champs sont découpés sur les espaces puis analysés indépendamment ; un champ invalide
+ * est remplacé par une valeur neutre pour que les suivants soient tout de même vérifiés. */
+pub fn parse_fen_all_errors(fen: &str) -> Result<ChessPosition, Vec<FenError>> {
+    let mut errors = Vec::new();
+    /* Chaque champ est vu comme un suffixe de `fen`, pour que le contexte des erreurs reste exact */
+    let mut fields = fen
+        .split(SEPARATORS)
+        .filter(|part| !part.is_empty())
+        .map(|part| &fen[part.as_ptr() as usize - fen.as_ptr() as usize..]);
+    let mut next = || fields.next().unwrap_or_default();
+
+    let pieces = recover(
+        field(fen, next(), FenField::PiecePlacement, parse_piece_placement),
+        [[None; 8]; 8],
+        &mut errors,
+    );
+    let active_color = recover(
+        field(fen, next(), FenField::ActiveColor, parse_active_color),
+        Color::White,
+        &mut errors,
+    );
+    let castling_rights = recover(
+        field(fen, next(), FenField::CastlingRights, parse_castling),
+        CastlingRights::none(),
+        &mut errors,
+    );
+    let en_passant = recover(
+        field(fen, next(), FenField::EnPassant, parse_en_passant),
+        None,
+        &mut errors,
+    );
+    let halfmove_clock = recover(
+        field(fen, next(), FenField::HalfmoveClock, parse_number),
+        0,
+        &mut errors,
+    );
+    let fullmove_number = recover(
+        field(fen, next(), FenField::FullmoveNumber, parse_number),
+        1,
+        &mut errors,
+    );
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(ChessPosition {
+        pieces,
+        active_color,
+        castling_rights,
+        en_passant,
+        halfmove_clock,
+        fullmove_number,
+    })
+}
+
+/* Renvoie la valeur d'un champ, ou la valeur de remplacement après avoir noté l'erreur */
+fn recover<T>(
+    result: Result<(&str, T), FenError>,
+    placeholder: T,
+    errors: &mut Vec<FenError>,
+) -> T {
+    match result {
+        Ok((_, value)) => value,
+        Err(err) => {
+            errors.push(err);
+            placeholder
+        }
+    }
+}
+
+/* Analyse un champ de `fen` qui doit être suivi d'un séparateur ou de la fin de la chaîne.
+ * Un échec produit une erreur située sur le texte du champ. */
+fn field<'a, T>(
+    fen: &str,
+    input: &'a str,
+    field: FenField,
+    parser: fn(&'a str) -> FenResult<'a, T>,
+) -> Result<(&'a str, T), FenError> {
+    if input.is_empty() {
+        return Err(FenError::missing(field));
+    }
+    let (at, kind, expected, found) = match parser(input) {
+        Ok((rest, value)) if rest.is_empty() || rest.starts_with(SEPARATORS) => {
+            return Ok((rest, value))
+        }
+        /* Le champ est valide mais collé au suivant */
+        Ok((rest, _)) => (rest, FenErrorKind::UnexpectedChar, EXPECTED_SEPARATOR, None),
+        Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => {
+            (err.input, err.kind, err.expected, err.found)
+        }
+        Err(nom::Err::Incomplete(_)) => (input, FenErrorKind::UnexpectedChar, "", None),
+    };
+    let found = found.unwrap_or_else(|| match at.chars().next() {
+        Some(c) if SEPARATORS.contains(&c) => "end of field".to_string(),
+        Some(c) => c.to_string(),
+        None => "end of input".to_string(),
+    });
+
+    let snippet = input.split(SEPARATORS).next().unwrap_or_default();
+    /* `input` est un suffixe de `fen` : sa position s'en déduit */
+    let start = fen.len() - input.len();
+    let end = start + snippet.len();
+    let mut error = FenError::from((field, kind));
+    error.span = Some(start..end);
+    error.snippet = snippet.to_string();
+    error.context = context_around(fen, start, end);
+    error.expected = expected;
+    error.found = found;
+    Err(error)
+}
+
+/* Analyse un compteur ; en mode `Lenient`, `-` prend la valeur par défaut du champ */
+fn clock<'a>(
+    fen: &str,
+    input: &'a str,
+    id: FenField,
+    mode: ParseMode,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(&'a str, u32), FenError> {
+    if mode == ParseMode::Strict {
+        return field(fen, input, id, parse_number);
+    }
+    let (rest, number) = field(fen, input, id, parse_number_or_dash)?;
+    let number = number.unwrap_or_else(|| {
+        let value = u32::from(id == FenField::FullmoveNumber);
+        warnings.push(ParseWarning::DefaultedField { field: id, value });
+        value
+    });
+    Ok((rest, number))
+}
+
+/* Caractères séparant les champs */
+pub(crate) const SEPARATORS: [char; 2] = [' ', '\t'];
+
+/* Saute les séparateurs entre deux champs */
+fn skip_separator(input: &str) -> &str {
+    input.trim_start_matches(SEPARATORS)
+}
+
+/* Parse le placement des pièces (1ère partie du FEN).
+ * Les rangs sont lus dans un tableau fixe : une entrée démesurée s'arrête au neuvième rang
+ * au lieu d'être accumulée en entier. */
+fn parse_piece_placement(input: &str) -> FenResult<'_, [[Option<Piece>; 8]; 8]> {
+    let start = input;
     let mut board = [[None; 8]; 8];
-    for (i, rank) in ranks.iter().enumerate() {
-        if rank.len() != 8 {
-            return Err(nom::Err::Error(nom::error::Error::new(
-                input,
-                nom::error::ErrorKind::LengthValue,
-            )));
+    let mut input = input;
+    let mut count = 0;
+    loop {
+        let (rest, rank) = parse_rank(input)?;
+        if count < 8 {
+            board[7 - count] = rank;
+        }
+        count += 1;
+        input = rest;
+        match input.strip_prefix('/') {
+            Some(rest) if count < 8 => input = rest,
+            _ => break,
         }
-        board[7 - i] = *rank;
+    }
+
+    if count != 8 || input.starts_with('/') {
+        let text = start.split(SEPARATORS).next().unwrap_or_default();
+        return FenParseError::failure(
+            start,
+            FenErrorKind::WrongLength,
+            "8 ranks separated by '/'",
+            format!("{} ranks", text.matches('/').count() + 1),
+        );
     }
 
     Ok((input, board))
 }
 
 /* Parse un seul rang du plateau */
-fn parse_rank(input: &str) -> IResult<&str, [Option<Piece>; 8]> {
-    let (input, pieces) = many1(alt((parse_piece, parse_empty)))(input)?;
-
-    let mut rank = [None; 8];
-    let mut idx = 0;
-
-    for item in pieces {
-        match item {
-            RankItem::Piece(p) => {
-                if idx >= 8 {
-                    return Err(nom::Err::Error(nom::error::Error::new(
-                        input,
-                        nom::error::ErrorKind::LengthValue,
-                    )));
+fn parse_rank(input: &str) -> FenResult<'_, [Option<Piece>; 8]> {
+    let start = input;
+    /* Le compte des cases sature : il suffit de savoir qu'il dépasse 8 */
+    let (input, (rank, squares)) = expecting(
+        EXPECTED_PIECE,
+        cut(fold_many1(
+            alt((parse_piece, parse_empty)),
+            || ([None; 8], 0usize),
+            |(mut rank, squares), item| match item {
+                RankItem::Piece(piece) => {
+                    if let Some(slot) = rank.get_mut(squares) {
+                        *slot = Some(piece);
+                    }
+                    (rank, squares.saturating_add(1))
                 }
-                rank[idx] = Some(p);
-                idx += 1;
-            }
-            RankItem::Empty(count) => {
-                idx += count as usize;
-                if idx > 8 {
-                    return Err(nom::Err::Error(nom::error::Error::new(
-                        input,
-                        nom::error::ErrorKind::LengthValue,
-                    )));
-                }
-            }
+                RankItem::Empty(count) => (rank, squares.saturating_add(count as usize)),
+            },
+        )),
+    )(input)?;
+
+    /* Un caractère inconnu interrompt le rang avant sa fin */
+    match input.chars().next() {
+        Some(c) if c != '/' && !SEPARATORS.contains(&c) => {
+            return FenParseError::failure(
+                input,
+                FenErrorKind::UnexpectedChar,
+                EXPECTED_PIECE,
+                c.to_string(),
+            );
         }
+        _ => {}
     }
 
-    if idx != 8 {
-        return Err(nom::Err::Error(nom::error::Error::new(
-            input,
-            nom::error::ErrorKind::LengthValue,
-        )));
+    if squares != 8 {
+        let text = &start[..start.len() - input.len()];
+        return FenParseError::failure(
+            start,
+            FenErrorKind::WrongLength,
+            "8 squares in each rank",
+            text.to_string(),
+        );
     }
 
     Ok((input, rank))
@@ -110,85 +647,75 @@ enum RankItem {
 }
 
 /* Parse une pièce individuelle */
-fn parse_piece(input: &str) -> IResult<&str, RankItem> {
-    let (input, c) = one_of("KQRBNPkqrbnp")(input)?;
-    let piece = match c {
-        'K' => Piece {
-            color: Color::White,
-            kind: PieceKind::King,
-        },
-        'Q' => Piece {
-            color: Color::White,
-            kind: PieceKind::Queen,
-        },
-        'R' => Piece {
-            color: Color::White,
-            kind: PieceKind::Rook,
-        },
-        'B' => Piece {
-            color: Color::White,
-            kind: PieceKind::Bishop,
-        },
-        'N' => Piece {
-            color: Color::White,
-            kind: PieceKind::Knight,
-        },
-        'P' => Piece {
-            color: Color::White,
-            kind: PieceKind::Pawn,
-        },
-        'k' => Piece {
-            color: Color::Black,
-            kind: PieceKind::King,
-        },
-        'q' => Piece {
-            color: Color::Black,
-            kind: PieceKind::Queen,
-        },
-        'r' => Piece {
-            color: Color::Black,
-            kind: PieceKind::Rook,
-        },
-        'b' => Piece {
-            color: Color::Black,
-            kind: PieceKind::Bishop,
-        },
-        'n' => Piece {
-            color: Color::Black,
-            kind: PieceKind::Knight,
-        },
-        'p' => Piece {
-            color: Color::Black,
-            kind: PieceKind::Pawn,
-        },
-        _ => unreachable!(),
-    };
+fn parse_piece(input: &str) -> FenResult<'_, RankItem> {
+    let (input, piece) = map_opt(anychar, Piece::from_fen_char)(input)?;
     Ok((input, RankItem::Piece(piece)))
 }
 
-/* Parse une série de cases vides (chiffre 1-8) */
-fn parse_empty(input: &str) -> IResult<&str, RankItem> {
-    let (input, count) = map_res(digit1, |s: &str| s.parse::<u8>())(input)?;
-    Ok((input, RankItem::Empty(count)))
+/* Parse une série de cases vides : un seul chiffre de 1 à 8 ; `0`, `9` et un second chiffre
+ * collé au premier sont refusés comme caractères inattendus */
+fn parse_empty(input: &str) -> FenResult<'_, RankItem> {
+    let (input, digit) = one_of("12345678")(input)?;
+    if let Some(c) = input.chars().next().filter(char::is_ascii_digit) {
+        return FenParseError::failure(
+            input,
+            FenErrorKind::UnexpectedChar,
+            "a piece or '/' after an empty-square count",
+            c.to_string(),
+        );
+    }
+    Ok((input, RankItem::Empty(digit as u8 - b'0')))
 }
 
 /* Parse la couleur active (w/b) */
-fn parse_active_color(input: &str) -> IResult<&str, Color> {
-    let (input, c) = one_of("wb")(input)?;
-    let (input, _) = space1(input)?;
-    let color = match c {
-        'w' => Color::White,
-        'b' => Color::Black,
-        _ => unreachable!(),
-    };
-    Ok((input, color))
+fn parse_active_color(input: &str) -> FenResult<'_, Color> {
+    expecting(
+        "'w' or 'b'",
+        alt((
+            value(Color::White, char('w')),
+            value(Color::Black, char('b')),
+        )),
+    )(input)
 }
 
-/* Parse les droits de roque */
-fn parse_castling(input: &str) -> IResult<&str, CastlingRights> {
-    let (input, s) =
-        take_while1(|c: char| c == '-' || c == 'K' || c == 'Q' || c == 'k' || c == 'q')(input)?;
-    let (input, _) = space1(input)?;
+/* Lit un champ de droits de roque isolé, sans rien avant ni après */
+pub(crate) fn parse_castling_rights(text: &str) -> Result<CastlingRights, FenError> {
+    let (rest, rights) = field(text, text, FenField::CastlingRights, parse_castling)?;
+    if rest.is_empty() {
+        return Ok(rights);
+    }
+    let start = text.len() - rest.len();
+    Err(FenError {
+        span: Some(start..text.len()),
+        snippet: rest.to_string(),
+        expected: "end of the castling field",
+        found: rest.to_string(),
+        ..FenError::from((FenField::CastlingRights, FenErrorKind::UnexpectedChar))
+    })
+}
+
+/* Parse les droits de roque : `-` seul, ou une combinaison de `KQkq` ; un `-` mêlé aux lettres
+ * est un caractère inattendu */
+fn parse_castling(input: &str) -> FenResult<'_, CastlingRights> {
+    let start = input;
+    let (input, s) = expecting(
+        EXPECTED_CASTLING,
+        alt((
+            tag("-"),
+            take_while1(|c: char| c == 'K' || c == 'Q' || c == 'k' || c == 'q'),
+        )),
+    )(input)?;
+    match input.chars().next() {
+        Some(c) if !SEPARATORS.contains(&c) => {
+            return FenParseError::failure(
+                input,
+                FenErrorKind::UnexpectedChar,
+                EXPECTED_CASTLING,
+                c.to_string(),
+            );
+        }
+        _ => {}
+    }
 
     let mut rights = CastlingRights::none();
 
@@ -196,48 +723,137 @@ fn parse_castling(input: &str) -> IResult<&str, CastlingRights> {
         return Ok((input, rights));
     }
 
-    /* Vérifier les doublons */
-    let mut seen = std::collections::HashSet::new();
-    for c in s.chars() {
-        if !seen.insert(c) {
-            return Err(nom::Err::Error(nom::error::Error::new(
-                input,
-                nom::error::ErrorKind::Verify,
-            )));
-        }
-        match c {
-            'K' => rights.white_kingside = true,
-            'Q' => rights.white_queenside = true,
-            'k' => rights.black_kingside = true,
-            'q' => rights.black_queenside = true,
-            _ => (),
+    /* Vérifier les doublons : un bit par lettre de `KQkq`, sans allocation. Le cinquième
+     * caractère est forcément un doublon, et le champ est refusé sans être parcouru jusqu'au
+     * bout. */
+    let mut seen = 0u8;
+    for (i, c) in s.char_indices() {
+        let (bit, right) = match c {
+            'K' => (1, &mut rights.white_kingside),
+            'Q' => (2, &mut rights.white_queenside),
+            'k' => (4, &mut rights.black_kingside),
+            _ => (8, &mut rights.black_queenside),
+        };
+        if seen & bit != 0 {
+            return FenParseError::failure(
+                &start[i..],
+                FenErrorKind::Duplicate,
+                "each of 'KQkq' at most once",
+                c.to_string(),
+            );
         }
+        seen |= bit;
+        *right = true;
     }
 
     Ok((input, rights))
 }
 
 /* Parse la case de prise en passant */
-fn parse_en_passant(input: &str) -> IResult<&str, Option<(u8, u8)>> {
-    let (input, ep) = alt((
-        tag("-"),
-        recognize(separated_pair(one_of("abcdefgh"), one_of("36"), tag(""))),
-    ))(input)?;
-    let (input, _) = space1(input)?;
+fn parse_en_passant(input: &str) -> FenResult<'_, Option<(u8, u8)>> {
+    let (rest, ep) = expecting(
+        "'-' or a square on rank 3 or 6",
+        alt((
+            tag("-"),
+            recognize(separated_pair(one_of("abcdefgh"), one_of("36"), tag(""))),
+        )),
+    )(input)?;
 
     if ep == "-" {
-        return Ok((input, None));
+        return Ok((rest, None));
     }
 
-    let file = ep.chars().next().unwrap() as u8 - b'a';
-    let rank = ep.chars().nth(1).unwrap().to_digit(10).unwrap() as u8 - 1;
-
-    Ok((input, Some((file, rank))))
+    /* Le texte reconnu est toujours une case valide ; le vérifier évite toute hypothèse */
+    match Square::from_name(ep) {
+        Some(square) => Ok((
+            rest,
+            Some((square.file().index() as u8, square.rank().index() as u8)),
+        )),
+        None => FenParseError::failure(
+            input,
+            FenErrorKind::UnexpectedChar,
+            "'-' or a square on rank 3 or 6",
+            ep.to_string(),
+        ),
+    }
 }
 
 /* Parse un nombre (pour demi-coups ou numéro de tour) */
-fn parse_number(input: &str) -> IResult<&str, u32> {
-    let (input, num) = map_res(digit1, |s: &str| s.parse::<u32>())(input)?;
-    let (input, _) = opt(space1)(input)?;
-    Ok((input, num))
+fn parse_number(input: &str) -> FenResult<'_, u32> {
+    let (rest, digits) = expecting("a non-negative integer", digit1)(input)?;
+    match digits.parse::<u32>() {
+        Ok(num) => Ok((rest, num)),
+        Err(_) => FenParseError::failure(
+            input,
+            FenErrorKind::OutOfRange,
+            "an integer below 2^32",
+            digits.to_string(),
+        ),
+    }
+}
+
+/* Parse un compteur ou `-`, qui donne `None` */
+fn parse_number_or_dash(input: &str) -> FenResult<'_, Option<u32>> {
+    alt((value(None, char('-')), map(parse_number, Some)))(input)
+}
+
+impl ChessPosition {
+    /* Lit un plateau écrit sur 64 caractères, un par case, dans l'ordre de lecture d'un
+     * diagramme : l'indice 0 est a8, l'indice 7 h8, l'indice 8 a7, ..., l'indice 63 h1. Une
+     * pièce s'écrit par sa lettre FEN, une case vide par `.` ou `1`. Les compteurs valent 0
+     * et 1. */
+    pub fn from_flat_board(
+        board: &str,
+        active_color: Color,
+        castling_rights: CastlingRights,
+        en_passant: Option<Square>,
+    ) -> Result<Self, FenError> {
+        let located = |kind, start: usize, snippet: &str, expected, found: String| {
+            let mut error = FenError::from((FenField::PiecePlacement, kind));
+            error.span = Some(start..start + snippet.len());
+            error.snippet = snippet.to_string();
+            error.context = context_around(board, start, start + snippet.len());
+            error.expected = expected;
+            error.found = found;
+            error
+        };
+        let count = board.chars().count();
+        if count != 64 {
+            return Err(located(
+                FenErrorKind::WrongLength,
+                0,
+                board,
+                "64 squares",
+                format!("{} characters", count),
+            ));
+        }
+        let mut pieces = [[None; 8]; 8];
+        for ((offset, c), square) in board.char_indices().zip(Square::iter(SquareOrder::Fen)) {
+            let piece = match c {
+                '.' | '1' => None,
+                _ => match Piece::from_fen_char(c) {
+                    Some(piece) => Some(piece),
+                    None => {
+                        return Err(located(
+                            FenErrorKind::UnexpectedChar,
+                            offset,
+                            &board[offset..offset + c.len_utf8()],
+                            "a piece letter, '.' or '1'",
+                            c.to_string(),
+                        ))
+                    }
+                },
+            };
+            pieces[square.rank().index()][square.file().index()] = piece;
+        }
+        Ok(ChessPosition {
+            pieces,
+            active_color,
+            castling_rights,
+            en_passant: en_passant
+                .map(|square| (square.file().index() as u8, square.rank().index() as u8)),
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        })
+    }
 }
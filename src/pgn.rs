@@ -0,0 +1,234 @@
+/* Lecture des parties au format PGN.
+
+`final_position` rejoue la ligne principale d'une partie et rend la position atteinte ; `positions` rend, au fil de la lecture, chaque coup et la position qui le suit, pour parcourir de grandes collections sans garder toutes les positions. La partie part de la position initiale, ou de la balise `[FEN "..."]` si elle est présente. Dans le texte des coups, les commentaires (`{...}` et `;` jusqu'à la fin de la ligne), les variantes `(...)`, même imbriquées, les annotations `$n` et les numéros de coups (`12.`, `12...`, collés ou non au coup) sont ignorés ; un résultat (`1-0`, `0-1`, `1/2-1/2`, `*`) termine la partie. Les roques écrits avec des zéros (`0-0`, `0-0-0`) sont ramenés à `O-O` et `O-O-O`. Chaque coup est lu en notation algébrique abrégée par `move_from_san`, puis joué par `make_move`. */
+
+use crate::error::FenError;
+use crate::parser::parse_fen;
+use crate::types::{ChessPosition, Color};
+use std::borrow::Cow;
+use std::fmt;
+use thiserror::Error;
+
+/* Erreur de lecture d'une partie */
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum PgnError {
+    #[error("invalid FEN tag: {0}")]
+    InvalidFen(#[from] FenError),
+    /* Coup illisible, ambigu ou illégal, avec son numéro et le camp qui le joue */
+    #[error("move {number}{} {san} is not legal in this position", move_dots(*.color))]
+    IllegalMove {
+        number: u32,
+        color: Color,
+        san: String,
+    },
+    /* Commentaire `{` ou variante `(` jamais refermés */
+    #[error("unclosed '{0}' in the movetext")]
+    Unclosed(char),
+}
+
+/* Coup de la partie tel qu'il y est écrit, en notation algébrique abrégée */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct San(String);
+
+impl San {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for San {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* Ponctuation d'un numéro de coup : `12.` pour les blancs, `12...` pour les noirs */
+fn move_dots(color: Color) -> &'static str {
+    match color {
+        Color::White => ".",
+        Color::Black => "...",
+    }
+}
+
+/* Valeur d'une ligne de balise `[Nom "valeur"]`, si son nom est `name` */
+fn tag_value<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (tag, value) = inner.split_once(char::is_whitespace)?;
+    if tag != name {
+        return None;
+    }
+    value.trim().strip_prefix('"')?.strip_suffix('"')
+}
+
+/* Roque écrit avec des zéros ramené à la lettre `O`, marques d'échec et d'appréciation comprises */
+fn normalize_castling(san: &str) -> Cow<'_, str> {
+    for (zeros, letters) in [("0-0-0", "O-O-O"), ("0-0", "O-O")] {
+        if let Some(rest) = san.strip_prefix(zeros) {
+            return Cow::Owned(format!("{}{}", letters, rest));
+        }
+    }
+    Cow::Borrowed(san)
+}
+
+/* Position de départ d'une partie et texte de ses coups, après les balises */
+fn start(pgn: &str) -> Result<(ChessPosition, &str), PgnError> {
+    let mut position = ChessPosition::default();
+    let mut rest = pgn.trim_start();
+    while rest.starts_with('[') {
+        let (line, next) = rest.split_once('\n').unwrap_or((rest, ""));
+        if let Some(fen) = tag_value(line.trim_end(), "FEN") {
+            position = parse_fen(fen)?;
+        }
+        rest = next.trim_start();
+    }
+    Ok((position, rest))
+}
+
+/* Coups de la ligne principale, dans l'ordre, lus au fil de la demande */
+struct SanTokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> SanTokens<'a> {
+    /* Saute le commentaire ou la variante qui commence `rest` */
+    fn skip_group(&mut self) -> Result<(), PgnError> {
+        if self.rest.starts_with('{') {
+            let close = self.rest.find('}').ok_or(PgnError::Unclosed('{'))?;
+            self.rest = &self.rest[close + 1..];
+            return Ok(());
+        }
+        let mut depth = 0usize;
+        loop {
+            let index = self
+                .rest
+                .find(['{', '(', ')', ';'])
+                .ok_or(PgnError::Unclosed('('))?;
+            self.rest = &self.rest[index..];
+            match self.rest.as_bytes()[0] {
+                b'{' => self.skip_group()?,
+                b';' => self.skip_line(),
+                b'(' => {
+                    depth += 1;
+                    self.rest = &self.rest[1..];
+                }
+                _ => {
+                    depth -= 1;
+                    self.rest = &self.rest[1..];
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /* Saute la fin de la ligne, pour un commentaire `;` */
+    fn skip_line(&mut self) {
+        self.rest = self.rest.split_once('\n').map_or("", |(_, next)| next);
+    }
+}
+
+impl<'a> Iterator for SanTokens<'a> {
+    type Item = Result<Cow<'a, str>, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.rest = self.rest.trim_start();
+            match self.rest.chars().next()? {
+                '{' | '(' => {
+                    if let Err(error) = self.skip_group() {
+                        self.rest = "";
+                        return Some(Err(error));
+                    }
+                    continue;
+                }
+                ';' => {
+                    self.skip_line();
+                    continue;
+                }
+                _ => {}
+            }
+            let end = self
+                .rest
+                .find(|c: char| c.is_whitespace() || "{}();".contains(c))
+                .unwrap_or(self.rest.len())
+                .max(1);
+            let (word, next) = self.rest.split_at(end);
+            self.rest = next;
+            if matches!(word, "1-0" | "0-1" | "1/2-1/2" | "*") {
+                self.rest = "";
+                return None;
+            }
+            if word.starts_with('$') {
+                continue;
+            }
+            let digits = word.bytes().take_while(u8::is_ascii_digit).count();
+            let san = match word[digits..].trim_start_matches('.') {
+                stripped if digits > 0 && stripped.len() < word.len() - digits => stripped,
+                _ => word,
+            };
+            if !san.is_empty() {
+                return Some(Ok(normalize_castling(san)));
+            }
+        }
+    }
+}
+
+/* Joue un coup en notation abrégée ; l'erreur porte le numéro du coup et le camp au trait */
+fn play(position: &mut ChessPosition, san: &str) -> Result<(), PgnError> {
+    let illegal = |position: &ChessPosition| PgnError::IllegalMove {
+        number: position.fullmove_number,
+        color: position.active_color,
+        san: san.to_string(),
+    };
+    let mv = position
+        .move_from_san(san)
+        .ok_or_else(|| illegal(position))?;
+    position.make_move(mv).map_err(|_| illegal(position))
+}
+
+/* Position atteinte à la fin de la ligne principale d'une partie */
+pub fn final_position(pgn: &str) -> Result<ChessPosition, PgnError> {
+    let (mut position, movetext) = start(pgn)?;
+    for san in (SanTokens { rest: movetext }) {
+        play(&mut position, &san?)?;
+    }
+    Ok(position)
+}
+
+/* Positions successives d'une partie ; s'arrête après la première erreur */
+struct GamePositions<'a> {
+    tokens: SanTokens<'a>,
+    position: ChessPosition,
+}
+
+impl Iterator for GamePositions<'_> {
+    type Item = Result<(San, ChessPosition), PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let played = self
+            .tokens
+            .next()?
+            .and_then(|san| play(&mut self.position, &san).map(|()| san));
+        Some(match played {
+            Ok(san) => Ok((San(san.into_owned()), self.position.clone())),
+            Err(error) => {
+                self.tokens.rest = "";
+                Err(error)
+            }
+        })
+    }
+}
+
+/* Chaque coup de la ligne principale avec la position qui le suit, lus à la demande ; seule
+ * une balise `FEN` invalide est signalée d'emblée, la position de départ n'est pas rendue */
+pub fn positions(
+    pgn: &str,
+) -> Result<impl Iterator<Item = Result<(San, ChessPosition), PgnError>> + '_, PgnError> {
+    let (position, movetext) = start(pgn)?;
+    Ok(GamePositions {
+        tokens: SanTokens { rest: movetext },
+        position,
+    })
+}
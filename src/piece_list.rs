@@ -0,0 +1,117 @@
+/* Notation en liste de pièces, celle des recueils de finales : `White: Kg1, Rd1, Pa2; Black: Kg8, Pa7`.
+
+Chaque camp est annoncé par `White:` ou `Black:`, sans égard à la casse, puis suivi de ses pièces : la lettre anglaise de la pièce en majuscule et la case, la lettre `P` étant facultative pour les pions. Virgules, points-virgules et espaces séparent indifféremment les entrées. La liste ne dit rien du trait, des roques ni des compteurs : la position lue a les valeurs de `DiagramMeta::default()`. */
+
+use crate::diagram::DiagramMeta;
+use crate::error::{FenError, FenErrorKind, FenField};
+use crate::types::{ChessPosition, Color, Piece, PieceKind, Square};
+
+/* Erreur de placement propre à la liste de pièces */
+fn list_error(kind: FenErrorKind, expected: &'static str, found: String) -> FenError {
+    FenError {
+        expected,
+        found,
+        ..FenError::from((FenField::PiecePlacement, kind))
+    }
+}
+
+/* Camp annoncé par une étiquette (`white`, `Black`, ...), deux-points déjà retirés */
+fn color_label(token: &str) -> Option<Color> {
+    [Color::White, Color::Black]
+        .into_iter()
+        .find(|color| token.eq_ignore_ascii_case(color.name()))
+}
+
+/* Type et case d'une entrée (`Kg1`, `Pa2` ou `a2`) */
+fn entry(token: &str) -> Option<(PieceKind, Square)> {
+    let mut chars = token.chars();
+    let first = chars.next()?;
+    if first.is_ascii_uppercase() {
+        let piece = Piece::from_fen_char(first)?;
+        Some((piece.kind, Square::from_name(chars.as_str())?))
+    } else {
+        Some((PieceKind::Pawn, Square::from_name(token)?))
+    }
+}
+
+impl ChessPosition {
+    /* Position décrite par une liste de pièces ; deux entrées sur une même case sont une
+     * erreur qui nomme la case */
+    pub fn from_piece_list(text: &str) -> Result<Self, FenError> {
+        let mut pieces = [[None; 8]; 8];
+        let mut color = None;
+        let tokens = text
+            .split(|c: char| matches!(c, ',' | ';' | ':') || c.is_whitespace())
+            .filter(|token| !token.is_empty());
+        for token in tokens {
+            if let Some(label) = color_label(token) {
+                color = Some(label);
+                continue;
+            }
+            let Some(color) = color else {
+                return Err(list_error(
+                    FenErrorKind::Missing,
+                    "a White: or Black: label before the pieces",
+                    token.to_string(),
+                ));
+            };
+            let (kind, square) = entry(token).ok_or_else(|| {
+                list_error(
+                    FenErrorKind::UnexpectedChar,
+                    "a piece letter followed by a square, such as Kg1 or a2",
+                    token.to_string(),
+                )
+            })?;
+            let slot = &mut pieces[square.rank().index()][square.file().index()];
+            if slot.is_some() {
+                return Err(list_error(
+                    FenErrorKind::Duplicate,
+                    "at most one piece per square",
+                    format!("two pieces on {}", square),
+                ));
+            }
+            *slot = Some(Piece { color, kind });
+        }
+
+        let meta = DiagramMeta::default();
+        Ok(ChessPosition {
+            pieces,
+            active_color: meta.active_color,
+            castling_rights: meta.castling_rights,
+            en_passant: None,
+            halfmove_clock: meta.halfmove_clock,
+            fullmove_number: meta.fullmove_number,
+        })
+    }
+
+    /* Liste de pièces de la position : les blancs puis les noirs, du roi aux pions, et pour
+     * un même type de la colonne a à la colonne h puis par rangée ; les pions gardent leur `P` */
+    pub fn to_piece_list(&self) -> String {
+        let side = |color: Color| {
+            let mut entries = Vec::new();
+            for kind in PieceKind::ALL {
+                let mut squares: Vec<Square> = (0..64)
+                    .filter_map(Square::from_index)
+                    .filter(|&square| self.piece_at(square) == Some(Piece { color, kind }))
+                    .collect();
+                squares.sort_by_key(|square| (square.file().index(), square.rank().index()));
+                let letter = Piece {
+                    color: Color::White,
+                    kind,
+                }
+                .to_fen_char();
+                entries.extend(squares.iter().map(|square| format!("{}{}", letter, square)));
+            }
+            let name = match color {
+                Color::White => "White:",
+                Color::Black => "Black:",
+            };
+            if entries.is_empty() {
+                name.to_string()
+            } else {
+                format!("{} {}", name, entries.join(", "))
+            }
+        };
+        format!("{}; {}", side(Color::White), side(Color::Black))
+    }
+}
@@ -0,0 +1,132 @@
+/* Positions de référence, pour ne plus recopier Kiwipete dans chaque test.
+
+Chaque constante garde sa FEN (`fen`), la position lue à la première demande (`position()`) et le nombre de coups légaux du camp au trait (`perft1`), qui sert de contrôle. Les positions de perft sont celles de la page « Perft Results » du Chess Programming Wiki. */
+
+use crate::parser::parse_fen;
+use crate::types::ChessPosition;
+use std::sync::OnceLock;
+
+/* Position nommée, lue à la demande */
+#[derive(Debug)]
+pub struct TestPosition {
+    pub name: &'static str,
+    pub fen: &'static str,
+    /* Nombre de coups légaux (perft à la profondeur 1) */
+    pub perft1: u64,
+    /* Ce que la position illustre */
+    pub description: &'static str,
+    position: OnceLock<ChessPosition>,
+}
+
+impl TestPosition {
+    const fn new(
+        name: &'static str,
+        fen: &'static str,
+        perft1: u64,
+        description: &'static str,
+    ) -> Self {
+        TestPosition {
+            name,
+            fen,
+            perft1,
+            description,
+            position: OnceLock::new(),
+        }
+    }
+
+    /* Position décrite par `fen`, lue une seule fois */
+    pub fn position(&self) -> &ChessPosition {
+        self.position.get_or_init(|| {
+            parse_fen(self.fen).unwrap_or_else(|error| panic!("{}: {}", self.name, error))
+        })
+    }
+}
+
+pub static STARTPOS: TestPosition = TestPosition::new(
+    "startpos",
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    20,
+    "standard starting position",
+);
+
+pub static KIWIPETE: TestPosition = TestPosition::new(
+    "kiwipete",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    48,
+    "perft position 2: castling, pins and en passant; 2039 nodes at depth 2",
+);
+
+pub static PERFT_POSITION_3: TestPosition = TestPosition::new(
+    "perft3",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    14,
+    "rook endgame with en passant discovered checks; 191 nodes at depth 2",
+);
+
+pub static PERFT_POSITION_4: TestPosition = TestPosition::new(
+    "perft4",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    6,
+    "promotions and castling under fire; 264 nodes at depth 2",
+);
+
+pub static PERFT_POSITION_5: TestPosition = TestPosition::new(
+    "perft5",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    44,
+    "promotion with capture next to the castling king; 1486 nodes at depth 2",
+);
+
+pub static LASKER_TRAP: TestPosition = TestPosition::new(
+    "lasker-trap",
+    "rnbqk1nr/ppp2ppp/8/4P3/1BP5/8/PP2K1PP/RN1Q1BnR w kq - 0 8",
+    4,
+    "Albin Countergambit after 7...fxg1=N+, white in check by the new knight",
+);
+
+pub static EMPTY_BOARD: TestPosition = TestPosition::new(
+    "empty",
+    "8/8/8/8/8/8/8/8 w - - 0 1",
+    0,
+    "no pieces at all, not a legal position",
+);
+
+pub static RETI_STUDY: TestPosition = TestPosition::new(
+    "reti-study",
+    "7K/8/k1P5/7p/8/8/8/8 w - - 0 1",
+    4,
+    "Réti 1921, white to play and draw",
+);
+
+pub static SAAVEDRA_POSITION: TestPosition = TestPosition::new(
+    "saavedra",
+    "8/8/1KP5/3r4/8/8/8/k7 w - - 0 1",
+    5,
+    "Saavedra 1895, white to play and win by underpromotion",
+);
+
+pub static LUCENA_POSITION: TestPosition = TestPosition::new(
+    "lucena",
+    "1K1k4/1P6/8/8/8/8/r7/2R5 w - - 0 1",
+    14,
+    "Lucena position, white wins by building a bridge",
+);
+
+/* Toutes les positions du module */
+pub static ALL: [&TestPosition; 10] = [
+    &STARTPOS,
+    &KIWIPETE,
+    &PERFT_POSITION_3,
+    &PERFT_POSITION_4,
+    &PERFT_POSITION_5,
+    &LASKER_TRAP,
+    &EMPTY_BOARD,
+    &RETI_STUDY,
+    &SAAVEDRA_POSITION,
+    &LUCENA_POSITION,
+];
+
+/* Position portant ce nom (`kiwipete`, `perft3`, ...) */
+pub fn by_name(name: &str) -> Option<&'static TestPosition> {
+    ALL.iter().copied().find(|position| position.name == name)
+}
@@ -0,0 +1,135 @@
+/* Évaluation par tables de cases (« piece-square tables »).
+
+Les tables sont celles de la « Simplified Evaluation Function » de Tomasz Michniewski, écrites du point de vue des blancs, la huitième rangée en tête comme sur un diagramme ; la valeur d'une pièce noire se lit sur la case symétrique. Le roi a une table de milieu de partie et une table de finale, les autres pièces une seule : le score est interpolé entre les deux selon `phase_value`, et s'ajoute au bilan matériel du barème par défaut. */
+
+use crate::analysis::{phase_points, PieceValues};
+use crate::types::{ChessPosition, Color, PieceKind, Square};
+
+type Table = [i32; 64];
+
+#[rustfmt::skip]
+const PAWN: Table = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    50,  50,  50,  50,  50,  50,  50,  50,
+    10,  10,  20,  30,  30,  20,  10,  10,
+     5,   5,  10,  25,  25,  10,   5,   5,
+     0,   0,   0,  20,  20,   0,   0,   0,
+     5,  -5, -10,   0,   0, -10,  -5,   5,
+     5,  10,  10, -20, -20,  10,  10,   5,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT: Table = [
+   -50, -40, -30, -30, -30, -30, -40, -50,
+   -40, -20,   0,   0,   0,   0, -20, -40,
+   -30,   0,  10,  15,  15,  10,   0, -30,
+   -30,   5,  15,  20,  20,  15,   5, -30,
+   -30,   0,  15,  20,  20,  15,   0, -30,
+   -30,   5,  10,  15,  15,  10,   5, -30,
+   -40, -20,   0,   5,   5,   0, -20, -40,
+   -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+#[rustfmt::skip]
+const BISHOP: Table = [
+   -20, -10, -10, -10, -10, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,  10,  10,   5,   0, -10,
+   -10,   5,   5,  10,  10,   5,   5, -10,
+   -10,   0,  10,  10,  10,  10,   0, -10,
+   -10,  10,  10,  10,  10,  10,  10, -10,
+   -10,   5,   0,   0,   0,   0,   5, -10,
+   -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const ROOK: Table = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+     5,  10,  10,  10,  10,  10,  10,   5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+     0,   0,   0,   5,   5,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const QUEEN: Table = [
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,   5,   5,   5,   0, -10,
+    -5,   0,   5,   5,   5,   5,   0,  -5,
+     0,   0,   5,   5,   5,   5,   0,  -5,
+   -10,   5,   5,   5,   5,   5,   0, -10,
+   -10,   0,   5,   0,   0,   0,   0, -10,
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const KING_MIDDLEGAME: Table = [
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -20, -30, -30, -40, -40, -30, -30, -20,
+   -10, -20, -20, -20, -20, -20, -20, -10,
+    20,  20,   0,   0,   0,   0,  20,  20,
+    20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+#[rustfmt::skip]
+const KING_ENDGAME: Table = [
+   -50, -40, -30, -20, -20, -30, -40, -50,
+   -30, -20, -10,   0,   0, -10, -20, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -30,   0,   0,   0,   0, -30, -30,
+   -50, -30, -30, -30, -30, -30, -30, -50,
+];
+
+/* Tables de milieu de partie et de finale d'un type de pièce */
+fn tables(kind: PieceKind) -> (&'static Table, &'static Table) {
+    match kind {
+        PieceKind::King => (&KING_MIDDLEGAME, &KING_ENDGAME),
+        PieceKind::Queen => (&QUEEN, &QUEEN),
+        PieceKind::Rook => (&ROOK, &ROOK),
+        PieceKind::Bishop => (&BISHOP, &BISHOP),
+        PieceKind::Knight => (&KNIGHT, &KNIGHT),
+        PieceKind::Pawn => (&PAWN, &PAWN),
+    }
+}
+
+/* Indice d'une case dans les tables : la huitième rangée en tête pour les blancs, la case
+ * symétrique pour les noirs */
+fn table_index(square: Square, color: Color) -> usize {
+    match color {
+        Color::White => square.index() ^ 56,
+        Color::Black => square.index(),
+    }
+}
+
+/* Évaluation en centipions, positive si les blancs sont mieux : matériel et tables de cases,
+ * interpolées entre milieu de partie et finale */
+pub fn pst_eval(position: &ChessPosition) -> i32 {
+    let values = PieceValues::default();
+    let (mut middlegame, mut endgame) = (0, 0);
+    for square in (0..64).filter_map(Square::from_index) {
+        let Some(piece) = position.piece_at(square) else {
+            continue;
+        };
+        let (mg_table, eg_table) = tables(piece.kind);
+        let index = table_index(square, piece.color);
+        let sign = match piece.color {
+            Color::White => 1,
+            Color::Black => -1,
+        };
+        middlegame += sign * (values.value(piece.kind) + mg_table[index]);
+        endgame += sign * (values.value(piece.kind) + eg_table[index]);
+    }
+    let phase = phase_points(position) as i32;
+    (middlegame * phase + endgame * (24 - phase)) / 24
+}
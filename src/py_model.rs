@@ -0,0 +1,57 @@
+/* Modèle de la classe Python `Position`.
+
+Ce module réunit, sous la forme d'un type Rust ordinaire, ce qu'exposerait la classe `Position` des liaisons Python : construction depuis une FEN, plateau en dictionnaire case → pièce, trait, roques, échec et coups UCI. Les erreurs sont les messages que porterait la `ValueError` levée côté Python. Le module ne dépend pas de `pyo3` et n'est pas un module d'extension : un paquet de liaisons doit envelopper `Position` dans une classe `#[pyclass]`. */
+
+use crate::types::{ChessPosition, Move, Square};
+use std::collections::BTreeMap;
+
+/* Position manipulée depuis Python */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Position {
+    inner: ChessPosition,
+}
+
+impl Position {
+    /* Position décrite par une FEN ; l'erreur est le message de `FenError` */
+    pub fn from_fen(fen: &str) -> Result<Self, String> {
+        ChessPosition::from_fen(fen)
+            .map(|inner| Self { inner })
+            .map_err(|error| error.to_string())
+    }
+
+    pub fn fen(&self) -> String {
+        self.inner.to_fen()
+    }
+
+    /* Pièces par case (`"e1"` → `"K"`), dans l'ordre des cases */
+    pub fn board(&self) -> BTreeMap<String, String> {
+        (0..64)
+            .filter_map(Square::from_index)
+            .filter_map(|square| {
+                self.inner
+                    .piece_at(square)
+                    .map(|piece| (square.to_string(), piece.to_fen_char().to_string()))
+            })
+            .collect()
+    }
+
+    /* Camp ayant le trait : `"white"` ou `"black"` */
+    pub fn turn(&self) -> &'static str {
+        self.inner.active_color.name()
+    }
+
+    /* Droits de roque en notation FEN (`"KQkq"`, `"-"`) */
+    pub fn castling(&self) -> String {
+        self.inner.castling_rights.to_fen()
+    }
+
+    pub fn is_check(&self) -> bool {
+        self.inner.in_check()
+    }
+
+    /* Joue un coup UCI ; un coup mal écrit ou illégal laisse la position inchangée */
+    pub fn apply_uci(&mut self, uci: &str) -> Result<(), String> {
+        let mv = Move::from_uci(uci).ok_or_else(|| format!("'{}' is not a valid UCI move", uci))?;
+        self.inner.make_move(mv).map_err(|error| error.to_string())
+    }
+}
@@ -0,0 +1,215 @@
+/* Réparation des FEN récupérées telles quelles (pages web, bases de parties, exports).
+
+`repair_fen` corrige, dans cet ordre, les défauts courants qui laissent la position sans ambiguïté, et signale chaque correction par un `RepairWarning` :
+
+1. un numéro de coup en tête (`12. `, `3... `) est retiré ;
+2. un compteur absent (FEN à quatre ou cinq champs) ou écrit `-` prend sa valeur par défaut, 0 demi-coup et coup numéro 1 ;
+3. deux compteurs donnés tous deux, impossibles dans cet ordre (numéro de coup nul, ou plus de demi-coups sans prise que de demi-coups joués) mais possibles une fois échangés sont échangés ;
+4. un droit de roque sans le roi et la tour sur leurs cases d'origine est retiré ;
+5. une case de prise en passant incohérente, ou qu'aucun pion du camp au trait ne borde, est retirée ;
+6. un numéro de coup nul qui reste devient 1.
+
+Rien d'autre n'est touché : la FEN rendue, écrite par `to_fen`, passe `parse_fen` et `validate`, et toute autre erreur (placement illisible, roi manquant, pion sur la dernière rangée...) est renvoyée comme une `FenError`. */
+
+use crate::error::{FenError, FenErrorKind, FenField};
+use crate::fields::split_fen_fields;
+use crate::parser::parse_fen;
+use crate::types::{Color, Square};
+use crate::validation::ValidationError;
+use std::fmt;
+
+/* Correction appliquée par `repair_fen` */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RepairWarning {
+    /* Numéro de coup retiré en tête de la FEN */
+    StrippedPrefix {
+        prefix: String,
+    },
+    /* Compteur absent ou écrit `-`, remplacé par sa valeur par défaut */
+    DefaultedClock {
+        field: FenField,
+        value: u32,
+    },
+    /* Compteurs échangés, avec leurs valeurs une fois remis dans l'ordre */
+    SwappedClocks {
+        halfmove_clock: u32,
+        fullmove_number: u32,
+    },
+    /* Droit de roque retiré faute du roi et de la tour à leur place */
+    RemovedCastlingRight {
+        right: char,
+    },
+    /* Case de prise en passant retirée */
+    RemovedEnPassant {
+        square: Square,
+    },
+    /* Numéro de coup nul remplacé par 1 */
+    FullmoveZero,
+}
+
+impl fmt::Display for RepairWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepairWarning::StrippedPrefix { prefix } => {
+                write!(f, "removed the move number prefix '{}'", prefix)
+            }
+            RepairWarning::DefaultedClock { field, value } => {
+                write!(f, "{} was missing, set to {}", field.name(), value)
+            }
+            RepairWarning::SwappedClocks {
+                halfmove_clock,
+                fullmove_number,
+            } => write!(
+                f,
+                "swapped the counters: halfmove clock {}, fullmove number {}",
+                halfmove_clock, fullmove_number
+            ),
+            RepairWarning::RemovedCastlingRight { right } => write!(
+                f,
+                "removed castling right '{}': the king or rook has left its square",
+                right
+            ),
+            RepairWarning::RemovedEnPassant { square } => write!(
+                f,
+                "removed en passant square {}: no pawn can capture there",
+                square
+            ),
+            RepairWarning::FullmoveZero => f.write_str("fullmove number was 0, set to 1"),
+        }
+    }
+}
+
+/* Longueur d'un numéro de coup en tête, espaces suivants compris : `12. `, `3...` */
+fn move_number_prefix(text: &str) -> Option<usize> {
+    let digits = text.bytes().take_while(u8::is_ascii_digit).count();
+    let dots = text[digits..]
+        .bytes()
+        .take_while(|&byte| byte == b'.')
+        .count();
+    if digits == 0 || dots == 0 {
+        return None;
+    }
+    let rest = &text[digits + dots..];
+    Some(text.len() - rest.trim_start().len())
+}
+
+/* Demi-coups joués depuis le début de la partie au coup `fullmove`, ce camp ayant le trait */
+fn plies_played(fullmove: u32, active_color: Color) -> u64 {
+    2 * u64::from(fullmove.saturating_sub(1)) + u64::from(active_color == Color::Black)
+}
+
+/* Vrai si le compteur de demi-coups ne dépasse pas les demi-coups joués */
+fn clocks_consistent(halfmove: u32, fullmove: u32, active_color: Color) -> bool {
+    fullmove > 0 && u64::from(halfmove) <= plies_played(fullmove, active_color)
+}
+
+/* Erreur rendue pour une incohérence que `repair_fen` ne corrige pas */
+fn beyond_repair(error: &ValidationError) -> FenError {
+    let field = match error {
+        ValidationError::KingCount { .. }
+        | ValidationError::PawnOnBackRank { .. }
+        | ValidationError::TooManyPawns { .. }
+        | ValidationError::TooManyPieces { .. } => FenField::PiecePlacement,
+        _ => FenField::Whole,
+    };
+    FenError {
+        expected: "a position that passes validation",
+        found: error.to_string(),
+        ..FenError::from((field, FenErrorKind::OutOfRange))
+    }
+}
+
+/* Répare une FEN : la FEN corrigée, avec une alerte par correction, ou l'erreur qui empêche
+ * d'en faire une FEN valide */
+pub fn repair_fen(fen: &str) -> Result<(String, Vec<RepairWarning>), FenError> {
+    let mut warnings = Vec::new();
+    let mut text = fen.trim();
+    if let Some(length) = move_number_prefix(text) {
+        warnings.push(RepairWarning::StrippedPrefix {
+            prefix: text[..length].trim_end().to_string(),
+        });
+        text = &text[length..];
+    }
+
+    let fields = split_fen_fields(text)?;
+    let active_color = match fields.active_color() {
+        "b" => Color::Black,
+        _ => Color::White,
+    };
+    /* Un compteur remplacé par sa valeur par défaut n'est jamais échangé */
+    let explicit = [fields.halfmove_clock(), fields.fullmove_number()]
+        .iter()
+        .all(|text| text.is_some_and(|text| text != "-"));
+    let mut clocks = [
+        (FenField::HalfmoveClock, fields.halfmove_clock(), 0),
+        (FenField::FullmoveNumber, fields.fullmove_number(), 1),
+    ]
+    .map(|(field, text, default)| match text {
+        None | Some("-") => {
+            warnings.push(RepairWarning::DefaultedClock {
+                field,
+                value: default,
+            });
+            default.to_string()
+        }
+        Some(text) => text.to_string(),
+    });
+    if let (Ok(halfmove), Ok(fullmove)) = (clocks[0].parse::<u32>(), clocks[1].parse::<u32>()) {
+        if explicit
+            && !clocks_consistent(halfmove, fullmove, active_color)
+            && halfmove > 0
+            && clocks_consistent(fullmove, halfmove, active_color)
+        {
+            clocks.swap(0, 1);
+            warnings.push(RepairWarning::SwappedClocks {
+                halfmove_clock: fullmove,
+                fullmove_number: halfmove,
+            });
+        }
+    }
+
+    let candidate = format!(
+        "{} {} {} {} {} {}",
+        fields.placement(),
+        fields.active_color(),
+        fields.castling(),
+        fields.en_passant(),
+        clocks[0],
+        clocks[1]
+    );
+    let mut position = parse_fen(&candidate)?;
+
+    for error in position.validate().err().unwrap_or_default() {
+        if let ValidationError::CastlingWithoutPieces { right, .. } = error {
+            let rights = &mut position.castling_rights;
+            match right {
+                'K' => rights.white_kingside = false,
+                'Q' => rights.white_queenside = false,
+                'k' => rights.black_kingside = false,
+                _ => rights.black_queenside = false,
+            }
+            warnings.push(RepairWarning::RemovedCastlingRight { right });
+        }
+    }
+    if let Some(square) = position.en_passant_square() {
+        let consistent = !position
+            .validate()
+            .err()
+            .unwrap_or_default()
+            .contains(&ValidationError::ImpossibleEnPassant { square });
+        if !consistent || position.en_passant_capture_origins().is_empty() {
+            position.en_passant = None;
+            warnings.push(RepairWarning::RemovedEnPassant { square });
+        }
+    }
+    if position.fullmove_number == 0 {
+        position.fullmove_number = 1;
+        warnings.push(RepairWarning::FullmoveZero);
+    }
+
+    if let Err(errors) = position.validate() {
+        return Err(beyond_repair(&errors[0]));
+    }
+    Ok((position.to_fen(), warnings))
+}
@@ -0,0 +1,156 @@
+/* Session interactive de manipulation d'une position.
+
+Ce module contient l'état et l'interprétation des commandes du mode `repl` du programme. Les entrées et sorties sont des flux quelconques, ce qui permet de rejouer un script de commandes et d'en capturer le résultat. */
+
+use crate::display::{DisplayOptions, Orientation};
+use crate::types::{ChessPosition, Color, Move};
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  fen <string>   load a position
+  fen            print the current FEN
+  show           render the board
+  move <uci...>  play one or more moves (e.g. move e2e4 e7e5)
+  undo           take back the last move or load
+  flip           turn the board around
+  help           show this help
+  quit           leave the session
+";
+
+/* État d'une session : position courante, positions précédentes et options d'affichage */
+#[derive(Debug, Clone, Default)]
+pub struct ReplSession {
+    position: ChessPosition,
+    /* Positions antérieures, avec le coup qui avait mené à chacune */
+    history: Vec<(ChessPosition, Option<Move>)>,
+    last_move: Option<Move>,
+    options: DisplayOptions,
+}
+
+impl ReplSession {
+    /* Session partant de la position initiale, avec les options d'affichage données */
+    pub fn with_options(options: DisplayOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /* Position courante */
+    pub fn position(&self) -> &ChessPosition {
+        &self.position
+    }
+
+    /* Interprète une ligne de commande ; renvoie faux quand la session doit s'arrêter.
+     * Une commande erronée n'affiche qu'un message `error: ...` */
+    pub fn execute<W: Write>(&mut self, line: &str, out: &mut W) -> io::Result<bool> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Ok(true);
+        };
+        let rest: Vec<&str> = words.collect();
+        match command {
+            "fen" if rest.is_empty() => writeln!(out, "{}", self.position.to_fen())?,
+            "fen" => match ChessPosition::from_fen(&rest.join(" ")) {
+                Ok(position) => {
+                    self.push(position, None);
+                    writeln!(out, "{}", self.position.to_fen())?;
+                }
+                Err(error) => writeln!(out, "error: {}", error)?,
+            },
+            "show" => self.show(out)?,
+            "move" if rest.is_empty() => writeln!(out, "error: move expects UCI moves")?,
+            "move" => {
+                for (index, uci) in rest.iter().enumerate() {
+                    if let Err(reason) = self.play(uci) {
+                        writeln!(out, "error: move {}: {}", index + 1, reason)?;
+                        break;
+                    }
+                }
+                writeln!(out, "{}", self.position.to_fen())?;
+            }
+            "undo" => match self.history.pop() {
+                Some((position, last_move)) => {
+                    self.position = position;
+                    self.last_move = last_move;
+                    writeln!(out, "{}", self.position.to_fen())?;
+                }
+                None => writeln!(out, "error: nothing to undo")?,
+            },
+            "flip" => {
+                let bottom = self
+                    .options
+                    .orientation
+                    .bottom_color(self.position.active_color);
+                self.options.orientation = match bottom {
+                    Color::White => Orientation::Black,
+                    Color::Black => Orientation::White,
+                };
+                let name = if bottom == Color::White {
+                    "black"
+                } else {
+                    "white"
+                };
+                writeln!(out, "{} at the bottom", name)?;
+            }
+            "help" => write!(out, "{}", HELP)?,
+            "quit" | "exit" => return Ok(false),
+            _ => writeln!(out, "error: unknown command '{}' (type help)", command)?,
+        }
+        Ok(true)
+    }
+
+    /* Lit et exécute les commandes jusqu'à `quit` ou la fin de l'entrée ; l'invite n'est
+     * affichée qu'en mode interactif */
+    pub fn run<R: BufRead, W: Write>(
+        &mut self,
+        input: R,
+        out: &mut W,
+        prompt: bool,
+    ) -> io::Result<()> {
+        let mut lines = input.lines();
+        loop {
+            if prompt {
+                write!(out, "fen> ")?;
+                out.flush()?;
+            }
+            let Some(line) = lines.next() else {
+                return Ok(());
+            };
+            if !self.execute(&line?, out)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /* Remplace la position courante en conservant la précédente pour `undo` */
+    fn push(&mut self, position: ChessPosition, mv: Option<Move>) {
+        let previous = std::mem::replace(&mut self.position, position);
+        self.history.push((previous, self.last_move));
+        self.last_move = mv;
+    }
+
+    /* Joue un coup UCI, ou explique pourquoi il est refusé */
+    fn play(&mut self, uci: &str) -> Result<(), String> {
+        let mv = Move::from_uci(uci).ok_or_else(|| format!("'{}' is not a valid UCI move", uci))?;
+        let mut next = self.position.clone();
+        next.make_move(mv).map_err(|error| error.to_string())?;
+        self.push(next, Some(mv));
+        Ok(())
+    }
+
+    /* Plateau, dernier coup mis en évidence, suivi du camp ayant le trait */
+    fn show<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut options = self.options.clone();
+        if let Some(mv) = self.last_move {
+            options.highlight_move(mv);
+        }
+        write!(out, "{}", self.position.render_board(&options))?;
+        let side = match self.position.active_color {
+            Color::White => "White",
+            Color::Black => "Black",
+        };
+        writeln!(out, "{} to move", side)
+    }
+}
@@ -0,0 +1,110 @@
+/* Statistiques d'un corpus de FEN.
+
+Le corpus est lu en une seule passe : on ne conserve que des compteurs, les clés de répétition déjà vues et un histogramme du nombre de pièces, d'où l'on tire minimum, médiane et maximum sans garder les positions. */
+
+use fen_parser::{ChessPosition, Color};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/* Compteurs accumulés position après position */
+#[derive(Debug, Default)]
+pub struct CorpusStats {
+    lines: u64,
+    failures: u64,
+    duplicates: u64,
+    keys: HashSet<u64>,
+    white_to_move: u64,
+    black_to_move: u64,
+    castling: HashMap<String, u64>,
+    signatures: HashMap<String, u64>,
+    /* Nombre de positions pour chaque nombre de pièces, de 0 à 64 */
+    piece_counts: Vec<u64>,
+}
+
+/* Entrées d'un histogramme, de la plus fréquente à la plus rare puis par ordre alphabétique */
+fn by_frequency(histogram: &HashMap<String, u64>) -> Vec<(&str, u64)> {
+    let mut entries: Vec<(&str, u64)> = histogram
+        .iter()
+        .map(|(name, &count)| (name.as_str(), count))
+        .collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    entries
+}
+
+impl CorpusStats {
+    /* Comptabilise une position lue */
+    pub fn record(&mut self, position: &ChessPosition) {
+        self.lines += 1;
+        if !self.keys.insert(position.repetition_key()) {
+            self.duplicates += 1;
+        }
+        match position.active_color {
+            Color::White => self.white_to_move += 1,
+            Color::Black => self.black_to_move += 1,
+        }
+        *self
+            .castling
+            .entry(position.castling_rights.to_fen())
+            .or_default() += 1;
+        let material = position.material();
+        *self.signatures.entry(material.signature()).or_default() += 1;
+        let pieces = material.total() as usize;
+        if self.piece_counts.len() <= pieces {
+            self.piece_counts.resize(pieces + 1, 0);
+        }
+        self.piece_counts[pieces] += 1;
+    }
+
+    /* Comptabilise une ligne qui n'est pas une FEN valide */
+    pub fn record_failure(&mut self) {
+        self.lines += 1;
+        self.failures += 1;
+    }
+
+    /* Nombre de pièces de la position de rang `rank` (à partir de 0) dans l'ordre croissant */
+    fn piece_count_at(&self, rank: u64) -> usize {
+        let mut seen = 0;
+        for (pieces, &count) in self.piece_counts.iter().enumerate() {
+            seen += count;
+            if seen > rank {
+                return pieces;
+            }
+        }
+        0
+    }
+
+    /* Rapport lisible, avec les `top` signatures matérielles les plus fréquentes */
+    pub fn report(&self, top: usize) -> String {
+        let mut out = String::new();
+        let positions = self.lines - self.failures;
+        let _ = writeln!(out, "lines: {}", self.lines);
+        let _ = writeln!(out, "parse failures: {}", self.failures);
+        let _ = writeln!(out, "duplicate positions: {}", self.duplicates);
+        let _ = writeln!(
+            out,
+            "side to move: white {}, black {}",
+            self.white_to_move, self.black_to_move
+        );
+        let castling: Vec<String> = by_frequency(&self.castling)
+            .iter()
+            .map(|(rights, count)| format!("{} {}", rights, count))
+            .collect();
+        let _ = writeln!(out, "castling rights: {}", castling.join(", "));
+        let _ = writeln!(out, "material signatures (top {}):", top);
+        for (signature, count) in by_frequency(&self.signatures).into_iter().take(top) {
+            let _ = writeln!(out, "  {:<34} {}", signature, count);
+        }
+        if positions == 0 {
+            let _ = writeln!(out, "pieces: -");
+        } else {
+            let _ = writeln!(
+                out,
+                "pieces: min {}, median {}, max {}",
+                self.piece_count_at(0),
+                self.piece_count_at((positions - 1) / 2),
+                self.piece_count_at(positions - 1)
+            );
+        }
+        out
+    }
+}
@@ -0,0 +1,201 @@
+/* Générateurs de chaînes FEN pour éprouver le parseur.
+
+`valid_fen()` produit des FEN syntaxiquement valides et canoniques, en forçant les cas limites : rangées entièrement vides, les seize combinaisons de roques, compteurs à 0 et à `u32::MAX`. `almost_valid_fen()` part d'une telle FEN et lui applique une seule altération ciblée (champ retiré, lettre doublée, chiffre augmenté au-delà de la rangée), qui la rend toujours invalide.
+
+Les stratégies suivent la forme de celles de proptest (un `Strategy` produit une valeur à partir d'une source pseudo-aléatoire), sans réduction des cas en échec : la graine suffit à rejouer un cas. */
+
+use crate::generator::RandomPositionGenerator;
+
+const PIECE_LETTERS: &[u8] = b"KQRBNPkqrbnp";
+const CASTLING_LETTERS: [char; 4] = ['K', 'Q', 'k', 'q'];
+
+/* Source pseudo-aléatoire des stratégies, déterminée par sa graine */
+#[derive(Debug, Clone)]
+pub struct TestRng(RandomPositionGenerator);
+
+impl TestRng {
+    pub fn new(seed: u64) -> Self {
+        TestRng(RandomPositionGenerator::new(seed))
+    }
+
+    /* Entier dans l'intervalle [0, bound) */
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.0.below(bound)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+/* Façon de produire une valeur à partir d'une source pseudo-aléatoire */
+pub trait Strategy {
+    type Value;
+
+    fn generate(&self, rng: &mut TestRng) -> Self::Value;
+}
+
+/* Suite sans fin de valeurs produites par une stratégie à partir d'une graine */
+pub fn samples<S: Strategy>(strategy: S, seed: u64) -> impl Iterator<Item = S::Value> {
+    let mut rng = TestRng::new(seed);
+    std::iter::repeat_with(move || strategy.generate(&mut rng))
+}
+
+/* Stratégie de `valid_fen()` */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidFen;
+
+/* FEN syntaxiquement valides, sous la forme canonique qu'écrit `to_fen` */
+pub fn valid_fen() -> ValidFen {
+    ValidFen
+}
+
+/* Une rangée du placement ; une fois sur quatre, la série vide maximale `8` */
+fn rank(rng: &mut TestRng) -> String {
+    if rng.below(4) == 0 {
+        return "8".to_string();
+    }
+    let mut out = String::new();
+    let mut empty = 0;
+    for _ in 0..8 {
+        if rng.below(2) == 0 {
+            empty += 1;
+            continue;
+        }
+        if empty > 0 {
+            out.push(char::from(b'0' + empty));
+            empty = 0;
+        }
+        out.push(char::from(
+            PIECE_LETTERS[rng.below(PIECE_LETTERS.len() as u64) as usize],
+        ));
+    }
+    if empty > 0 {
+        out.push(char::from(b'0' + empty));
+    }
+    out
+}
+
+/* Valeur d'un compteur, les bornes étant tirées aussi souvent que le reste */
+fn counter(rng: &mut TestRng) -> u32 {
+    match rng.below(4) {
+        0 => 0,
+        1 => u32::MAX,
+        2 => 1,
+        _ => rng.below(1000) as u32,
+    }
+}
+
+impl Strategy for ValidFen {
+    type Value = String;
+
+    fn generate(&self, rng: &mut TestRng) -> String {
+        let placement: Vec<String> = (0..8).map(|_| rank(rng)).collect();
+        let white = rng.below(2) == 0;
+        /* Seize combinaisons, dont `-` pour aucune */
+        let mask = rng.below(16);
+        let castling: String = CASTLING_LETTERS
+            .iter()
+            .enumerate()
+            .filter(|&(bit, _)| mask & (1 << bit) != 0)
+            .map(|(_, &letter)| letter)
+            .collect();
+        let en_passant = if rng.below(4) == 0 {
+            let file = char::from(b'a' + rng.below(8) as u8);
+            format!("{}{}", file, if white { 6 } else { 3 })
+        } else {
+            "-".to_string()
+        };
+        format!(
+            "{} {} {} {} {} {}",
+            placement.join("/"),
+            if white { 'w' } else { 'b' },
+            if castling.is_empty() { "-" } else { &castling },
+            en_passant,
+            counter(rng),
+            counter(rng)
+        )
+    }
+}
+
+/* Altération appliquée par `almost_valid_fen()` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mutation {
+    /* Un des six champs est retiré */
+    DroppedField,
+    /* Une lettre du placement, du trait ou des roques est écrite deux fois */
+    DuplicatedLetter,
+    /* Un chiffre du placement est augmenté d'un, la rangée dépassant alors huit cases */
+    BumpedDigit,
+}
+
+impl Mutation {
+    pub const ALL: [Mutation; 3] = [
+        Mutation::DroppedField,
+        Mutation::DuplicatedLetter,
+        Mutation::BumpedDigit,
+    ];
+
+    /* Applique l'altération à une FEN valide à six champs */
+    pub fn apply(self, fen: &str, rng: &mut TestRng) -> String {
+        let mut fields: Vec<String> = fen.split(' ').map(str::to_string).collect();
+        match self {
+            Mutation::DroppedField => {
+                fields.remove(rng.below(fields.len() as u64) as usize);
+            }
+            Mutation::DuplicatedLetter => {
+                /* Positions (champ, octet) des lettres ; le trait en fournit toujours une */
+                let letters: Vec<(usize, usize)> = [0, 1, 2]
+                    .into_iter()
+                    .flat_map(|field| {
+                        fields[field]
+                            .bytes()
+                            .enumerate()
+                            .filter(|(_, byte)| byte.is_ascii_alphabetic())
+                            .map(move |(at, _)| (field, at))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                let (field, at) = letters[rng.below(letters.len() as u64) as usize];
+                let letter = fields[field].as_bytes()[at];
+                fields[field].insert(at, char::from(letter));
+            }
+            Mutation::BumpedDigit => {
+                let digits: Vec<usize> = fields[0]
+                    .bytes()
+                    .enumerate()
+                    .filter(|(_, byte)| byte.is_ascii_digit())
+                    .map(|(at, _)| at)
+                    .collect();
+                if digits.is_empty() {
+                    /* Placement sans case vide : une case de plus en tête */
+                    fields[0].insert(0, '1');
+                } else {
+                    let at = digits[rng.below(digits.len() as u64) as usize];
+                    let digit = fields[0].as_bytes()[at] + 1;
+                    fields[0].replace_range(at..=at, &char::from(digit).to_string());
+                }
+            }
+        }
+        fields.join(" ")
+    }
+}
+
+/* Stratégie de `almost_valid_fen()` */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlmostValidFen;
+
+/* FEN valides altérées une seule fois, et donc toujours refusées par le parseur */
+pub fn almost_valid_fen() -> AlmostValidFen {
+    AlmostValidFen
+}
+
+impl Strategy for AlmostValidFen {
+    type Value = String;
+
+    fn generate(&self, rng: &mut TestRng) -> String {
+        let fen = ValidFen.generate(rng);
+        let mutation = Mutation::ALL[rng.below(Mutation::ALL.len() as u64) as usize];
+        mutation.apply(&fen, rng)
+    }
+}
@@ -0,0 +1,130 @@
+/* Rendu SVG d'une position d'échecs.
+
+Ce module produit un diagramme SVG autonome (sans dépendance externe), adapté aux articles et à la documentation. */
+
+use crate::display::PieceTheme;
+use crate::types::{ChessPosition, Square};
+
+/* Options du rendu SVG */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /* Côté d'une case, en pixels */
+    pub square_size: u32,
+    /* Couleur des cases claires */
+    pub light_color: String,
+    /* Couleur des cases sombres */
+    pub dark_color: String,
+    /* Affiche les lettres et chiffres des coordonnées */
+    pub coordinates: bool,
+    /* Noirs en bas */
+    pub flipped: bool,
+    /* Glyphes des pièces */
+    pub theme: PieceTheme,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            square_size: 45,
+            light_color: "#f0d9b5".to_string(),
+            dark_color: "#b58863".to_string(),
+            coordinates: false,
+            flipped: false,
+            theme: PieceTheme::UNICODE,
+        }
+    }
+}
+
+/* Échappe un texte pour l'insérer dans un document XML */
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+impl ChessPosition {
+    /* Rend la position sous forme de document SVG autonome */
+    pub fn render_svg(&self, opts: &SvgOptions) -> String {
+        let size = opts.square_size.max(1);
+        let margin = if opts.coordinates { size / 2 } else { 0 };
+        let total = 8 * size + 2 * margin;
+
+        let mut out = String::new();
+        out.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{total}\" height=\"{total}\" viewBox=\"0 0 {total} {total}\">\n"
+        ));
+
+        /* Cases puis pièces, pour que les glyphes soient dessinés au-dessus */
+        let mut pieces = String::new();
+        for index in 0..64 {
+            let Some(square) = Square::from_index(index) else {
+                continue;
+            };
+            let (file, rank) = (square.file().index() as u32, square.rank().index() as u32);
+            let (column, row) = if opts.flipped {
+                (7 - file, rank)
+            } else {
+                (file, 7 - rank)
+            };
+            let x = margin + column * size;
+            let y = margin + row * size;
+            let fill = if (file + rank) % 2 == 0 {
+                &opts.dark_color
+            } else {
+                &opts.light_color
+            };
+            out.push_str(&format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                x,
+                y,
+                size,
+                size,
+                xml_escape(fill)
+            ));
+
+            if let Some(piece) = self.piece_at(square) {
+                pieces.push_str(&format!(
+                    "  <text class=\"piece\" x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    x + size / 2,
+                    y + size / 2,
+                    size * 4 / 5,
+                    xml_escape(opts.theme.glyph(piece))
+                ));
+            }
+        }
+        out.push_str(&pieces);
+
+        if opts.coordinates {
+            let font = (size / 3).max(1);
+            for i in 0..8u32 {
+                let (file, rank) = if opts.flipped { (7 - i, i) } else { (i, 7 - i) };
+                out.push_str(&format!(
+                    "  <text class=\"coordinate\" x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                    margin + i * size + size / 2,
+                    total - margin / 4,
+                    font,
+                    (b'a' + file as u8) as char
+                ));
+                out.push_str(&format!(
+                    "  <text class=\"coordinate\" x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    margin / 2,
+                    margin + i * size + size / 2,
+                    font,
+                    rank + 1
+                ));
+            }
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
@@ -0,0 +1,238 @@
+/* Télémétrie structurée du parseur.
+
+Quand la fonctionnalité `telemetry` est active, la lecture des FEN (`parse_fen`, `parse_fen_into`, `parse_fen_with_mode`), les lecteurs par lots et `make_move` émettent des intervalles et des événements à champs nommés : un intervalle de niveau `Debug` par lecture, avec la longueur de l'entrée, un événement `Warn` par écart toléré en mode `Lenient` et un événement `Error` portant les champs de l'erreur (`field`, `kind`, `span`, `expected`, `found`).
+
+Un `Subscriber` les reçoit tous, quel que soit leur niveau, et choisit ce qu'il garde. Il s'installe pour tout le programme (`set_global_subscriber`, une seule fois) ou pour le fil courant le temps d'une fonction (`with_subscriber`), ce qui suffit aux tests ; les fils de `parallel` ne voient que l'abonné global. Sans abonné, seule la recherche de l'abonné est exécutée. */
+
+use crate::error::FenError;
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+/* Niveau d'un événement, du plus grave au plus bavard */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /* Nom en majuscules ("ERROR", "WARN", ...) */
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/* Nature d'un événement : entrée ou sortie d'un intervalle, ou événement ponctuel */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    SpanEnter,
+    SpanExit,
+    Event,
+}
+
+/* Événement transmis à l'abonné */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub kind: EventKind,
+    pub level: Level,
+    /* Nom de l'intervalle, ou message de l'événement */
+    pub name: &'static str,
+    /* Intervalle le plus intérieur ouvert sur ce fil, hors l'intervalle lui-même */
+    pub span: Option<&'static str>,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Event {
+    /* Valeur d'un champ */
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.kind {
+            EventKind::SpanEnter => "enter ",
+            EventKind::SpanExit => "exit ",
+            EventKind::Event => "",
+        };
+        write!(f, "{:>5} {}{}", self.level, marker, self.name)?;
+        for (name, value) in &self.fields {
+            write!(f, " {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/* Destinataire des événements */
+pub trait Subscriber: Send + Sync {
+    fn on_event(&self, event: &Event);
+}
+
+impl<F: Fn(&Event) + Send + Sync> Subscriber for F {
+    fn on_event(&self, event: &Event) {
+        self(event)
+    }
+}
+
+/* Abonné qui garde les événements reçus, pour les tests */
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl Recorder {
+    /* Événements reçus jusqu'ici, dans l'ordre */
+    pub fn events(&self) -> Vec<Event> {
+        self.events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+impl Subscriber for Recorder {
+    fn on_event(&self, event: &Event) {
+        self.events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(event.clone());
+    }
+}
+
+static GLOBAL: OnceLock<Arc<dyn Subscriber>> = OnceLock::new();
+
+thread_local! {
+    static LOCAL: RefCell<Option<Arc<dyn Subscriber>>> = const { RefCell::new(None) };
+    static SPANS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/* Installe l'abonné de tout le programme ; faux s'il l'était déjà */
+pub fn set_global_subscriber(subscriber: impl Subscriber + 'static) -> bool {
+    GLOBAL.set(Arc::new(subscriber)).is_ok()
+}
+
+/* Exécute `body` avec `subscriber` comme abonné du fil courant, à la place de l'abonné
+ * global ; l'abonné précédent est rétabli ensuite, même en cas de panique */
+pub fn with_subscriber<T>(subscriber: impl Subscriber + 'static, body: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Arc<dyn Subscriber>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            LOCAL.with(|local| *local.borrow_mut() = previous);
+        }
+    }
+    let subscriber: Arc<dyn Subscriber> = Arc::new(subscriber);
+    let _restore = Restore(LOCAL.with(|local| local.borrow_mut().replace(subscriber)));
+    body()
+}
+
+/* Abonné du fil courant, à défaut l'abonné global */
+fn current() -> Option<Arc<dyn Subscriber>> {
+    LOCAL
+        .with(|local| local.borrow().clone())
+        .or_else(|| GLOBAL.get().cloned())
+}
+
+fn innermost_span() -> Option<&'static str> {
+    SPANS.with(|spans| spans.borrow().last().copied())
+}
+
+/* Émet un événement ; les champs ne sont calculés qu'en présence d'un abonné */
+pub(crate) fn event(
+    level: Level,
+    name: &'static str,
+    fields: impl FnOnce() -> Vec<(&'static str, String)>,
+) {
+    if let Some(subscriber) = current() {
+        subscriber.on_event(&Event {
+            kind: EventKind::Event,
+            level,
+            name,
+            span: innermost_span(),
+            fields: fields(),
+        });
+    }
+}
+
+/* Intervalle ouvert jusqu'à la destruction de la garde, qui émet sa sortie avec la durée
+ * écoulée (`elapsed_us`) */
+pub(crate) struct SpanGuard {
+    open: Option<(Arc<dyn Subscriber>, Level, &'static str, Instant)>,
+}
+
+/* Ouvre un intervalle ; sans abonné, la garde ne fait rien */
+pub(crate) fn span(
+    level: Level,
+    name: &'static str,
+    fields: impl FnOnce() -> Vec<(&'static str, String)>,
+) -> SpanGuard {
+    let Some(subscriber) = current() else {
+        return SpanGuard { open: None };
+    };
+    subscriber.on_event(&Event {
+        kind: EventKind::SpanEnter,
+        level,
+        name,
+        span: innermost_span(),
+        fields: fields(),
+    });
+    SPANS.with(|spans| spans.borrow_mut().push(name));
+    SpanGuard {
+        open: Some((subscriber, level, name, Instant::now())),
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        let Some((subscriber, level, name, start)) = self.open.take() else {
+            return;
+        };
+        SPANS.with(|spans| spans.borrow_mut().pop());
+        subscriber.on_event(&Event {
+            kind: EventKind::SpanExit,
+            level,
+            name,
+            span: innermost_span(),
+            fields: vec![("elapsed_us", start.elapsed().as_micros().to_string())],
+        });
+    }
+}
+
+/* Événement `Error` portant les champs d'une erreur de lecture */
+pub(crate) fn parse_error(error: &FenError) {
+    event(Level::Error, "invalid FEN", || {
+        vec![
+            ("field", error.field.name().to_string()),
+            ("kind", error.kind.description().to_string()),
+            (
+                "span",
+                error.span.as_ref().map_or("-".to_string(), |span| {
+                    format!("{}..{}", span.start, span.end)
+                }),
+            ),
+            ("expected", error.expected.to_string()),
+            ("found", error.found.clone()),
+        ]
+    });
+}
@@ -0,0 +1,37 @@
+/* Corpus de FEN partagés par les mesures et les tests.
+
+Le corpus mixte `benches/data/mixed.fen` est compilé dans la bibliothèque, pour que les mesures donnent les mêmes chiffres d'une machine à l'autre, avant comme après un changement du parseur. Les lignes vides et les commentaires `#` sont ignorés, comme dans `batch`. */
+
+use crate::batch;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+/* Corpus mixte : positions vraisemblables, plateaux quelconques, formes non canoniques et
+ * FEN invalides, une par ligne */
+pub const MIXED_CORPUS: &str = include_str!("../benches/data/mixed.fen");
+
+/* FEN d'un texte, une par ligne, sans les lignes vides ni les commentaires */
+pub fn corpus_lines(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/* FEN d'un fichier de corpus */
+pub fn load_corpus(path: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    let file = File::open(path)?;
+    batch::entries(BufReader::new(file))
+        .map(|entry| entry.map(|entry| entry.fen))
+        .collect()
+}
+
+/* `count` FEN du corpus mixte, reprises depuis le début une fois le corpus épuisé */
+pub fn mixed_corpus(count: usize) -> Vec<&'static str> {
+    corpus_lines(MIXED_CORPUS)
+        .into_iter()
+        .cycle()
+        .take(count)
+        .collect()
+}
@@ -4,16 +4,21 @@ Ce module contient les structures et enumerations nécessaires pour représenter
 
 use crate::error::FenError;
 use crate::parser::parse_fen;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{BitAnd, BitOr, Not};
+use core::str::FromStr;
 
 /* Couleur d'une pièce (Blanc ou Noir) */
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
     White,
     Black,
 }
 
 /* Type de pièce d'échecs */
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PieceKind {
     King,
     Queen,
@@ -23,15 +28,110 @@ pub enum PieceKind {
     Pawn,
 }
 
+impl Color {
+    /* Nom en minuscules ("white", "black") */
+    pub fn name(self) -> &'static str {
+        match self {
+            Color::White => "white",
+            Color::Black => "black",
+        }
+    }
+
+    /* Camp adverse */
+    pub fn opposite(self) -> Self {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+impl PieceKind {
+    /* Tous les types de pièces, du roi au pion */
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::King,
+        PieceKind::Queen,
+        PieceKind::Rook,
+        PieceKind::Bishop,
+        PieceKind::Knight,
+        PieceKind::Pawn,
+    ];
+
+    /* Indice du type dans `PieceKind::ALL` (0 = roi) */
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /* Valeur conventionnelle en pions (9/5/3/3/1), nulle pour le roi */
+    pub fn value(self) -> u32 {
+        match self {
+            PieceKind::King => 0,
+            PieceKind::Queen => 9,
+            PieceKind::Rook => 5,
+            PieceKind::Bishop | PieceKind::Knight => 3,
+            PieceKind::Pawn => 1,
+        }
+    }
+
+    /* Nom en minuscules ("king", "queen", ...) */
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceKind::King => "king",
+            PieceKind::Queen => "queen",
+            PieceKind::Rook => "rook",
+            PieceKind::Bishop => "bishop",
+            PieceKind::Knight => "knight",
+            PieceKind::Pawn => "pawn",
+        }
+    }
+}
+
 /* Pièce d'échecs avec sa couleur et son type */
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Piece {
     pub color: Color,
     pub kind: PieceKind,
 }
 
+impl Piece {
+    /* Pièce correspondant à une lettre FEN, ou `None` pour un autre caractère */
+    pub fn from_fen_char(c: char) -> Option<Self> {
+        let kind = match c.to_ascii_lowercase() {
+            'k' => PieceKind::King,
+            'q' => PieceKind::Queen,
+            'r' => PieceKind::Rook,
+            'b' => PieceKind::Bishop,
+            'n' => PieceKind::Knight,
+            'p' => PieceKind::Pawn,
+            _ => return None,
+        };
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        Some(Piece { color, kind })
+    }
+
+    /* Lettre FEN de la pièce (majuscule pour les blancs) */
+    pub fn to_fen_char(self) -> char {
+        let c = match self.kind {
+            PieceKind::King => 'k',
+            PieceKind::Queen => 'q',
+            PieceKind::Rook => 'r',
+            PieceKind::Bishop => 'b',
+            PieceKind::Knight => 'n',
+            PieceKind::Pawn => 'p',
+        };
+        match self.color {
+            Color::White => c.to_ascii_uppercase(),
+            Color::Black => c,
+        }
+    }
+}
+
 /* Droits de roque pour les deux camps */
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CastlingRights {
     pub white_kingside: bool,
     pub white_queenside: bool,
@@ -45,6 +145,27 @@ impl CastlingRights {
         self.white_kingside || self.white_queenside || self.black_kingside || self.black_queenside
     }
 
+    /* Représentation FEN des droits de roque ("KQkq", "-" si aucun) */
+    pub fn to_fen(&self) -> String {
+        let mut s = String::new();
+        if self.white_kingside {
+            s.push('K');
+        }
+        if self.white_queenside {
+            s.push('Q');
+        }
+        if self.black_kingside {
+            s.push('k');
+        }
+        if self.black_queenside {
+            s.push('q');
+        }
+        if s.is_empty() {
+            s.push('-');
+        }
+        s
+    }
+
     /* Crée une instance sans aucun droit de roque */
     pub fn none() -> Self {
         Self {
@@ -56,8 +177,60 @@ impl CastlingRights {
     }
 }
 
+/* Champ FEN (`KQkq`, `-`) ; avec `{:#}`, forme développée par camp :
+ * `White: O-O, O-O-O; Black: O-O`, ou `none` sans aucun droit */
+impl fmt::Display for CastlingRights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() {
+            return f.write_str(&self.to_fen());
+        }
+        if !self.has_any() {
+            return f.write_str("none");
+        }
+        let sides = [
+            ("White", self.white_kingside, self.white_queenside),
+            ("Black", self.black_kingside, self.black_queenside),
+        ];
+        let mut first = true;
+        for (name, kingside, queenside) in sides {
+            if !kingside && !queenside {
+                continue;
+            }
+            if !first {
+                f.write_str("; ")?;
+            }
+            first = false;
+            write!(f, "{}: ", name)?;
+            match (kingside, queenside) {
+                (true, true) => f.write_str("O-O, O-O-O")?,
+                (true, false) => f.write_str("O-O")?,
+                _ => f.write_str("O-O-O")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/* Lit le champ des droits de roque seul, avec les contrôles de `parse_fen` */
+impl FromStr for CastlingRights {
+    type Err = FenError;
+
+    fn from_str(text: &str) -> Result<Self, FenError> {
+        crate::parser::parse_castling_rights(text)
+    }
+}
+
+/* Aile d'un roque */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastlingSide {
+    /* Petit roque, O-O */
+    Kingside,
+    /* Grand roque, O-O-O */
+    Queenside,
+}
+
 /* Position complète d'un jeu d'échecs */
-#[derive(Debug, PartialEq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct ChessPosition {
     /* Plateau 8x8 représentant les pièces */
     pub pieces: [[Option<Piece>; 8]; 8],
@@ -76,15 +249,665 @@ pub struct ChessPosition {
 /* Crée une position initiale standard */
 impl Default for ChessPosition {
     fn default() -> Self {
-        // On utilise directement la construction manuelle pour éviter la dépendance circulaire
-        let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
-        parse_fen(fen).expect("Default FEN should be valid")
+        crate::parser::start_position()
     }
 }
 
-/* Crée une position à partir d'une chaîne FEN */
 impl ChessPosition {
+    /* Crée une position à partir d'une chaîne FEN */
     pub fn from_fen(fen: &str) -> Result<Self, FenError> {
         parse_fen(fen)
     }
+
+    /* Case de prise en passant, si elle existe */
+    pub fn en_passant_square(&self) -> Option<Square> {
+        self.en_passant
+            .and_then(|(file, rank)| Square::from_coords(file, rank))
+    }
+
+    /* Case du pion qui serait pris en passant, juste derrière la case cible du point de vue
+     * du camp au trait : e5 pour une cible en e6, les blancs ayant le trait */
+    pub fn en_passant_victim(&self) -> Option<Square> {
+        let target = self.en_passant_square()?;
+        target.step(match self.active_color {
+            Color::White => Direction::South,
+            Color::Black => Direction::North,
+        })
+    }
+
+    /* Cases d'où un pion du camp au trait peut prendre en passant : de part et d'autre du
+     * pion à prendre, sur sa rangée (la cinquième du camp), et portant un pion du camp. Une
+     * seule case au plus pour une cible en colonne a ou h, aucune sans cible. */
+    pub fn en_passant_capture_origins(&self) -> SquareSet {
+        let Some(victim) = self.en_passant_victim() else {
+            return SquareSet::empty();
+        };
+        let pawn = Some(Piece {
+            color: self.active_color,
+            kind: PieceKind::Pawn,
+        });
+        [Direction::West, Direction::East]
+            .into_iter()
+            .filter_map(|direction| victim.step(direction))
+            .filter(|&square| self.piece_at(square) == pawn)
+            .collect()
+    }
+
+    /* Renvoie la pièce occupant une case */
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.pieces[square.rank().index()][square.file().index()]
+    }
+
+    /* Rangées de la première à la huitième, chacune de a à h */
+    pub fn ranks(&self) -> impl ExactSizeIterator<Item = [Option<Piece>; 8]> + '_ {
+        self.pieces.iter().copied()
+    }
+
+    /* Colonnes de a à h, chacune de la première à la huitième rangée */
+    pub fn files(&self) -> impl ExactSizeIterator<Item = [Option<Piece>; 8]> + '_ {
+        (0..8).map(move |file| core::array::from_fn(|rank| self.pieces[rank][file]))
+    }
+
+    /* Les 15 diagonales parallèles à a1-h8, de a8 (une case) à h1 : la huitième est la
+     * grande diagonale a1-h8. Chacune va de sa case la plus basse à la plus haute. */
+    pub fn diagonals(&self) -> impl ExactSizeIterator<Item = Vec<Option<Piece>>> + '_ {
+        (0..15usize).map(move |diagonal| {
+            (0..8)
+                .filter_map(|rank| {
+                    let file = (diagonal + rank).checked_sub(7).filter(|&file| file < 8)?;
+                    Some(self.pieces[rank][file])
+                })
+                .collect()
+        })
+    }
+
+    /* Les 15 diagonales parallèles à a8-h1, de a1 (une case) à h8 : la huitième est la
+     * grande diagonale h1-a8. Chacune va de sa case la plus basse à la plus haute. */
+    pub fn anti_diagonals(&self) -> impl ExactSizeIterator<Item = Vec<Option<Piece>>> + '_ {
+        (0..15usize).map(move |diagonal| {
+            (0..8)
+                .filter_map(|rank| {
+                    let file = diagonal.checked_sub(rank).filter(|&file| file < 8)?;
+                    Some(self.pieces[rank][file])
+                })
+                .collect()
+        })
+    }
+
+    /* Cases occupées par une pièce, quel qu'en soit le camp */
+    pub fn occupied(&self) -> SquareSet {
+        (0..64)
+            .filter_map(Square::from_index)
+            .filter(|&square| self.piece_at(square).is_some())
+            .collect()
+    }
+
+    /* Première pièce rencontrée en partant d'une case dans une direction, avec sa case */
+    pub fn first_piece_along(&self, from: Square, direction: Direction) -> Option<(Square, Piece)> {
+        from.ray(direction)
+            .find_map(|square| self.piece_at(square).map(|piece| (square, piece)))
+    }
+
+    /* Position miroir : rangées inversées, couleurs des pièces, droits de roque et trait
+     * échangés ; les compteurs sont conservés */
+    pub fn color_flipped(&self) -> ChessPosition {
+        let mut pieces = [[None; 8]; 8];
+        for (rank, row) in self.pieces.iter().enumerate() {
+            pieces[7 - rank] = row.map(|piece| {
+                piece.map(|piece| Piece {
+                    color: piece.color.opposite(),
+                    kind: piece.kind,
+                })
+            });
+        }
+        let rights = self.castling_rights;
+        ChessPosition {
+            pieces,
+            active_color: self.active_color.opposite(),
+            castling_rights: CastlingRights {
+                white_kingside: rights.black_kingside,
+                white_queenside: rights.black_queenside,
+                black_kingside: rights.white_kingside,
+                black_queenside: rights.white_queenside,
+            },
+            en_passant: self.en_passant.map(|(file, rank)| (file, 7 - rank.min(7))),
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+        }
+    }
+
+    /* Vrai si la position est sa propre image miroir, au trait près que le miroir échange
+     * forcément : placement, droits de roque et prise en passant, sans les compteurs */
+    pub fn is_color_symmetric(&self) -> bool {
+        let flipped = self.color_flipped();
+        flipped.pieces == self.pieces
+            && flipped.castling_rights == self.castling_rights
+            && flipped.en_passant == self.en_passant
+    }
+
+    /* Vrai si le placement des pièces seul est symétrique, sans tenir compte du trait, des
+     * droits de roque ni de la prise en passant */
+    pub fn board_symmetric(&self) -> bool {
+        self.color_flipped().pieces == self.pieces
+    }
+}
+
+/* Colonne de l'échiquier (a à h) */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum File {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+}
+
+impl File {
+    /* Toutes les colonnes, de a à h */
+    pub const ALL: [File; 8] = [
+        File::A,
+        File::B,
+        File::C,
+        File::D,
+        File::E,
+        File::F,
+        File::G,
+        File::H,
+    ];
+
+    /* Crée une colonne depuis son indice (0 = a) */
+    pub fn from_index(index: usize) -> Option<Self> {
+        File::ALL.get(index).copied()
+    }
+
+    /* Indice de la colonne (0 = a) */
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /* Lettre de la colonne */
+    pub fn to_char(self) -> char {
+        (b'a' + self as u8) as char
+    }
+}
+
+/* Rangée de l'échiquier (1 à 8) */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Eighth,
+}
+
+impl Rank {
+    /* Toutes les rangées, de 1 à 8 */
+    pub const ALL: [Rank; 8] = [
+        Rank::First,
+        Rank::Second,
+        Rank::Third,
+        Rank::Fourth,
+        Rank::Fifth,
+        Rank::Sixth,
+        Rank::Seventh,
+        Rank::Eighth,
+    ];
+
+    /* Crée une rangée depuis son indice (0 = 1ère rangée) */
+    pub fn from_index(index: usize) -> Option<Self> {
+        Rank::ALL.get(index).copied()
+    }
+
+    /* Indice de la rangée (0 = 1ère rangée) */
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /* Chiffre de la rangée */
+    pub fn to_char(self) -> char {
+        (b'1' + self as u8) as char
+    }
+}
+
+/* Case de l'échiquier, indexée de 0 (a1) à 63 (h8) */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Square(u8);
+
+impl Square {
+    /* Crée une case depuis sa colonne et sa rangée */
+    pub fn new(file: File, rank: Rank) -> Self {
+        Square(rank as u8 * 8 + file as u8)
+    }
+
+    /* Crée une case depuis des indices de colonne et de rangée (0-7) */
+    pub fn from_coords(file: u8, rank: u8) -> Option<Self> {
+        if file < 8 && rank < 8 {
+            Some(Square(rank * 8 + file))
+        } else {
+            None
+        }
+    }
+
+    /* Crée une case depuis son indice (0 = a1, 63 = h8) */
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index < 64 {
+            Some(Square(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /* Les 64 cases dans l'ordre de leurs indices, de a1 à h8 */
+    pub fn all() -> impl DoubleEndedIterator<Item = Square> + ExactSizeIterator {
+        Square::iter(SquareOrder::Lerf)
+    }
+
+    /* Les 64 cases dans l'ordre demandé */
+    pub fn iter(order: SquareOrder) -> impl DoubleEndedIterator<Item = Square> + ExactSizeIterator {
+        (0..64u8).map(move |i| match order {
+            SquareOrder::Lerf => Square(i),
+            SquareOrder::Fen => Square((7 - i / 8) * 8 + i % 8),
+            SquareOrder::FileMajor => Square(i % 8 * 8 + i / 8),
+        })
+    }
+
+    /* Crée une case depuis son nom algébrique (ex: "e4") */
+    pub fn from_name(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        let file = chars.next()?;
+        let rank = chars.next()?;
+        if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return None;
+        }
+        Square::from_coords(file as u8 - b'a', rank as u8 - b'1')
+    }
+
+    /* Indice de la case (0 = a1, 63 = h8) */
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /* Colonne de la case */
+    pub fn file(self) -> File {
+        File::ALL[(self.0 % 8) as usize]
+    }
+
+    /* Rangée de la case */
+    pub fn rank(self) -> Rank {
+        Rank::ALL[(self.0 / 8) as usize]
+    }
+
+    /* Vrai pour une case blanche ; a1 est noire, h1 blanche */
+    pub fn is_light(self) -> bool {
+        (self.0 % 8 + self.0 / 8) % 2 == 1
+    }
+
+    /* Vrai pour une case noire */
+    pub fn is_dark(self) -> bool {
+        !self.is_light()
+    }
+
+    /* Couleur de la case */
+    pub fn color_complex(self) -> SquareColor {
+        if self.is_light() {
+            SquareColor::Light
+        } else {
+            SquareColor::Dark
+        }
+    }
+
+    /* Écart de colonnes avec une autre case */
+    pub fn file_distance(self, other: Square) -> u8 {
+        (self.0 % 8).abs_diff(other.0 % 8)
+    }
+
+    /* Écart de rangées avec une autre case */
+    pub fn rank_distance(self, other: Square) -> u8 {
+        (self.0 / 8).abs_diff(other.0 / 8)
+    }
+
+    /* Distance en coups de roi (distance de Tchebychev), de 0 à 7 */
+    pub fn chebyshev_distance(self, other: Square) -> u8 {
+        self.file_distance(other).max(self.rank_distance(other))
+    }
+
+    /* Distance en déplacements orthogonaux (distance de Manhattan), de 0 à 14 */
+    pub fn manhattan_distance(self, other: Square) -> u8 {
+        self.file_distance(other) + self.rank_distance(other)
+    }
+
+    /* Direction d'une autre case située sur la même colonne, rangée ou diagonale ; `None`
+     * pour la case elle-même ou une case hors de ces lignes */
+    pub fn direction_to(self, other: Square) -> Option<Direction> {
+        let file = (other.0 % 8) as i8 - (self.0 % 8) as i8;
+        let rank = (other.0 / 8) as i8 - (self.0 / 8) as i8;
+        if self == other || (file != 0 && rank != 0 && file.abs() != rank.abs()) {
+            return None;
+        }
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == (file.signum(), rank.signum()))
+    }
+
+    /* Cases rencontrées en partant de la case dans une direction, jusqu'au bord du plateau,
+     * la case de départ exclue */
+    pub fn ray(self, direction: Direction) -> impl Iterator<Item = Square> {
+        core::iter::successors(self.step(direction), move |square| square.step(direction))
+    }
+
+    /* Vrai si les deux cases sont sur la même rangée */
+    pub fn same_rank(self, other: Square) -> bool {
+        self.0 / 8 == other.0 / 8
+    }
+
+    /* Vrai si les deux cases sont sur la même colonne */
+    pub fn same_file(self, other: Square) -> bool {
+        self.0 % 8 == other.0 % 8
+    }
+
+    /* Vrai si les deux cases sont sur la même diagonale, parallèle à a1-h8 */
+    pub fn same_diagonal(self, other: Square) -> bool {
+        (self.0 % 8) as i8 - (self.0 / 8) as i8 == (other.0 % 8) as i8 - (other.0 / 8) as i8
+    }
+
+    /* Vrai si les deux cases sont sur la même anti-diagonale, parallèle à a8-h1 */
+    pub fn same_antidiagonal(self, other: Square) -> bool {
+        self.0 % 8 + self.0 / 8 == other.0 % 8 + other.0 / 8
+    }
+
+    /* Case voisine dans une direction, `None` au bord du plateau */
+    pub fn step(self, direction: Direction) -> Option<Square> {
+        let (file, rank) = direction.offset();
+        let file = (self.0 % 8) as i8 + file;
+        let rank = (self.0 / 8) as i8 + rank;
+        Square::from_coords(u8::try_from(file).ok()?, u8::try_from(rank).ok()?)
+    }
+}
+
+/* Ordre de parcours des cases par `Square::iter` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SquareOrder {
+    /* Rangée par rangée depuis a1 : a1, b1, ..., h1, a2, ..., h8 (ordre des indices) */
+    #[default]
+    Lerf,
+    /* Rangée par rangée depuis a8, comme le placement FEN : a8, ..., h8, a7, ..., h1 */
+    Fen,
+    /* Colonne par colonne depuis a1 : a1, a2, ..., a8, b1, ..., h8 */
+    FileMajor,
+}
+
+/* Couleur d'une case de l'échiquier */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SquareColor {
+    Light,
+    Dark,
+}
+
+/* Cases strictement entre deux cases alignées (même rangée, colonne ou diagonale) ; vide si
+ * elles ne sont pas alignées ou sont voisines */
+pub fn between(a: Square, b: Square) -> SquareSet {
+    match a.direction_to(b) {
+        Some(direction) => a.ray(direction).take_while(|&square| square != b).collect(),
+        None => SquareSet::empty(),
+    }
+}
+
+/* Une des huit directions de l'échiquier, vue des blancs : le nord va vers la huitième rangée */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /* Les huit directions, du nord dans le sens des aiguilles d'une montre */
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /* Décalage d'un pas (colonnes, rangées) */
+    pub fn offset(self) -> (i8, i8) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, -1),
+            Direction::South => (0, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, 1),
+        }
+    }
+
+    /* Vrai pour les quatre directions diagonales */
+    pub fn is_diagonal(self) -> bool {
+        let (file, rank) = self.offset();
+        file != 0 && rank != 0
+    }
+}
+
+/* Affiche la case en notation algébrique */
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.file().to_char(), self.rank().to_char())
+    }
+}
+
+/* Ensemble de cases sous forme de masque de 64 bits, bit `i` pour la case d'indice `i` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SquareSet(pub u64);
+
+impl SquareSet {
+    /* Les 32 cases blanches, dont h1 et a8 */
+    pub const LIGHT_SQUARES: SquareSet = SquareSet(0x55AA_55AA_55AA_55AA);
+    /* Les 32 cases noires, dont a1 et h8 */
+    pub const DARK_SQUARES: SquareSet = SquareSet(0xAA55_AA55_AA55_AA55);
+
+    /* Ensemble vide */
+    pub const fn empty() -> Self {
+        SquareSet(0)
+    }
+
+    /* Les 64 cases */
+    pub const fn full() -> Self {
+        SquareSet(u64::MAX)
+    }
+
+    /* Les huit cases d'une rangée */
+    pub const fn rank(rank: Rank) -> Self {
+        SquareSet(0xFF << (8 * rank as u32))
+    }
+
+    /* Les huit cases d'une colonne */
+    pub const fn file(file: File) -> Self {
+        SquareSet(0x0101_0101_0101_0101 << file as u32)
+    }
+
+    /* Ajoute une case ; vrai si elle n'y était pas */
+    pub fn insert(&mut self, square: Square) -> bool {
+        let absent = !self.contains(square);
+        self.0 |= 1 << square.0;
+        absent
+    }
+
+    /* Retire une case ; vrai si elle y était */
+    pub fn remove(&mut self, square: Square) -> bool {
+        let present = self.contains(square);
+        self.0 &= !(1 << square.0);
+        present
+    }
+
+    pub fn contains(self, square: Square) -> bool {
+        self.0 & (1 << square.0) != 0
+    }
+
+    /* Nombre de cases de l'ensemble */
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /* Cases de l'ensemble, par indice croissant (a1, b1, ..., h8) */
+    pub fn iter(self) -> SquareSetIter {
+        SquareSetIter(self.0)
+    }
+}
+
+impl BitOr for SquareSet {
+    type Output = SquareSet;
+
+    fn bitor(self, other: SquareSet) -> SquareSet {
+        SquareSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for SquareSet {
+    type Output = SquareSet;
+
+    fn bitand(self, other: SquareSet) -> SquareSet {
+        SquareSet(self.0 & other.0)
+    }
+}
+
+impl Not for SquareSet {
+    type Output = SquareSet;
+
+    fn not(self) -> SquareSet {
+        SquareSet(!self.0)
+    }
+}
+
+/* Itérateur sur les cases d'un `SquareSet`, du bit de poids faible au bit de poids fort */
+#[derive(Debug, Clone)]
+pub struct SquareSetIter(u64);
+
+impl Iterator for SquareSetIter {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Square> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        /* Retire le bit le plus bas */
+        self.0 &= self.0 - 1;
+        Some(Square(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for SquareSetIter {}
+
+impl IntoIterator for SquareSet {
+    type Item = Square;
+    type IntoIter = SquareSetIter;
+
+    fn into_iter(self) -> SquareSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Square> for SquareSet {
+    fn from_iter<I: IntoIterator<Item = Square>>(squares: I) -> Self {
+        let mut set = SquareSet::empty();
+        for square in squares {
+            set.insert(square);
+        }
+        set
+    }
+}
+
+/* Diagramme 8×8 pour le débogage, 8e rangée en haut : `x` pour une case de l'ensemble, `.` sinon */
+impl fmt::Display for SquareSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for square in Square::iter(SquareOrder::Fen) {
+            if square.file() != File::A {
+                f.write_str(" ")?;
+            }
+            f.write_str(if self.contains(square) { "x" } else { "." })?;
+            if square.file() == File::H {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* Coup d'une case à une autre, avec promotion éventuelle */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<PieceKind>,
+}
+
+impl Move {
+    /* Crée un coup sans promotion */
+    pub fn new(from: Square, to: Square) -> Self {
+        Self {
+            from,
+            to,
+            promotion: None,
+        }
+    }
+
+    /* Lit un coup en notation UCI (ex: "e2e4", "e7e8q") */
+    pub fn from_uci(uci: &str) -> Option<Self> {
+        let from = Square::from_name(uci.get(0..2)?)?;
+        let to = Square::from_name(uci.get(2..4)?)?;
+        let promotion = match uci.get(4..)? {
+            "" => None,
+            "q" => Some(PieceKind::Queen),
+            "r" => Some(PieceKind::Rook),
+            "b" => Some(PieceKind::Bishop),
+            "n" => Some(PieceKind::Knight),
+            _ => return None,
+        };
+        Some(Self {
+            from,
+            to,
+            promotion,
+        })
+    }
+}
+
+/* Affiche le coup en notation UCI */
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.to)?;
+        match self.promotion {
+            Some(PieceKind::Queen) => write!(f, "q"),
+            Some(PieceKind::Rook) => write!(f, "r"),
+            Some(PieceKind::Bishop) => write!(f, "b"),
+            Some(PieceKind::Knight) => write!(f, "n"),
+            _ => Ok(()),
+        }
+    }
 }
@@ -0,0 +1,156 @@
+/* Validation sémantique d'une position d'échecs.
+
+Une FEN peut être syntaxiquement correcte tout en décrivant une position impossible : deux rois blancs, un pion sur la dernière rangée, un droit de roque sans tour... Ce module regroupe ces contrôles, effectués après le parsing. */
+
+use crate::analysis::MaterialCount;
+use crate::types::{ChessPosition, Color, File, Piece, PieceKind, Rank, Square};
+use thiserror::Error;
+
+/* Incohérence détectée dans une position syntaxiquement valide */
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ValidationError {
+    #[error("{} has {count} kings, expected exactly one", .color.name())]
+    KingCount { color: Color, count: u32 },
+    #[error("{} pawn on {square}: pawns cannot stand on the first or last rank", .color.name())]
+    PawnOnBackRank { color: Color, square: Square },
+    #[error("{} has {count} pawns, expected at most 8", .color.name())]
+    TooManyPawns { color: Color, count: u32 },
+    #[error("{} has {count} pieces, expected at most 16", .color.name())]
+    TooManyPieces { color: Color, count: u32 },
+    #[error("castling right '{right}' requires the king on {king} and a rook on {rook}")]
+    CastlingWithoutPieces {
+        right: char,
+        king: Square,
+        rook: Square,
+    },
+    #[error("en passant square {square} does not match a pawn that just advanced two squares")]
+    ImpossibleEnPassant { square: Square },
+    #[error("fullmove number is 0, expected at least 1")]
+    FullmoveZero,
+}
+
+impl ChessPosition {
+    /* Vérifie la cohérence de la position ; renvoie toutes les incohérences trouvées */
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let material = MaterialCount::of(self);
+
+        for color in [Color::White, Color::Black] {
+            let kings = material.count(color, PieceKind::King);
+            if kings != 1 {
+                errors.push(ValidationError::KingCount {
+                    color,
+                    count: kings,
+                });
+            }
+            let pawns = material.count(color, PieceKind::Pawn);
+            if pawns > 8 {
+                errors.push(ValidationError::TooManyPawns {
+                    color,
+                    count: pawns,
+                });
+            }
+            let pieces: u32 = PieceKind::ALL
+                .iter()
+                .map(|&kind| material.count(color, kind))
+                .sum();
+            if pieces > 16 {
+                errors.push(ValidationError::TooManyPieces {
+                    color,
+                    count: pieces,
+                });
+            }
+        }
+
+        for rank in [Rank::First, Rank::Eighth] {
+            for file in File::ALL {
+                let square = Square::new(file, rank);
+                if let Some(piece) = self.piece_at(square) {
+                    if piece.kind == PieceKind::Pawn {
+                        errors.push(ValidationError::PawnOnBackRank {
+                            color: piece.color,
+                            square,
+                        });
+                    }
+                }
+            }
+        }
+
+        self.check_castling(&mut errors);
+        self.check_en_passant(&mut errors);
+
+        if self.fullmove_number == 0 {
+            errors.push(ValidationError::FullmoveZero);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /* Chaque droit de roque suppose le roi et la tour concernés sur leurs cases d'origine */
+    fn check_castling(&self, errors: &mut Vec<ValidationError>) {
+        let rights = self.castling_rights;
+        let checks = [
+            (rights.white_kingside, 'K', Color::White, File::H),
+            (rights.white_queenside, 'Q', Color::White, File::A),
+            (rights.black_kingside, 'k', Color::Black, File::H),
+            (rights.black_queenside, 'q', Color::Black, File::A),
+        ];
+        for (enabled, right, color, rook_file) in checks {
+            if !enabled {
+                continue;
+            }
+            let rank = match color {
+                Color::White => Rank::First,
+                Color::Black => Rank::Eighth,
+            };
+            let king = Square::new(File::E, rank);
+            let rook = Square::new(rook_file, rank);
+            let king_home = self.piece_at(king)
+                == Some(Piece {
+                    color,
+                    kind: PieceKind::King,
+                });
+            let rook_home = self.piece_at(rook)
+                == Some(Piece {
+                    color,
+                    kind: PieceKind::Rook,
+                });
+            if !king_home || !rook_home {
+                errors.push(ValidationError::CastlingWithoutPieces { right, king, rook });
+            }
+        }
+    }
+
+    /* La case de prise en passant doit se trouver derrière un pion adverse venant d'avancer
+     * de deux cases, sur la rangée qui correspond au camp ayant le trait */
+    fn check_en_passant(&self, errors: &mut Vec<ValidationError>) {
+        let Some(square) = self.en_passant_square() else {
+            return;
+        };
+        let (target_rank, origin_rank) = match self.active_color {
+            Color::White => (Rank::Sixth, Rank::Seventh),
+            Color::Black => (Rank::Third, Rank::Second),
+        };
+        let pawn = Piece {
+            color: self.active_color.opposite(),
+            kind: PieceKind::Pawn,
+        };
+        let consistent = square.rank() == target_rank
+            && self.piece_at(square).is_none()
+            && self
+                .piece_at(Square::new(square.file(), origin_rank))
+                .is_none()
+            && self
+                .en_passant_victim()
+                .and_then(|victim| self.piece_at(victim))
+                == Some(pawn);
+        if !consistent {
+            errors.push(ValidationError::ImpossibleEnPassant { square });
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/* Sérialisation d'une position en chaîne FEN.
+
+Ce module implémente l'opération inverse du parseur : il transforme une structure ChessPosition en chaîne FEN standard. */
+
+use crate::types::{ChessPosition, Color, File, Rank, Square, SquareOrder};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+impl ChessPosition {
+    /* Placement des pièces (1ère partie du FEN), de la 8e à la 1ère rangée */
+    pub fn placement_to_fen(&self) -> String {
+        let mut out = String::new();
+        let mut empty = 0;
+        for square in Square::iter(SquareOrder::Fen) {
+            match self.piece_at(square) {
+                Some(piece) => {
+                    if empty > 0 {
+                        out.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    out.push(piece.to_fen_char());
+                }
+                None => empty += 1,
+            }
+            if square.file() == File::H {
+                if empty > 0 {
+                    out.push_str(&empty.to_string());
+                    empty = 0;
+                }
+                if square.rank() != Rank::First {
+                    out.push('/');
+                }
+            }
+        }
+        out
+    }
+
+    /* Forme compacte sur une ligne : une case par caractère ('.' si vide), rangées dans l'ordre FEN, puis les autres champs */
+    pub fn to_compact_string(&self) -> String {
+        let board = self.to_flat_board();
+        let ranks: Vec<&str> = (0..8).map(|rank| &board[rank * 8..rank * 8 + 8]).collect();
+        let fen = self.to_fen();
+        let fields = fen.split_once(' ').map_or("", |(_, rest)| rest);
+        format!("{} {}", ranks.join("/"), fields)
+    }
+
+    /* Plateau sur 64 caractères, de a8 à h8 puis de a7 à h1 (indice 0 = a8, 63 = h1) : lettre
+     * FEN de la pièce, `.` pour une case vide */
+    pub fn to_flat_board(&self) -> String {
+        Square::iter(SquareOrder::Fen)
+            .map(|square| {
+                self.piece_at(square)
+                    .map_or('.', |piece| piece.to_fen_char())
+            })
+            .collect()
+    }
+
+    /* Chaîne FEN complète de la position */
+    pub fn to_fen(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            self.placement_to_fen(),
+            match self.active_color {
+                Color::White => 'w',
+                Color::Black => 'b',
+            },
+            self.castling_rights.to_fen(),
+            match self.en_passant_square() {
+                Some(square) => square.to_string(),
+                None => "-".to_string(),
+            },
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+}
+
+/* Affiche la position sous forme de chaîne FEN ; avec `{:#}`, le plateau de `render_board` au
+ * style par défaut (la FEN sans la fonctionnalité `std`). Largeur, remplissage et alignement
+ * s'appliquent à la FEN, ou à chaque ligne du plateau */
+impl fmt::Display for ChessPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "std")]
+        if f.alternate() {
+            let board = self.render_board(&crate::display::DisplayOptions::default());
+            for line in board.lines() {
+                f.pad(line)?;
+                f.write_str("\n")?;
+            }
+            return Ok(());
+        }
+        f.pad(&self.to_fen())
+    }
+}
+
+/* Debug lisible dans les journaux : la forme compacte sur une ligne */
+impl fmt::Debug for ChessPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ChessPosition")
+            .field(&self.to_compact_string())
+            .finish()
+    }
+}
@@ -0,0 +1,67 @@
+/* Clé de répétition des positions (hachage de Zobrist).
+
+Deux positions ont la même clé quand elles ne diffèrent que par leurs compteurs de coups : placement, trait, droits de roque et case de prise en passant y entrent seuls, comme pour la règle de la triple répétition. Les constantes sont tirées une fois pour toutes d'un SplitMix64 de graine fixe, si bien que les clés restent les mêmes d'une exécution et d'une version à l'autre. */
+
+use crate::types::{ChessPosition, Color, PieceKind};
+
+/* Entrées de la table : 12 pièces x 64 cases, le trait, 4 droits de roque, 8 colonnes */
+const PIECE_SQUARE: usize = 0;
+const BLACK_TO_MOVE: usize = 12 * 64;
+const CASTLING: usize = BLACK_TO_MOVE + 1;
+const EN_PASSANT_FILE: usize = CASTLING + 4;
+const TABLE_SIZE: usize = EN_PASSANT_FILE + 8;
+
+const TABLE: [u64; TABLE_SIZE] = {
+    let mut table = [0; TABLE_SIZE];
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut index = 0;
+    while index < TABLE_SIZE {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[index] = z ^ (z >> 31);
+        index += 1;
+    }
+    table
+};
+
+impl ChessPosition {
+    /* Clé de répétition de la position, indépendante des compteurs de coups */
+    pub fn repetition_key(&self) -> u64 {
+        let mut key = 0;
+        for (rank, row) in self.pieces.iter().enumerate() {
+            for (file, piece) in row.iter().enumerate() {
+                let Some(piece) = piece else {
+                    continue;
+                };
+                let kind = PieceKind::ALL
+                    .iter()
+                    .position(|&kind| kind == piece.kind)
+                    .unwrap_or(0);
+                let color = match piece.color {
+                    Color::White => 0,
+                    Color::Black => 6,
+                };
+                key ^= TABLE[PIECE_SQUARE + (color + kind) * 64 + rank * 8 + file];
+            }
+        }
+        if self.active_color == Color::Black {
+            key ^= TABLE[BLACK_TO_MOVE];
+        }
+        let rights = self.castling_rights;
+        let flags = [
+            rights.white_kingside,
+            rights.white_queenside,
+            rights.black_kingside,
+            rights.black_queenside,
+        ];
+        for (index, _) in flags.iter().enumerate().filter(|(_, &set)| set) {
+            key ^= TABLE[CASTLING + index];
+        }
+        if let Some(square) = self.en_passant_square() {
+            key ^= TABLE[EN_PASSANT_FILE + square.file().index()];
+        }
+        key
+    }
+}
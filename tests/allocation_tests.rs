@@ -0,0 +1,64 @@
+/* Tests des allocations du parseur.
+
+Un allocateur global compte les allocations du fil d'exécution courant, ce qui permet de vérifier qu'une FEN valide se lit sans toucher au tas. */
+
+use fen_parser::{parse_fen, parse_fen_nom};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/* Nombre d'allocations faites par `f` sur ce fil */
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/* Test que la lecture des droits de roque, et plus largement d'une FEN valide, n'alloue rien */
+#[test]
+fn test_valid_fen_parses_without_allocating() {
+    for castling in ["-", "K", "kq", "KQkq", "qkQK"] {
+        let fen = format!("r3k2r/8/8/8/8/8/8/R3K2R w {} - 0 1", castling);
+        let count = allocations(|| {
+            parse_fen(&fen).unwrap();
+        });
+        assert_eq!(count, 0, "{}", fen);
+    }
+}
+
+/* Test que le parseur nom lit lui aussi le placement dans des tableaux fixes, sans allocation,
+ * y compris pour les formes non canoniques que la voie rapide lui laisse */
+#[test]
+fn test_nom_parser_placement_does_not_allocate() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/8/8/8/8/8/8/8\tb\t-\te3\t12\t40",
+        "8/8/8/8/8/8/8/8 w - - 0 1 trailing",
+    ] {
+        let count = allocations(|| {
+            parse_fen_nom(fen).unwrap();
+        });
+        assert_eq!(count, 0, "{}", fen);
+        assert_eq!(parse_fen(fen), parse_fen_nom(fen));
+    }
+}
@@ -0,0 +1,525 @@
+/* Tests de l'analyse statique des positions.
+
+Ces tests vérifient le décompte du matériel et le bilan en centipions, les signatures matérielles, la clé de répétition, la structure de pions (pions doublés, isolés, passés et arriérés), l'état des colonnes, les fous, la phase de la partie, l'abri du roi et les indices d'inaccessibilité depuis la position initiale. */
+
+use fen_parser::analysis::{
+    backward_pawns, bishops_by_square_color, doubled_pawn_count, doubled_pawns, file_state,
+    game_phase, game_phase_with, has_bishop_pair, has_opposite_colored_bishops, is_isolated,
+    is_opposite_colored_bishops, isolated_pawns, king_file_openness, material_balance,
+    material_balance_simple, open_files, passed_pawns, pawn_attacks, pawn_shield,
+    pawn_shield_ranks, phase_value, reachability_warnings, semi_open_files, FileState, Phase,
+    PhaseThresholds, PieceValues,
+};
+use fen_parser::{
+    parse_fen, Color, File, Piece, PieceKind, ReachabilityWarning, Square, SquareSet,
+};
+
+fn square(name: &str) -> Square {
+    Square::from_name(name).unwrap()
+}
+
+/* Test le décompte du matériel de la position initiale */
+#[test]
+fn test_material_start_position() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let material = position.material();
+    assert_eq!(material.count(Color::White, PieceKind::Pawn), 8);
+    assert_eq!(material.count(Color::Black, PieceKind::Knight), 2);
+    assert_eq!(material.count(Color::Black, PieceKind::King), 1);
+    assert_eq!(material.value(Color::White), 39);
+    assert_eq!(material.imbalance(), 0);
+}
+
+/* Test le signe du déséquilibre matériel */
+#[test]
+fn test_material_imbalance_sign() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    assert_eq!(position.material().imbalance(), 5);
+    let position = parse_fen("3qk3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(position.material().imbalance(), -9);
+}
+
+/* Test la signature matérielle et le nombre total de pièces */
+#[test]
+fn test_material_signature() {
+    let position = parse_fen("4k3/2r5/8/8/8/8/PP6/R3K3 w - - 0 1").unwrap();
+    assert_eq!(position.material().signature(), "KRPPvKR");
+    assert_eq!(position.material().total(), 6);
+}
+
+/* Test que la clé de répétition ignore les compteurs mais pas le trait ni les roques */
+#[test]
+fn test_repetition_key() {
+    let start = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let later = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 8 5").unwrap();
+    let black = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    let no_castling = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w Kkq - 0 1").unwrap();
+    assert_eq!(start.repetition_key(), later.repetition_key());
+    assert_ne!(start.repetition_key(), black.repetition_key());
+    assert_ne!(start.repetition_key(), no_castling.repetition_key());
+}
+
+/* Test l'absence de pions doublés dans la position initiale */
+#[test]
+fn test_doubled_pawns_clean_structure() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert!(doubled_pawns(&position, Color::White).is_empty());
+    assert_eq!(doubled_pawn_count(&position, Color::Black), 0);
+}
+
+/* Test les pions f doublés après la reprise gxf6 */
+#[test]
+fn test_doubled_f_pawns() {
+    let position =
+        parse_fen("rnbqkb1r/pppp1p1p/5p2/4p3/4P3/8/PPPP1PPP/RNBQKB1R w KQkq - 0 4").unwrap();
+    assert_eq!(doubled_pawns(&position, Color::Black), [(File::F, 2)]);
+    assert_eq!(doubled_pawn_count(&position, Color::Black), 1);
+    assert!(doubled_pawns(&position, Color::White).is_empty());
+}
+
+/* Test le décompte des pions triplés */
+#[test]
+fn test_tripled_pawns() {
+    let position = parse_fen("4k3/8/8/2P5/2P5/2P1P3/4P3/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        doubled_pawns(&position, Color::White),
+        [(File::C, 3), (File::E, 2)]
+    );
+    assert_eq!(doubled_pawn_count(&position, Color::White), 3);
+}
+
+/* Test le pion isolé de la dame, dans les deux camps */
+#[test]
+fn test_isolated_queen_pawn() {
+    let position =
+        parse_fen("r1bq1rk1/pp2bppp/2n2n2/3p4/3P4/2NB1N2/PP3PPP/R1BQ1RK1 w - - 0 10").unwrap();
+    let d4 = square("d4");
+    let d5 = square("d5");
+    assert_eq!(
+        isolated_pawns(&position, Color::White),
+        SquareSet::from_iter([d4])
+    );
+    assert_eq!(
+        isolated_pawns(&position, Color::Black),
+        SquareSet::from_iter([d5])
+    );
+    assert!(is_isolated(&position, d4));
+    assert!(!is_isolated(&position, square("f2")));
+    assert!(!is_isolated(&position, square("d3")));
+}
+
+/* Test un pion isolé sur la colonne a, qui n'a qu'une colonne voisine */
+#[test]
+fn test_isolated_edge_pawn() {
+    let position = parse_fen("4k3/8/8/8/8/P7/2P4P/4K3 w - - 0 1").unwrap();
+    assert_eq!(isolated_pawns(&position, Color::White).len(), 3);
+
+    let position = parse_fen("4k3/8/8/8/8/P7/1P5P/4K3 w - - 0 1").unwrap();
+    let h2 = square("h2");
+    assert_eq!(
+        isolated_pawns(&position, Color::White),
+        SquareSet::from_iter([h2])
+    );
+}
+
+/* Test une chaîne de pions liés, sans pion isolé */
+#[test]
+fn test_connected_chain_has_no_isolated_pawn() {
+    let position = parse_fen("4k3/8/8/4P3/3P4/2P5/1P6/4K3 w - - 0 1").unwrap();
+    assert!(isolated_pawns(&position, Color::White).is_empty());
+}
+
+/* Test un pion passé protégé, face à un pion noir qui bloque le pion protecteur */
+#[test]
+fn test_protected_passed_pawn() {
+    let position = parse_fen("4k3/5p2/8/3P4/4P3/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        passed_pawns(&position, Color::White),
+        SquareSet::from_iter([square("d5")])
+    );
+    assert!(passed_pawns(&position, Color::Black).is_empty());
+}
+
+/* Test un pion qui n'est pas encore passé, un pion adverse l'attendant sur la colonne
+ * voisine ; un pion adverse déjà dépassé ne compte pas */
+#[test]
+fn test_candidate_is_not_passed() {
+    let position = parse_fen("4k3/8/3p4/8/2P5/8/8/4K3 w - - 0 1").unwrap();
+    assert!(passed_pawns(&position, Color::White).is_empty());
+    assert!(passed_pawns(&position, Color::Black).is_empty());
+
+    let position = parse_fen("4k3/8/8/8/2P5/1p6/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        passed_pawns(&position, Color::White),
+        SquareSet::from_iter([square("c4")])
+    );
+    assert_eq!(
+        passed_pawns(&position, Color::Black),
+        SquareSet::from_iter([square("b3")])
+    );
+}
+
+/* Test les pions proches de la promotion : un pion adverse à côté n'est pas devant */
+#[test]
+fn test_passed_pawns_near_promotion() {
+    let position = parse_fen("4k3/Pp6/8/8/8/8/pP6/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        passed_pawns(&position, Color::White),
+        SquareSet::from_iter([square("a7")])
+    );
+    assert_eq!(
+        passed_pawns(&position, Color::Black),
+        SquareSet::from_iter([square("a2")])
+    );
+}
+
+/* Test les cases attaquées par les pions, sans débordement d'une colonne à l'autre */
+#[test]
+fn test_pawn_attacks() {
+    let position = parse_fen("4k3/7p/8/8/8/8/P7/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        pawn_attacks(&position, Color::White),
+        SquareSet::from_iter([square("b3")])
+    );
+    assert_eq!(
+        pawn_attacks(&position, Color::Black),
+        SquareSet::from_iter([square("g6")])
+    );
+}
+
+/* Test le pion d6 arriéré de la structure Najdorf après ...e5, sur une colonne semi-ouverte */
+#[test]
+fn test_sicilian_backward_d6_pawn() {
+    let position =
+        parse_fen("r1bqkb1r/1p3ppp/p1np1n2/4p3/4P3/1NN5/PPP2PPP/R1BQKB1R w KQkq - 0 7").unwrap();
+    assert_eq!(
+        backward_pawns(&position, Color::Black),
+        SquareSet::from_iter([square("d6")])
+    );
+    assert!(backward_pawns(&position, Color::White).is_empty());
+}
+
+/* Test une chaîne soutenue et un pion isolé, qui ne sont pas arriérés, puis un pion resté
+ * derrière son voisin */
+#[test]
+fn test_supported_chain_is_not_backward() {
+    let position = parse_fen("4k3/8/2p5/8/3P4/2P5/8/4K3 w - - 0 1").unwrap();
+    assert!(backward_pawns(&position, Color::White).is_empty());
+
+    let position = parse_fen("4k3/8/4p3/8/3P4/8/8/4K3 w - - 0 1").unwrap();
+    assert!(backward_pawns(&position, Color::White).is_empty());
+
+    let position = parse_fen("4k3/8/8/4p3/2P5/3P4/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        backward_pawns(&position, Color::White),
+        SquareSet::from_iter([square("d3")])
+    );
+}
+
+/* Test les trois états de colonne sur une position construite */
+#[test]
+fn test_file_states() {
+    let position = parse_fen("4k3/pp3p2/8/8/8/8/P1P2P2/4K3 w - - 0 1").unwrap();
+    assert_eq!(file_state(&position, File::A), FileState::Closed);
+    assert_eq!(
+        file_state(&position, File::B),
+        FileState::SemiOpenFor(Color::White)
+    );
+    assert_eq!(
+        file_state(&position, File::C),
+        FileState::SemiOpenFor(Color::Black)
+    );
+    assert_eq!(file_state(&position, File::D), FileState::Open);
+    assert_eq!(open_files(&position), [File::D, File::E, File::G, File::H]);
+    assert_eq!(semi_open_files(&position, Color::White), [File::B]);
+    assert_eq!(semi_open_files(&position, Color::Black), [File::C]);
+}
+
+/* Test la position initiale, où toutes les colonnes sont fermées */
+#[test]
+fn test_start_position_files_closed() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert!(File::ALL
+        .into_iter()
+        .all(|file| file_state(&position, file) == FileState::Closed));
+    assert!(open_files(&position).is_empty());
+    assert!(semi_open_files(&position, Color::White).is_empty());
+}
+
+/* Test la paire de fous de la position initiale */
+#[test]
+fn test_bishop_pair() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(bishops_by_square_color(&position, Color::White), (1, 1));
+    assert!(has_bishop_pair(&position, Color::White));
+    assert!(has_bishop_pair(&position, Color::Black));
+}
+
+/* Test deux fous de même couleur après une promotion, qui ne forment pas la paire */
+#[test]
+fn test_same_colored_bishops_are_not_a_pair() {
+    let position = parse_fen("4k3/8/8/8/8/8/4B3/4KB2 w - - 0 1").unwrap();
+    assert_eq!(bishops_by_square_color(&position, Color::White), (2, 0));
+    assert!(!has_bishop_pair(&position, Color::White));
+}
+
+/* Test un fou seul */
+#[test]
+fn test_single_bishop() {
+    let position = parse_fen("2b1k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(bishops_by_square_color(&position, Color::Black), (1, 0));
+    assert!(!has_bishop_pair(&position, Color::Black));
+    assert_eq!(bishops_by_square_color(&position, Color::White), (0, 0));
+}
+
+/* Test le bilan matériel nul de la position initiale */
+#[test]
+fn test_material_balance_start_position() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(material_balance_simple(&position), 0);
+}
+
+/* Test une tour d'avance pour chaque camp */
+#[test]
+fn test_material_balance_rook_up() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    assert_eq!(material_balance_simple(&position), 500);
+    let position = parse_fen("r3k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(material_balance_simple(&position), -500);
+}
+
+/* Test un barème personnalisé */
+#[test]
+fn test_material_balance_custom_values() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1").unwrap();
+    assert_eq!(material_balance_simple(&position), 650);
+    let values = PieceValues {
+        knight: 300,
+        bishop: 300,
+        ..PieceValues::default()
+    };
+    assert_eq!(material_balance(&position, &values), 600);
+}
+
+/* Test la phase de la position initiale */
+#[test]
+fn test_phase_start_position() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(phase_value(&position), 1.0);
+    assert_eq!(game_phase(&position), Phase::Opening);
+}
+
+/* Test la phase d'une position réduite aux rois */
+#[test]
+fn test_phase_bare_kings() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(phase_value(&position), 0.0);
+    assert_eq!(game_phase(&position), Phase::Endgame);
+}
+
+/* Test un milieu de partie sans dames, puis des seuils personnalisés */
+#[test]
+fn test_phase_queenless_middlegame() {
+    let position =
+        parse_fen("r1b1kb1r/pp3ppp/2n2n2/2pp4/3P4/2N2N2/PP3PPP/R1B1KB1R w KQkq - 0 8").unwrap();
+    let value = phase_value(&position);
+    assert!((value - 16.0 / 24.0).abs() < 1e-6);
+    assert_eq!(game_phase(&position), Phase::Middlegame);
+
+    let thresholds = PhaseThresholds {
+        opening: 0.5,
+        ..PhaseThresholds::default()
+    };
+    assert_eq!(game_phase_with(&position, &thresholds), Phase::Opening);
+}
+
+/* Test un roi roqué derrière f2, g2 et h2 */
+#[test]
+fn test_pawn_shield_castled_king() {
+    let position = parse_fen("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1").unwrap();
+    assert_eq!(pawn_shield(&position, Color::White), 3);
+    assert_eq!(pawn_shield_ranks(&position, Color::White), (3, 0));
+    assert_eq!(pawn_shield(&position, Color::Black), 3);
+    assert!(king_file_openness(&position, Color::White).is_empty());
+}
+
+/* Test le même roi après g3 : toujours trois pions, dont un à deux rangées */
+#[test]
+fn test_pawn_shield_advanced_g_pawn() {
+    let position = parse_fen("6k1/5ppp/8/8/8/6P1/5P1P/6K1 w - - 0 1").unwrap();
+    assert_eq!(pawn_shield(&position, Color::White), 3);
+    assert_eq!(pawn_shield_ranks(&position, Color::White), (2, 1));
+}
+
+/* Test un roi sans pions, entouré de colonnes ouvertes ou semi-ouvertes */
+#[test]
+fn test_pawn_shield_pawnless_king() {
+    let position = parse_fen("6k1/6p1/8/8/8/8/8/6K1 w - - 0 1").unwrap();
+    assert_eq!(pawn_shield(&position, Color::White), 0);
+    assert_eq!(
+        king_file_openness(&position, Color::White),
+        [
+            (File::F, FileState::Open),
+            (File::G, FileState::SemiOpenFor(Color::White)),
+            (File::H, FileState::Open),
+        ]
+    );
+
+    let position = parse_fen("k7/8/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    assert_eq!(
+        king_file_openness(&position, Color::Black),
+        [(File::A, FileState::Open), (File::B, FileState::Open)]
+    );
+}
+
+/* Test une finale de pions avec fous de couleurs opposées */
+#[test]
+fn test_opposite_colored_bishops_endgame() {
+    let position = parse_fen("4k3/pp3b2/8/8/8/8/PP6/2B1K3 w - - 0 1").unwrap();
+    assert!(is_opposite_colored_bishops(&position));
+    assert!(has_opposite_colored_bishops(&position));
+}
+
+/* Test des fous de même couleur */
+#[test]
+fn test_same_colored_bishops_endgame() {
+    let position = parse_fen("4k3/pp6/8/8/3b4/8/PP6/2B1K3 w - - 0 1").unwrap();
+    assert!(!is_opposite_colored_bishops(&position));
+    assert!(!has_opposite_colored_bishops(&position));
+}
+
+/* Test des fous de couleurs opposées avec des tours, refusés par la forme stricte seulement */
+#[test]
+fn test_opposite_colored_bishops_with_rooks() {
+    let position = parse_fen("r3k3/pp3b2/8/8/8/8/PP6/2B1K2R w - - 0 1").unwrap();
+    assert!(!is_opposite_colored_bishops(&position));
+    assert!(has_opposite_colored_bishops(&position));
+}
+
+/* Test la signature ordonnée, le camp le plus fort en premier */
+#[test]
+fn test_material_signature_stronger_side_first() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+    assert_eq!(position.material_signature(), "KQvK");
+
+    let position = parse_fen("3qk3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    assert_eq!(position.material_signature(), "KQvKR");
+    assert_eq!(position.material().signature(), "KRvKQ");
+
+    let position = parse_fen("4k3/5p2/4r3/8/8/8/5PP1/4RK2 w - - 0 1").unwrap();
+    assert_eq!(position.material_signature(), "KRPPvKRP");
+
+    let position = parse_fen("2r1kr2/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(position.material_signature(), "KRRvK");
+}
+
+/* Test la comparaison à une signature, dans les deux sens et avec des pions quelconques */
+#[test]
+fn test_matches_signature() {
+    let position = parse_fen("4k3/5p2/4r3/8/8/8/5PP1/4RK2 w - - 0 1").unwrap();
+    assert!(position.matches_signature("KRPPvKRP"));
+    assert!(position.matches_signature("KRPvKRPP"));
+    assert!(!position.matches_signature("KRPvKRP"));
+    assert!(position.matches_signature("KR*vKR*"));
+    assert!(position.matches_signature("KRP*vKRP"));
+    assert!(!position.matches_signature("KRPPP*vKR*"));
+    assert!(!position.matches_signature("KR*vKQ*"));
+    for malformed in ["", "KRPP", "RvK", "KXvK", "KvKvK"] {
+        assert!(!position.matches_signature(malformed), "{malformed}");
+    }
+}
+
+/* Test la clé de table de finales, identique pour les deux répartitions des couleurs */
+#[test]
+fn test_tablebase_key_is_canonical() {
+    for (fen, key) in [
+        ("4k3/8/8/8/8/8/8/2n1K2R w - - 0 1", "KRvKN"),
+        ("2N1k2r/8/8/8/8/8/8/4K3 w - - 0 1", "KRvKN"),
+        ("4k3/8/8/8/8/8/8/2BNK3 w - - 0 1", "KBNvK"),
+        ("2bnk3/8/8/8/8/8/8/4K3 w - - 0 1", "KBNvK"),
+        ("4k3/3p4/8/8/8/8/8/3QK3 w - - 0 1", "KQvKP"),
+        ("bb2k3/8/8/8/8/8/8/3QK3 w - - 0 1", "KBBvKQ"),
+    ] {
+        let position = parse_fen(fen).unwrap();
+        assert_eq!(position.tablebase_key().as_deref(), Some(key), "{fen}");
+    }
+}
+
+/* Test la limite de pièces : sept pièces passent, huit non */
+#[test]
+fn test_tablebase_key_piece_limit() {
+    let seven = parse_fen("4k3/ppp5/8/8/8/8/PP6/4K3 w - - 0 1").unwrap();
+    assert_eq!(seven.piece_count(), 7);
+    assert_eq!(seven.tablebase_key().as_deref(), Some("KPPPvKPP"));
+
+    let eight = parse_fen("4k3/ppp5/8/8/8/8/PPP5/4K3 w - - 0 1").unwrap();
+    assert_eq!(eight.piece_count(), 8);
+    assert_eq!(eight.tablebase_key(), None);
+    assert_eq!(
+        eight.tablebase_key_with_limit(8).as_deref(),
+        Some("KPPPvKPPP")
+    );
+}
+
+/* Test les indices d'inaccessibilité : positions atteignables sans indice */
+#[test]
+fn test_reachable_positions_have_no_warning() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqk1nr/ppp2ppp/8/4P3/1BP5/8/PP2K1PP/RN1Q1BnR w kq - 0 8",
+        /* Cavalier noir entré dans l'aile close par f3 */
+        "rnbqkb1r/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBnR w KQkq - 0 1",
+        /* Dame passée en c1 après la prise d'un cavalier entré */
+        "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1Q1KBNR w KQkq - 0 1",
+        /* Fou en e5 promu : un pion blanc manque */
+        "rnbqkbnr/pppppppp/8/4B3/8/8/PPPPPPP1/RN1QKBNR w KQkq - 0 1",
+        /* Pions doublés, le pion h noir a été pris */
+        "rnbqkbnr/ppppppp1/8/8/8/P7/P7/RNBQKBNR w kq - 0 1",
+    ] {
+        let position = parse_fen(fen).unwrap();
+        assert_eq!(reachability_warnings(&position), [], "{fen}");
+    }
+}
+
+/* Test chaque règle sur une position qui la déclenche seule */
+#[test]
+fn test_reachability_rules() {
+    let trapped = parse_fen("rnbqkbnr/pppppppp/8/4B3/8/8/PPPPPPPP/RN1QKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(
+        reachability_warnings(&trapped),
+        [ReachabilityWarning::TrappedBishop {
+            color: Color::White,
+            home: square("c1"),
+            square: square("e5"),
+        }]
+    );
+
+    let captures = parse_fen("rnbqkbnr/pppppppp/8/8/8/P7/P7/RNBQKBNR w kq - 0 1").unwrap();
+    assert_eq!(
+        reachability_warnings(&captures),
+        [ReachabilityWarning::PawnCaptures {
+            color: Color::White,
+            required: 1,
+            available: 0,
+        }]
+    );
+
+    let sealed = parse_fen("rnbqkbn1/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBrR w KQq - 0 1").unwrap();
+    let warnings = reachability_warnings(&sealed);
+    assert_eq!(
+        warnings,
+        [ReachabilityWarning::SealedCorner {
+            right: 'K',
+            square: square("g1"),
+            piece: Piece {
+                color: Color::Black,
+                kind: PieceKind::Rook,
+            },
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "castling right 'K' keeps the kingside corner closed, yet a black rook stands on g1"
+    );
+}
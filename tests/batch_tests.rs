@@ -0,0 +1,49 @@
+/* Tests du traitement par lots.
+
+Ces tests vérifient la lecture ligne à ligne d'un flux de FEN et le bilan affiché. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::batch::entries;
+use fen_parser::{BatchEntry, BatchSummary};
+
+/* Test que les lignes vides et les commentaires sont ignorés sans décaler la numérotation */
+#[test]
+fn test_entries_skip_blank_and_comment_lines() {
+    let input = "# en-tête\n\nfen one\n   \n  # note\n  fen two  \n";
+    let read: Vec<BatchEntry> = entries(input.as_bytes()).map(Result::unwrap).collect();
+
+    assert_eq!(
+        read,
+        [
+            BatchEntry {
+                line: 3,
+                fen: "fen one".into()
+            },
+            BatchEntry {
+                line: 6,
+                fen: "fen two".into()
+            },
+        ]
+    );
+}
+
+/* Test le format du bilan avec séparateur de milliers */
+#[test]
+fn test_summary_display() {
+    let summary = BatchSummary {
+        ok: 1204,
+        failed: 3,
+    };
+    assert_eq!(summary.to_string(), "1,204 ok, 3 failed");
+    assert!(!summary.all_ok());
+    assert_eq!(BatchSummary::default().to_string(), "0 ok, 0 failed");
+    assert_eq!(
+        BatchSummary {
+            ok: 1_000_000,
+            failed: 0
+        }
+        .to_string(),
+        "1,000,000 ok, 0 failed"
+    );
+}
@@ -0,0 +1,163 @@
+/* Tests des masques d'occupation.
+
+Ces tests vérifient que les masques tenus à jour coup après coup restent identiques à ceux d'un nouveau parcours du tableau, et que la détection des attaques par masques rend la même réponse que celle du tableau. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{
+    parse_fen, Bitboards, ByColor, ByPieceKind, CastlingRights, ChessPosition, Color, Piece,
+    PieceKind, PositionError, RandomPositionGenerator, Square, SquareSet,
+};
+
+/* Compare les masques à une lecture case par case de la position */
+fn assert_matches_array(boards: &Bitboards, position: &ChessPosition) {
+    assert_eq!(*boards, position.bitboards(), "{}", position.to_fen());
+    for square in SquareSet::full() {
+        let piece = position.piece_at(square);
+        for color in [Color::White, Color::Black] {
+            assert_eq!(
+                boards.occupancy(color).contains(square),
+                piece.is_some_and(|piece| piece.color == color)
+            );
+            for kind in PieceKind::ALL {
+                assert_eq!(
+                    boards.bitboard(color, kind).contains(square),
+                    piece == Some(Piece { color, kind })
+                );
+            }
+            assert_eq!(
+                boards.is_attacked(square, color),
+                position.is_attacked(square, color),
+                "{} attacked by {:?} in {}",
+                square,
+                color,
+                position.to_fen()
+            );
+        }
+    }
+    assert_eq!(boards.occupied(), position.occupied());
+}
+
+/* Test des masques tenus à jour au fil de parties aléatoires, roques, prises en passant et
+ * promotions comprises */
+#[test]
+fn test_tracked_bitboards_follow_moves() {
+    let mut generator = RandomPositionGenerator::new(426);
+    let starts = [
+        ChessPosition::default(),
+        ChessPosition::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap(),
+        ChessPosition::from_fen("8/2P5/8/K2pP2r/8/8/5kp1/8 w - d6 0 1").unwrap(),
+    ];
+    let mut choice = 0usize;
+    for start in starts
+        .into_iter()
+        .chain((0..40).map(|_| generator.random_plausible()))
+    {
+        let mut position = start;
+        let mut boards = position.bitboards();
+        for _ in 0..60 {
+            let moves = position.legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            choice = (choice * 31 + 7) % 1_000_003;
+            let mv = moves[choice % moves.len()];
+            position.make_move_tracked(mv, &mut boards).unwrap();
+            assert_matches_array(&boards, &position);
+        }
+    }
+}
+
+/* Test qu'un coup refusé laisse masques et position intacts, et que des écritures directes dans
+ * le tableau se retrouvent dans les masques calculés ensuite */
+#[test]
+fn test_bitboards_after_rejected_moves_and_direct_writes() {
+    let mut position = ChessPosition::default();
+    let mut boards = position.bitboards();
+    let illegal = fen_parser::Move::from_uci("e2e5").unwrap();
+    assert!(position.make_move_tracked(illegal, &mut boards).is_err());
+    assert_eq!(boards, ChessPosition::default().bitboards());
+
+    let e4 = Square::from_name("e4").unwrap();
+    position.pieces[e4.rank().index()][e4.file().index()] = Some(Piece {
+        color: Color::Black,
+        kind: PieceKind::Queen,
+    });
+    position.pieces[0][4] = None;
+    let boards = position.bitboards();
+    assert_matches_array(&boards, &position);
+    assert!(boards.bitboard(Color::Black, PieceKind::Queen).contains(e4));
+    assert!(boards.bitboard(Color::White, PieceKind::King).is_empty());
+    assert_eq!(position.occupancy(Color::Black).len(), 17);
+    assert_eq!(position.bitboard(Color::White, PieceKind::Pawn).len(), 8);
+}
+
+/* Test l'aller-retour `to_bitboards` / `from_bitboards` et la numérotation des bits */
+#[test]
+fn test_from_bitboards_round_trip() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 17 42",
+    ] {
+        let position = parse_fen(fen).unwrap();
+        let bitboards = position.to_bitboards();
+        let rebuilt = ChessPosition::from_bitboards(
+            &bitboards,
+            position.active_color,
+            position.castling_rights,
+            position.en_passant_square(),
+            (position.halfmove_clock, position.fullmove_number),
+        )
+        .unwrap();
+        assert_eq!(rebuilt.to_fen(), fen);
+    }
+
+    /* Bit 0 = a1, bit 63 = h8 */
+    let start = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let bitboards = start.to_bitboards();
+    assert_eq!(bitboards.white.rook, 0x81);
+    assert_eq!(bitboards.white.pawn, 0xFF00);
+    assert_eq!(bitboards.black.rook, 0x8100_0000_0000_0000);
+    assert_eq!(*bitboards.get(Color::Black).get(PieceKind::King), 1 << 60);
+}
+
+/* Test qu'une case occupée par deux pièces est refusée, avec la case et les deux pièces */
+#[test]
+fn test_from_bitboards_rejects_overlap() {
+    let mut bitboards = ByColor::<ByPieceKind<u64>>::default();
+    bitboards.white.king = 1 << 4;
+    bitboards.black.king = 1 << 60;
+    bitboards.white.rook = 1 << 28;
+    bitboards.black.pawn = (1 << 28) | (1 << 52);
+    let error = ChessPosition::from_bitboards(
+        &bitboards,
+        Color::White,
+        CastlingRights::none(),
+        None,
+        (0, 1),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        PositionError::Overlap {
+            square: Square::from_index(28).unwrap(),
+            first: Piece {
+                color: Color::White,
+                kind: PieceKind::Rook
+            },
+            second: Piece {
+                color: Color::Black,
+                kind: PieceKind::Pawn
+            },
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "square e4 is claimed by both a white rook and a black pawn"
+    );
+}
@@ -0,0 +1,78 @@
+/* Tests du cache des positions lues.
+
+Ces tests vérifient l'ordre d'éviction, les compteurs de succès et d'échecs, l'absence des erreurs dans le cache et le partage du cache entre plusieurs fils. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, FenCache};
+use std::sync::Arc;
+
+const FENS: [&str; 4] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+    "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2",
+];
+
+/* Test l'éviction de la position la moins récemment lue et les compteurs de succès et d'échecs */
+#[test]
+fn test_cache_evicts_least_recently_used() {
+    let cache = FenCache::new(3);
+    for fen in &FENS[..3] {
+        cache.get_or_parse(fen).unwrap();
+    }
+    /* La première FEN redevient la plus récente : la deuxième part à sa place */
+    let first = cache.get_or_parse(FENS[0]).unwrap();
+    assert!(Arc::ptr_eq(&first, &cache.get_or_parse(FENS[0]).unwrap()));
+    cache.get_or_parse(FENS[3]).unwrap();
+
+    assert_eq!(cache.len(), 3);
+    assert!(cache.contains(FENS[0]));
+    assert!(!cache.contains(FENS[1]));
+    assert!(cache.contains(FENS[2]));
+    assert!(cache.contains(FENS[3]));
+    assert_eq!((cache.hits(), cache.misses()), (2, 4));
+    assert_eq!(
+        *cache.get_or_parse(FENS[3]).unwrap(),
+        parse_fen(FENS[3]).unwrap()
+    );
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.hits(), 3);
+}
+
+/* Test qu'une FEN invalide n'est pas gardée et qu'un cache de capacité nulle reste vide */
+#[test]
+fn test_cache_does_not_keep_errors() {
+    let cache = FenCache::new(8);
+    for _ in 0..2 {
+        assert!(cache.get_or_parse("8/8/8 w - - 0 1").is_err());
+    }
+    assert!(cache.is_empty());
+    assert_eq!((cache.hits(), cache.misses()), (0, 2));
+
+    let disabled = FenCache::new(0);
+    disabled.get_or_parse(FENS[0]).unwrap();
+    assert!(disabled.is_empty());
+}
+
+/* Test le cache partagé entre quatre fils : chaque lecture est comptée, la capacité respectée */
+#[test]
+fn test_cache_shared_between_threads() {
+    let cache = FenCache::new(2);
+    std::thread::scope(|scope| {
+        for thread in 0..4 {
+            let cache = &cache;
+            scope.spawn(move || {
+                for round in 0..500 {
+                    let fen = FENS[(thread + round) % FENS.len()];
+                    let position = cache.get_or_parse(fen).unwrap();
+                    assert_eq!(position.to_fen(), fen);
+                }
+            });
+        }
+    });
+    assert_eq!(cache.hits() + cache.misses(), 2000);
+    assert!(cache.len() <= 2);
+}
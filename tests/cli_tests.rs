@@ -0,0 +1,738 @@
+/* Tests d'intégration du programme en ligne de commande.
+
+Ces tests lancent le binaire compilé et vérifient sa sortie et son code de retour. */
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::Json;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/* Lance le binaire avec les arguments donnés */
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_fen_parser"))
+        .args(args)
+        .output()
+        .expect("failed to run fen_parser")
+}
+
+/* Lance le binaire en écrivant `input` sur son entrée standard */
+fn run_with_stdin(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_fen_parser"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run fen_parser");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/* Test que la sortie redirigée (hors terminal) reste en texte brut */
+#[test]
+fn test_piped_output_is_plain() {
+    let output = run(&[START_FEN]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("8 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |"));
+    assert!(!stdout.contains('\x1b'));
+}
+
+/* Test le mode `explain` */
+#[test]
+fn test_explain_mode() {
+    let output = run(&["explain", START_FEN]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("Piece placement: rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR\n"));
+    assert!(stdout.contains("White to move"));
+    assert!(!stdout.contains(" | "));
+}
+
+/* Test la sortie JSON du programme */
+#[test]
+fn test_json_format() {
+    let output = run(&["--format=json", START_FEN]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with(&format!("{{\"fen\":\"{}\"", START_FEN)));
+    assert!(stdout.ends_with("}\n"));
+}
+
+/* Test la sortie Markdown du programme */
+#[test]
+fn test_markdown_format() {
+    let output = run(&["--format=markdown", START_FEN]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("|   | a | b | c | d | e | f | g | h |\n"));
+    assert_eq!(stdout.lines().count(), 10);
+}
+
+/* Test le mode `validate` sur l'entrée standard, avec un rapport complet par ligne */
+#[test]
+fn test_validate_mode_reports_every_error() {
+    let input = format!("{}\n8/8/8/8/8/8/8/8 w KX e9 0 1\n", START_FEN);
+    let output = run_with_stdin(&["validate", "--verbose"], &input);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.starts_with("<stdin>:1: OK\n<stdin>:2: 2 syntax errors\n"));
+    assert!(stdout.contains("  invalid castling rights 'KX'"));
+    assert!(stdout.contains("  invalid en passant square 'e9'"));
+}
+
+/* Test que les erreurs de parsing sont rendues en diagnostic sur la sortie d'erreur */
+#[cfg(feature = "diagnostics")]
+#[test]
+fn test_parse_error_diagnostic() {
+    let output = run(&["8/8/8/8/8/8/8/8 w KQkq e9 0 1"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.starts_with("error: invalid en passant square: unexpected character\n"));
+    assert!(stderr.contains("^^ expected '-' or a square on rank 3 or 6, found '9'"));
+}
+
+/* Test la lecture de la FEN sur l'entrée standard */
+#[test]
+fn test_fen_from_stdin() {
+    let output = run_with_stdin(&[], &format!("{}\n", START_FEN));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("8 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |"));
+    assert!(stdout.contains("Fullmove number: 1"));
+}
+
+/* Test qu'une FEN invalide lue sur l'entrée standard échoue sur la sortie d'erreur */
+#[test]
+fn test_invalid_fen_from_stdin() {
+    let output = run_with_stdin(&[], "8/8/8/8/8/8/8/8 x - - 0 1\n");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("active color"));
+}
+
+/* Test qu'une entrée standard vide affiche l'aide */
+#[test]
+fn test_empty_stdin_prints_usage() {
+    let output = run_with_stdin(&[], "\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("Usage: "));
+}
+
+/* Test le rendu de plusieurs FEN dont une invalide */
+#[test]
+fn test_multiple_fens_mixed_success() {
+    let empty = "8/8/8/8/8/8/8/8 w - - 0 1";
+    let bad = "8/8/8/8/8/8/8/8 w - e9 0 1";
+    let output = run(&[START_FEN, bad, empty]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.starts_with(&format!("[1/3] {}\n", START_FEN)));
+    assert!(stdout.contains(&format!("\n[2/3] {}\n\n[3/3] {}\n", bad, empty)));
+    assert_eq!(stdout.matches("Fullmove number: 1").count(), 2);
+    assert!(stderr.contains("en passant"));
+}
+
+/* Test que plusieurs FEN valides réussissent */
+#[test]
+fn test_multiple_valid_fens() {
+    let output = run(&["--format=json", START_FEN, START_FEN]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(stdout.matches("\"fen\":").count(), 2);
+}
+
+/* Écrit un fichier temporaire propre au test et renvoie son chemin */
+fn temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("fen_parser_{}_{}.fen", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/* Test le mode `--file` : erreurs numérotées, lignes ignorées et bilan */
+#[test]
+fn test_file_batch_mode() {
+    let contents = format!(
+        "# positions de test\n{}\n\n8/8/8/8/8/8/8/8 w KQX - 0 1\n8/8/8/8/8/8/8/8 w - - 0 1\n  # commentaire indenté\n8/8/8/8/8/8/8/8 w - e9 0 1\n",
+        START_FEN
+    );
+    let path = temp_file("batch", &contents);
+    let path_str = path.to_str().unwrap();
+    let output = run(&["--format=json", "--file", path_str]);
+    std::fs::remove_file(&path).unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout.lines().count(), 2);
+    let lines: Vec<&str> = stderr.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with(&format!("{}:4: invalid castling rights 'KQX'", path_str)));
+    assert!(lines[1].starts_with(&format!("{}:7: invalid en passant square 'e9'", path_str)));
+    assert_eq!(lines[2], "2 ok, 2 failed");
+}
+
+/* Test le mode `--file -` sur l'entrée standard, en validation seule */
+#[test]
+fn test_file_batch_from_stdin() {
+    let input = format!("{}\n{}\n", START_FEN, START_FEN);
+    let output = run_with_stdin(&["validate", "--file", "-"], &input);
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty());
+}
+
+/* Test les codes de sortie de `validate` : 0 valide, 1 syntaxe, 2 incohérence, 3 entrée-sortie */
+#[test]
+fn test_validate_exit_codes() {
+    let valid = run(&["validate", START_FEN]);
+    assert_eq!(valid.status.code(), Some(0));
+    assert!(valid.stdout.is_empty());
+
+    let broken = run(&[
+        "validate",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1",
+    ]);
+    assert_eq!(broken.status.code(), Some(1));
+    let stdout = String::from_utf8(broken.stdout).unwrap();
+    assert!(stdout.starts_with("argument 1: 1 syntax error\n  invalid halfmove clock 'x'"));
+
+    let two_kings = run(&["validate", "4k3/8/8/8/8/8/8/3KK3 w - - 0 1"]);
+    assert_eq!(two_kings.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8(two_kings.stdout).unwrap(),
+        "argument 1: 1 validation error\n  white has 2 kings, expected exactly one\n"
+    );
+
+    let missing = run(&["validate", "--file", "/nonexistent/fens.txt"]);
+    assert_eq!(missing.status.code(), Some(3));
+}
+
+/* Test la forme de chaque format de sortie pour la position initiale */
+#[test]
+fn test_output_formats() {
+    let stdout = |format: &str| {
+        let output = run(&["--format", format, START_FEN]);
+        assert!(output.status.success(), "format {}", format);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(stdout("unicode").contains("8 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |"));
+    let ascii = stdout("ascii");
+    assert!(ascii.is_ascii());
+    assert!(ascii.contains("8 | r n b q k b n r |"));
+    assert!(ascii.contains("4 | . . . . . . . . |"));
+    assert_eq!(stdout("fen"), format!("{}\n", START_FEN));
+    let json = stdout("json");
+    assert!(json.starts_with('{') && json.ends_with("}\n"));
+}
+
+/* Test qu'un format inconnu liste les choix possibles */
+#[test]
+fn test_unknown_format_lists_choices() {
+    let output = run(&["--format", "yaml", START_FEN]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("'yaml'"));
+    assert!(stderr.contains("unicode, ascii, fen, json, markdown"));
+}
+
+/* Test `--flip` et `--orient` : plateau retourné et coordonnées inversées */
+#[test]
+fn test_flip_and_orient() {
+    let board = |args: &[&str]| {
+        let output = run(args);
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let normal = board(&["--format", "ascii", START_FEN]);
+    let flipped = board(&["--format", "ascii", "--flip", START_FEN]);
+    let black_to_move = START_FEN.replace(" w ", " b ");
+
+    assert!(normal.contains("    a b c d e f g h\n"));
+    assert!(flipped.contains("    h g f e d c b a\n"));
+    assert!(flipped.starts_with("  +-----------------+\n1 | R N B K Q B N R |\n"));
+    assert_ne!(normal, flipped);
+    assert_eq!(
+        board(&["--orient", "black", START_FEN]),
+        board(&["--flip", START_FEN])
+    );
+    assert!(board(&["--orient=auto", &black_to_move]).contains("    h g f e d c b a\n"));
+}
+
+/* Test que l'orientation est refusée pour les formats sans plateau */
+#[test]
+fn test_orientation_rejected_for_non_board_formats() {
+    let output = run(&["--flip", "--format", "json", START_FEN]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("--flip only applies to the unicode and ascii formats"));
+}
+
+/* Test `--color` sur une sortie redirigée */
+#[test]
+fn test_color_flag() {
+    let stdout = |args: &[&str]| {
+        let output = run(args);
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let plain = stdout(&[START_FEN]);
+
+    assert!(!stdout(&["--color", "auto", START_FEN]).contains('\x1b'));
+    assert!(stdout(&["--color=always", START_FEN]).contains("\x1b[48;5;"));
+    assert_eq!(stdout(&["--color", "never", START_FEN]), plain);
+
+    let output = run(&["--color", "sometimes", START_FEN]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+/* Test les codes de sortie de `diff` : positions identiques, un coup d'écart, FEN invalide */
+#[test]
+fn test_diff_subcommand() {
+    let identical = run(&["diff", START_FEN, START_FEN]);
+    assert_eq!(identical.status.code(), Some(0));
+    assert!(String::from_utf8(identical.stdout)
+        .unwrap()
+        .contains("Positions are identical"));
+
+    let after_e4 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+    let different = run(&["diff", START_FEN, after_e4]);
+    assert_eq!(different.status.code(), Some(1));
+    let stdout = String::from_utf8(different.stdout).unwrap();
+    assert!(stdout.contains("  e2: P -> empty\n  e4: empty -> P\n"));
+    assert!(stdout.contains("  Active color: White -> Black\n"));
+    assert!(stdout.contains("  En passant: - -> e3\n"));
+
+    let invalid = run(&["diff", START_FEN, "8/8/8 w - - 0 1"]);
+    assert_eq!(invalid.status.code(), Some(2));
+    assert!(invalid.stdout.is_empty());
+    assert!(!invalid.stderr.is_empty());
+}
+
+/* Test `apply` : FEN finale après une courte ouverture, et trace coup par coup */
+#[test]
+fn test_apply_moves() {
+    let output = run(&[
+        "apply", "startpos", "e2e4", "e7e5", "g1f3", "--format", "fen",
+    ]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2\n"
+    );
+
+    let output = run(&["apply", "--trace", START_FEN, "e2e4", "c7c5"]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\n\
+         rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2\n"
+    );
+}
+
+/* Test qu'un coup illégal interrompt `apply` avec son rang et la position atteinte */
+#[test]
+fn test_apply_illegal_move() {
+    let output = run(&["apply", "startpos", "e2e4", "e7e4"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "move 2: e7e4 is not legal in this position\n\
+         position reached: rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\n"
+    );
+
+    let output = run(&["apply", "startpos", "e2e9"]);
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("move 1: 'e2e9' is not a valid UCI move\n"));
+}
+
+/* Test que `random` est reproductible avec `--seed` et que `--legal-ish` produit des FEN valides */
+#[test]
+fn test_random_subcommand() {
+    let first = run(&["random", "--count", "5", "--seed", "42", "--legal-ish"]);
+    let second = run(&["random", "--count=5", "--seed=42", "--legal-ish"]);
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+
+    let stdout = String::from_utf8(first.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 5);
+    let mut args = vec!["validate"];
+    args.extend(stdout.lines());
+    assert_eq!(run(&args).status.code(), Some(0));
+
+    assert_eq!(run(&["random", "--count", "many"]).status.code(), Some(2));
+}
+
+/* Test le mode `repl` piloté par un script sur l'entrée standard, sans invite */
+#[test]
+fn test_repl_from_script() {
+    let output = run_with_stdin(&["repl"], "move g1f3\nhelp\nquit\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1\n"));
+    assert!(stdout.contains("  undo "));
+    assert!(!stdout.contains("fen> "));
+}
+
+/* Test que la FEN seule reste un alias de `show` */
+#[test]
+fn test_legacy_invocation_matches_show() {
+    let legacy = run(&[START_FEN]);
+    let show = run(&["show", START_FEN]);
+
+    assert!(legacy.status.success());
+    assert!(String::from_utf8_lossy(&legacy.stdout).contains("8 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |"));
+    assert_eq!(legacy.stdout, show.stdout);
+}
+
+/* Test que `--help` liste les sous-commandes et que chacune a son aide */
+#[test]
+fn test_help_lists_subcommands() {
+    let output = run(&["--help"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    for command in [
+        "show", "validate", "diff", "apply", "convert", "random", "repl",
+    ] {
+        assert!(stdout.contains(&format!("\n  {} ", command)), "{}", command);
+    }
+    assert!(stdout.contains("Examples:"));
+
+    let validate = String::from_utf8(run(&["validate", "--help"]).stdout).unwrap();
+    assert!(validate.starts_with("Usage: fen_parser validate "));
+    assert_eq!(run(&["help", "validate"]).stdout, validate.as_bytes());
+}
+
+/* Test le refus des options inconnues ou propres à une autre sous-commande */
+#[test]
+fn test_unknown_and_misplaced_options() {
+    let output = run(&["--bogus", START_FEN]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Unknown option '--bogus'"));
+
+    let output = run(&["diff", "--trace", START_FEN, START_FEN]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("--trace is not an option of 'diff'"));
+}
+
+/* Test que `convert` produit une FEN canonique par défaut */
+#[test]
+fn test_convert_defaults_to_fen() {
+    let output = run(&[
+        "convert",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    ]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", START_FEN)
+    );
+}
+
+/* Test `--quiet` : rien sur la sortie standard pour `validate` réussi, seul l'artefact sinon */
+#[test]
+fn test_quiet_mode() {
+    let output = run(&["validate", "--quiet", "--verbose", START_FEN]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty());
+
+    assert!(run(&["--quiet", START_FEN]).stdout.is_empty());
+    let json = run(&["--quiet", "--format", "json", START_FEN, START_FEN]);
+    assert_eq!(String::from_utf8(json.stdout).unwrap().lines().count(), 2);
+
+    let output = run(&["validate", "--quiet", "4k3/8/8/8/8/8/8/3KK3 w - - 0 1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: argument 1: white has 2 kings, expected exactly one\n"
+    );
+}
+
+/* Test les enregistrements JSON sur la sortie d'erreur pour un échec */
+#[test]
+fn test_json_log_records() {
+    let output = run(&[
+        "--quiet",
+        "--log-format",
+        "json",
+        "8/8/8/8/8/8/8/8 w KQkq e9 0 1",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let records: Vec<_> = stderr.lines().map(common::parse_json).collect();
+    assert_eq!(records.len(), 1);
+    let record = records[0]
+        .as_ref()
+        .expect("stderr line should be a JSON object");
+    let fields = record.as_object().expect("record should be an object");
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields.get("level"), Some(&Json::String("error".into())));
+    assert!(record
+        .get("message")
+        .and_then(Json::as_str)
+        .unwrap()
+        .starts_with("invalid en passant square 'e9'"));
+}
+
+/* Test `svg` : fichier écrit, XML bien formé à 64 cases, pas d'écrasement sans `--force` */
+#[test]
+fn test_svg_subcommand() {
+    let dir = std::env::temp_dir().join(format!("fen_parser_svg_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("board.svg");
+    let _ = std::fs::remove_file(&path);
+    let path_str = path.to_str().unwrap();
+
+    let output = run(&[
+        "svg",
+        START_FEN,
+        "-o",
+        path_str,
+        "--size",
+        "400",
+        "--flip",
+        "--coordinates",
+    ]);
+    assert!(output.status.success(), "{:?}", output);
+    let svg = std::fs::read_to_string(&path).unwrap();
+    assert!(!svg.is_empty());
+    assert!(svg.starts_with("<svg "));
+    common::check_well_formed_xml(&svg).unwrap();
+    assert_eq!(svg.matches("<rect ").count(), 64);
+
+    let again = run(&["svg", START_FEN, "-o", path_str]);
+    assert_eq!(again.status.code(), Some(3));
+    assert!(String::from_utf8(again.stderr).unwrap().contains("--force"));
+    assert!(run(&["svg", START_FEN, "-o", path_str, "--force"])
+        .status
+        .success());
+    assert_ne!(std::fs::read_to_string(&path).unwrap(), svg);
+
+    let missing_dir = dir.join("missing").join("board.svg");
+    let output = run(&["svg", START_FEN, "--output", missing_dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(3));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/* Extrait de la suite « Win At Chess », avec une ligne invalide */
+const WAC_EXCERPT: &str = "\
+2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";
+8/7p/5k2/5p2/p1p2P2/Pr1pPK2/1P1R3P/8 b - - bm Rxb2; id \"WAC.002\";
+5rk1/1ppb3p/p1pb4/6q1/3P1p1r/2P1R2P/PP1BQ1P1/5RK1 b - - bm Rg3; id \"WAC.003\";
+r1bq2rk/pp3pbp/2p1p1pQ/7P/3P4/2PB1N2/PP3PPR/2KR4 x - - bm Qxh7+; id \"WAC.004\";
+";
+
+/* Test `epd` : extraction de la colonne `bm`, conversion en FEN et bilan */
+#[test]
+fn test_epd_subcommand() {
+    let path = temp_file("wac", WAC_EXCERPT);
+    let path_str = path.to_str().unwrap();
+
+    let output = run(&["epd", path_str, "--print-field", "bm"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Qg6\nRxb2\nRg3\n"
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let lines: Vec<&str> = stderr.lines().collect();
+    assert!(lines[0].starts_with(&format!("{}:4: invalid active color 'x'", path_str)));
+    assert_eq!(lines[1], "3 ok, 1 failed");
+
+    let output = run_with_stdin(&["epd", "-", "--to-fen"], WAC_EXCERPT);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        [
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1",
+            "8/7p/5k2/5p2/p1p2P2/Pr1pPK2/1P1R3P/8 b - - 0 1",
+            "5rk1/1ppb3p/p1pb4/6q1/3P1p1r/2P1R2P/PP1BQ1P1/5RK1 b - - 0 1",
+        ]
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+/* Test `stats` sur un corpus de 20 lignes : doublons, trait, roques, signatures et pièces */
+#[test]
+fn test_stats_subcommand() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    let mut corpus = vec![start; 4];
+    /* Même position que la précédente, seuls les compteurs changent */
+    corpus.push("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 4 3");
+    corpus.extend(["4k3/8/8/8/8/8/8/R3K3 b Q - 0 1"; 3]);
+    corpus.extend(["8/8/4k3/8/8/3K4/8/8 w - - 0 40"; 2]);
+    corpus.extend([
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/3P4/4K3 w - - 0 1",
+        "4k3/4p3/8/8/8/8/8/4K3 b - - 0 1",
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+        "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1",
+        "8/8/8/8/8/8/8/8 x - - 0 1",
+        "not a fen",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0",
+        "3qk3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "3qk3/8/8/8/8/8/8/4K3 b - - 0 1",
+    ]);
+    assert_eq!(corpus.len(), 20);
+    let path = temp_file("corpus", &(corpus.join("\n") + "\n"));
+
+    let output = run(&[
+        "stats",
+        "--file",
+        path.to_str().unwrap(),
+        "--top",
+        "2",
+        "--quiet",
+    ]);
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "lines: 20");
+    assert_eq!(lines[1], "parse failures: 3");
+    assert_eq!(lines[2], "duplicate positions: 7");
+    assert_eq!(lines[3], "side to move: white 11, black 6");
+    assert_eq!(lines[4], "castling rights: - 7, KQkq 7, Q 3");
+    assert_eq!(lines[5], "material signatures (top 2):");
+    assert!(lines[6].starts_with("  KQRRBBNNPPPPPPPPvKQRRBBNNPPPPPPPP"));
+    assert!(lines[6].ends_with(" 5"));
+    assert!(lines[7].starts_with("  KRvK "));
+    assert_eq!(lines[8], "pieces: min 2, median 3, max 32");
+    assert_eq!(lines.len(), 9);
+    std::fs::remove_file(path).unwrap();
+}
+
+/* Test `convert --from/--to` : départ 960 entre FEN classique et Shredder-FEN, en lot */
+#[test]
+fn test_convert_dialects() {
+    let classical = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w KQkq - 0 1";
+    let shredder = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w GEge - 0 1";
+
+    let output = run(&["convert", "--to", "shredder", classical]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", shredder)
+    );
+
+    let output = run(&["convert", "--from", "shredder", "--to", "fen", shredder]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", classical)
+    );
+
+    let input = format!("{}\n4k3/8/8/8/8/8/8/4K3 w Q - 0 1\n", classical);
+    let output = run_with_stdin(&["convert", "--to", "shredder", "--file", "-"], &input);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", shredder)
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("<stdin>:2: the white queenside castling right has no rook to castle with")
+    );
+
+    let output = run(&["convert", "--to", "shredder", "--format", "json", classical]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&["convert", "--to", "pgn", classical]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+/* Test `perft` : total de la position initiale et détail par coup dans la mise en page de Stockfish */
+#[test]
+fn test_perft_subcommand() {
+    let output = run(&["perft", "startpos", "--depth", "3"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Nodes searched: 8902\n"
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("nodes/s"));
+
+    let output = run(&["perft", "startpos", "--depth", "3", "--divide", "--quiet"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 22);
+    assert_eq!(lines[0], "a2a3: 380");
+    assert!(lines.contains(&"e2e4: 600"));
+    assert_eq!(lines[20], "");
+    assert_eq!(lines[21], "Nodes searched: 8902");
+    assert!(output.stderr.is_empty());
+
+    assert_eq!(run(&["perft", "startpos"]).status.code(), Some(2));
+}
+
+/* Test `--material` : seules les positions retenues par le filtre sont lues ou comptées */
+#[test]
+fn test_material_filter_option() {
+    let input = "4k3/8/8/8/8/8/8/R3K3 w - - 0 1\n\
+                 r3k3/8/8/8/8/8/8/4K3 b - - 0 1\n\
+                 4k3/8/8/8/8/8/8/Q3K3 w - - 0 1\n";
+    let output = run_with_stdin(&["stats", "--file", "-", "--material", "KRvK"], input);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("side to move: white 1, black 1"));
+
+    let output = run_with_stdin(&["stats", "--file", "-", "--material", "^KRvK"], input);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("side to move: white 1, black 0"));
+
+    let output = run(&["stats", "--file", "-", "--material", "KRvR"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Invalid value for --material"));
+}
+
+/* Test qu'un fichier introuvable donné à show et convert est une erreur d'entrée-sortie */
+#[test]
+fn test_missing_file_is_io_error() {
+    for command in ["show", "convert"] {
+        let output = run(&[command, "--file", "/nonexistent/fens.txt"]);
+        assert_eq!(output.status.code(), Some(3), "{}", command);
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("/nonexistent/fens.txt"));
+    }
+}
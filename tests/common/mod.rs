@@ -0,0 +1,335 @@
+/* Utilitaires partagés par les tests d'intégration.
+
+Contient un lecteur JSON minimal, suffisant pour relire les documents produits par la crate. */
+
+#![allow(dead_code)]
+
+use std::collections::BTreeMap;
+
+/* Valeur JSON */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(BTreeMap<String, Json>),
+}
+
+impl Json {
+    /* Accède à un membre d'objet */
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&BTreeMap<String, Json>> {
+        match self {
+            Json::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Json>> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/* Analyse un document JSON complet */
+pub fn parse_json(input: &str) -> Result<Json, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut pos = 0;
+    let value = parse_value(&chars, &mut pos)?;
+    skip_ws(&chars, &mut pos);
+    if pos != chars.len() {
+        return Err(format!("trailing characters at {}", pos));
+    }
+    Ok(value)
+}
+
+fn skip_ws(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && chars[*pos].is_whitespace() {
+        *pos += 1;
+    }
+}
+
+fn expect(chars: &[char], pos: &mut usize, c: char) -> Result<(), String> {
+    skip_ws(chars, pos);
+    if chars.get(*pos) == Some(&c) {
+        *pos += 1;
+        Ok(())
+    } else {
+        Err(format!("expected '{}' at {}", c, pos))
+    }
+}
+
+fn parse_value(chars: &[char], pos: &mut usize) -> Result<Json, String> {
+    skip_ws(chars, pos);
+    match chars.get(*pos) {
+        Some('{') => {
+            *pos += 1;
+            let mut map = BTreeMap::new();
+            skip_ws(chars, pos);
+            if chars.get(*pos) == Some(&'}') {
+                *pos += 1;
+                return Ok(Json::Object(map));
+            }
+            loop {
+                skip_ws(chars, pos);
+                let key = parse_string(chars, pos)?;
+                expect(chars, pos, ':')?;
+                map.insert(key, parse_value(chars, pos)?);
+                skip_ws(chars, pos);
+                match chars.get(*pos) {
+                    Some(',') => *pos += 1,
+                    Some('}') => {
+                        *pos += 1;
+                        return Ok(Json::Object(map));
+                    }
+                    _ => return Err(format!("expected ',' or '}}' at {}", pos)),
+                }
+            }
+        }
+        Some('[') => {
+            *pos += 1;
+            let mut items = Vec::new();
+            skip_ws(chars, pos);
+            if chars.get(*pos) == Some(&']') {
+                *pos += 1;
+                return Ok(Json::Array(items));
+            }
+            loop {
+                items.push(parse_value(chars, pos)?);
+                skip_ws(chars, pos);
+                match chars.get(*pos) {
+                    Some(',') => *pos += 1,
+                    Some(']') => {
+                        *pos += 1;
+                        return Ok(Json::Array(items));
+                    }
+                    _ => return Err(format!("expected ',' or ']' at {}", pos)),
+                }
+            }
+        }
+        Some('"') => parse_string(chars, pos).map(Json::String),
+        Some('t') => parse_literal(chars, pos, "true", Json::Bool(true)),
+        Some('f') => parse_literal(chars, pos, "false", Json::Bool(false)),
+        Some('n') => parse_literal(chars, pos, "null", Json::Null),
+        Some(c) if *c == '-' || c.is_ascii_digit() => {
+            let start = *pos;
+            while *pos < chars.len() && "+-.eE0123456789".contains(chars[*pos]) {
+                *pos += 1;
+            }
+            let text: String = chars[start..*pos].iter().collect();
+            text.parse()
+                .map(Json::Number)
+                .map_err(|_| format!("invalid number '{}'", text))
+        }
+        _ => Err(format!("unexpected character at {}", pos)),
+    }
+}
+
+fn parse_literal(chars: &[char], pos: &mut usize, word: &str, value: Json) -> Result<Json, String> {
+    let end = *pos + word.chars().count();
+    if end <= chars.len() && chars[*pos..end].iter().copied().eq(word.chars()) {
+        *pos = end;
+        Ok(value)
+    } else {
+        Err(format!("invalid literal at {}", pos))
+    }
+}
+
+fn parse_string(chars: &[char], pos: &mut usize) -> Result<String, String> {
+    if chars.get(*pos) != Some(&'"') {
+        return Err(format!("expected string at {}", pos));
+    }
+    *pos += 1;
+    let mut out = String::new();
+    loop {
+        match chars.get(*pos) {
+            Some('"') => {
+                *pos += 1;
+                return Ok(out);
+            }
+            Some('\\') => {
+                *pos += 1;
+                match chars.get(*pos) {
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some('/') => out.push('/'),
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('b') => out.push('\u{8}'),
+                    Some('f') => out.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = chars
+                            .get(*pos + 1..*pos + 5)
+                            .ok_or("short escape")?
+                            .iter()
+                            .collect();
+                        let code = u32::from_str_radix(&hex, 16).map_err(|_| "invalid escape")?;
+                        out.push(char::from_u32(code).ok_or("invalid code point")?);
+                        *pos += 4;
+                    }
+                    _ => return Err(format!("invalid escape at {}", pos)),
+                }
+                *pos += 1;
+            }
+            Some(c) if (*c as u32) < 0x20 => return Err(format!("control character at {}", pos)),
+            Some(c) => {
+                out.push(*c);
+                *pos += 1;
+            }
+            None => return Err("unterminated string".to_string()),
+        }
+    }
+}
+
+/* Vérifie sommairement qu'un document XML est bien formé : balises équilibrées, attributs entre guillemets et entités connues */
+pub fn check_well_formed_xml(input: &str) -> Result<(), String> {
+    let mut stack: Vec<String> = Vec::new();
+    let mut rest = input;
+    let mut seen_root = false;
+
+    while let Some(start) = rest.find(['<', '&']) {
+        check_text(&rest[..start])?;
+        rest = &rest[start..];
+        if rest.starts_with('&') {
+            let end = rest.find(';').ok_or("unterminated entity")?;
+            let entity = &rest[1..end];
+            let known = ["amp", "lt", "gt", "quot", "apos"].contains(&entity)
+                || entity.starts_with('#') && entity[1..].chars().all(|c| c.is_ascii_digit());
+            if !known {
+                return Err(format!("unknown entity '&{};'", entity));
+            }
+            rest = &rest[end + 1..];
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("<?") {
+            let end = body.find("?>").ok_or("unterminated declaration")?;
+            rest = &body[end + 2..];
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("<!--") {
+            let end = body.find("-->").ok_or("unterminated comment")?;
+            rest = &body[end + 3..];
+            continue;
+        }
+        let end = tag_end(rest).ok_or("unterminated tag")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            match stack.pop() {
+                Some(open) if open == name.trim() => {}
+                other => return Err(format!("closing </{}> does not match {:?}", name, other)),
+            }
+            continue;
+        }
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name: String = tag.chars().take_while(|c| !c.is_whitespace()).collect();
+        if name.is_empty() {
+            return Err("empty tag name".to_string());
+        }
+        check_attributes(&tag[name.len()..])?;
+        if stack.is_empty() {
+            if seen_root {
+                return Err("multiple root elements".to_string());
+            }
+            seen_root = true;
+        }
+        if !self_closing {
+            stack.push(name);
+        }
+    }
+    check_text(rest)?;
+
+    if !stack.is_empty() {
+        return Err(format!("unclosed elements: {:?}", stack));
+    }
+    if !seen_root {
+        return Err("no root element".to_string());
+    }
+    Ok(())
+}
+
+/* Position du '>' fermant une balise, en ignorant ceux entre guillemets */
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn check_text(text: &str) -> Result<(), String> {
+    if text.contains('>') {
+        return Err(format!("unescaped '>' in text {:?}", text));
+    }
+    Ok(())
+}
+
+fn check_attributes(mut attrs: &str) -> Result<(), String> {
+    loop {
+        attrs = attrs.trim_start();
+        if attrs.is_empty() {
+            return Ok(());
+        }
+        let eq = attrs
+            .find('=')
+            .ok_or_else(|| format!("attribute without value in {:?}", attrs))?;
+        let quote = attrs[eq + 1..]
+            .chars()
+            .next()
+            .ok_or("missing attribute value")?;
+        if quote != '"' && quote != '\'' {
+            return Err(format!("unquoted attribute value in {:?}", attrs));
+        }
+        let value_start = eq + 2;
+        let value_len = attrs[value_start..]
+            .find(quote)
+            .ok_or("unterminated attribute value")?;
+        let value = &attrs[value_start..value_start + value_len];
+        if value.contains('<') || value.contains('&') && !value.contains(';') {
+            return Err(format!(
+                "unescaped character in attribute value {:?}",
+                value
+            ));
+        }
+        attrs = &attrs[value_start + value_len + 1..];
+    }
+}
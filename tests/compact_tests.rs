@@ -0,0 +1,138 @@
+/* Tests de l'encodage binaire compact.
+
+Ces tests vérifient l'aller-retour, la taille de l'encodage et le refus des tampons tronqués ou corrompus, ainsi que la structure `CompactPosition`. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{ChessPosition, CompactError, CompactPosition, RandomPositionGenerator, Square};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+const POSITIONS: [&str; 4] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b Kq - 17 4000000000",
+    "8/8/8/8/8/8/8/8 b - - 0 1",
+];
+
+/* Test l'aller-retour et la taille de l'encodage */
+#[test]
+fn test_compact_round_trip() {
+    for fen in POSITIONS {
+        let position = ChessPosition::from_fen(fen).unwrap();
+        let bytes = position.to_compact();
+        assert!(bytes.len() <= 72, "{} bytes for {}", bytes.len(), fen);
+        assert_eq!(ChessPosition::from_compact(&bytes).unwrap(), position);
+    }
+    assert_eq!(ChessPosition::default().to_compact().len(), 37);
+}
+
+/* Test qu'un tampon tronqué ou corrompu donne une erreur, jamais une panique */
+#[test]
+fn test_compact_rejects_bad_buffers() {
+    let position = ChessPosition::from_fen(POSITIONS[2]).unwrap();
+    let bytes = position.to_compact();
+    for len in 0..bytes.len() {
+        assert!(
+            matches!(
+                ChessPosition::from_compact(&bytes[..len]),
+                Err(CompactError::Truncated { .. })
+            ),
+            "prefix of {} bytes",
+            len
+        );
+    }
+
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert_eq!(
+        ChessPosition::from_compact(&extended),
+        Err(CompactError::TrailingBytes(1))
+    );
+    let mut corrupted = bytes.clone();
+    corrupted[1] = 0x07;
+    assert!(matches!(
+        ChessPosition::from_compact(&corrupted),
+        Err(CompactError::InvalidPiece { code: 7, .. })
+    ));
+    let mut corrupted = bytes;
+    corrupted[0] = 2;
+    assert_eq!(
+        ChessPosition::from_compact(&corrupted),
+        Err(CompactError::UnsupportedVersion(2))
+    );
+}
+
+/* Positions du corpus de test : celles de ce fichier, le corpus d'amorçage de `fuzz/` et des
+ * positions générées */
+fn corpus() -> Vec<ChessPosition> {
+    let mut positions: Vec<ChessPosition> = POSITIONS
+        .iter()
+        .map(|fen| ChessPosition::from_fen(fen).unwrap())
+        .collect();
+    let seeds = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus/round_trip");
+    for entry in fs::read_dir(seeds).unwrap() {
+        let text = fs::read_to_string(entry.unwrap().path()).unwrap();
+        positions.extend(ChessPosition::from_fen(&text).ok());
+    }
+    let mut generator = RandomPositionGenerator::new(424);
+    for _ in 0..500 {
+        positions.push(generator.random_board());
+        positions.push(generator.random_plausible());
+    }
+    positions
+}
+
+/* Test l'aller-retour par `CompactPosition`, sa FEN et sa taille */
+#[test]
+fn test_compact_position_round_trip() {
+    assert!(std::mem::size_of::<CompactPosition>() <= 40);
+    for position in corpus() {
+        let compact = CompactPosition::try_from(&position).unwrap();
+        assert_eq!(compact.to_fen(), position.to_fen());
+        assert_eq!(ChessPosition::from(compact), position);
+        let e4 = Square::from_name("e4").unwrap();
+        assert_eq!(compact.piece_at(e4), position.piece_at(e4));
+        assert_eq!(compact.active_color(), position.active_color);
+    }
+}
+
+/* Test l'égalité et le hachage sur la forme compacte */
+#[test]
+fn test_compact_position_equality_and_hash() {
+    let positions = corpus();
+    let fens: HashSet<String> = positions.iter().map(ChessPosition::to_fen).collect();
+    let compact: HashSet<CompactPosition> = positions
+        .iter()
+        .map(|position| CompactPosition::try_from(position).unwrap())
+        .collect();
+    assert_eq!(compact.len(), fens.len());
+
+    let start = ChessPosition::default();
+    assert_eq!(
+        CompactPosition::try_from(&start),
+        CompactPosition::try_from(&start.clone())
+    );
+    let moved = ChessPosition::from_fen(POSITIONS[1]).unwrap();
+    assert_ne!(
+        CompactPosition::try_from(&start),
+        CompactPosition::try_from(&moved)
+    );
+}
+
+/* Test la limite du compteur de demi-coups : 65 535 passe, 65 536 est refusé au lieu d'être
+ * plafonné */
+#[test]
+fn test_compact_position_halfmove_clock_limit() {
+    let longest = ChessPosition::from_fen("8/8/8/8/8/8/8/8 w - - 65535 1").unwrap();
+    let compact = CompactPosition::try_from(&longest).unwrap();
+    assert!(compact.to_fen().ends_with(" 65535 1"));
+    assert_eq!(ChessPosition::from(compact), longest);
+
+    let too_long = ChessPosition::from_fen("8/8/8/8/8/8/8/8 w - - 65536 1").unwrap();
+    assert_eq!(
+        CompactPosition::try_from(&too_long),
+        Err(CompactError::HalfmoveClockOverflow(65536))
+    );
+}
@@ -0,0 +1,51 @@
+/* Tests de la recherche des doublons.
+
+Ces tests vérifient que les doublons exacts et ceux qui ne diffèrent que par les compteurs ou par une prise en passant inutilisable sont regroupés, que les images miroir ne le sont qu'en mode symétrique et que les entrées illisibles sont signalées. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::dedup::{find_duplicates, find_duplicates_with};
+use fen_parser::{DedupMode, FenField};
+
+const FENS: [&str; 8] = [
+    /* 0 et 1 : la même FEN */
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+    /* 2 : seuls les compteurs diffèrent */
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 4 9",
+    /* 3 : case e3 qu'aucun pion noir ne peut prendre */
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    /* 4 et 5 : une position et son miroir */
+    "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+    "4k3/4p3/8/8/8/8/8/4K3 b - - 0 1",
+    /* 6 : illisible */
+    "8/8/8 w - - 0 1",
+    /* 7 : prise en passant réelle, position distincte de la même sans prise */
+    "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+];
+
+/* Test le regroupement des doublons exacts, compteurs et prise en passant inutilisable compris */
+#[test]
+fn test_exact_duplicates() {
+    let mut fens = FENS.to_vec();
+    fens.push("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1");
+    let report = find_duplicates(fens.iter().copied());
+    assert_eq!(report.groups, vec![vec![0, 1, 2, 3]]);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].0, 6);
+    assert_eq!(report.failures[0].1.field, FenField::PiecePlacement);
+    assert_eq!(report.distinct, 5);
+    assert_eq!(report.redundant(), 3);
+}
+
+/* Test que les images miroir ne sont regroupées qu'en mode `Symmetric` */
+#[test]
+fn test_symmetric_duplicates() {
+    let report = find_duplicates_with(FENS.into_iter(), DedupMode::Symmetric);
+    assert_eq!(report.groups, vec![vec![0, 1, 2, 3], vec![4, 5]]);
+    assert_eq!(report.distinct, 3);
+
+    let exact = find_duplicates_with(FENS.into_iter(), DedupMode::Exact);
+    assert_eq!(exact, find_duplicates(FENS.into_iter()));
+    assert!(!exact.groups.contains(&vec![4, 5]));
+}
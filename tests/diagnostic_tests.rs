@@ -0,0 +1,50 @@
+/* Tests des diagnostics détaillés des erreurs de parsing.
+
+Ces tests figent le rendu texte du rapport : FEN soulignée sous le fragment fautif et aide associée. */
+
+#![cfg(feature = "diagnostics")]
+
+use fen_parser::parse_fen;
+
+/* Rend le diagnostic de la FEN invalide donnée */
+fn diagnostic(fen: &str) -> String {
+    parse_fen(fen).unwrap_err().diagnostic(fen)
+}
+
+/* Test le rendu d'un droit de roque invalide */
+#[test]
+fn test_castling_diagnostic_snapshot() {
+    let expected = concat!(
+        "error: invalid castling rights: unexpected character\n",
+        "  --> fen:1:19\n",
+        "   |\n",
+        " 1 | 8/8/8/8/8/8/8/8 w KQX - 0 1\n",
+        "   |                   ^^^ expected '-' or a combination of 'KQkq', found 'X'\n",
+        "   |\n",
+        "   = help: castling field may only contain K, Q, k, q or -\n",
+    );
+    assert_eq!(diagnostic("8/8/8/8/8/8/8/8 w KQX - 0 1"), expected);
+}
+
+/* Test le rendu d'un champ manquant, souligné en fin de chaîne */
+#[test]
+fn test_missing_field_diagnostic_snapshot() {
+    let expected = concat!(
+        "error: missing halfmove clock\n",
+        "  --> fen:1:22\n",
+        "   |\n",
+        " 1 | 8/8/8/8/8/8/8/8 b - -\n",
+        "   |                      ^ missing\n",
+        "   |\n",
+        "   = help: a FEN has six space-separated fields: placement, color, castling, en passant, halfmove clock and fullmove number\n",
+    );
+    assert_eq!(diagnostic("8/8/8/8/8/8/8/8 b - -"), expected);
+}
+
+/* Test que le soulignement compte les caractères et non les octets */
+#[test]
+fn test_diagnostic_multibyte_alignment() {
+    let report = diagnostic("8/8/8/8/8/8/8/8 é - - 0 1");
+    assert!(report.contains(" 1 | 8/8/8/8/8/8/8/8 é - - 0 1\n"));
+    assert!(report.contains("\n   |                 ^ expected 'w' or 'b', found 'é'\n"));
+}
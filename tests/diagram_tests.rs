@@ -0,0 +1,120 @@
+/* Tests de la lecture des diagrammes texte.
+
+Ces tests vérifient que chaque rendu de `render_board` se relit à l'identique, que les diagrammes saisis à la main sont acceptés et que les erreurs situent le caractère fautif. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{
+    parse_fen, BoardStyle, BorderStyle, ChessPosition, CoordinateSides, DiagramMeta, DisplayMode,
+    DisplayOptions, FenErrorKind, HighlightKind, Orientation, PieceTheme, Square,
+};
+
+const FENS: [&str; 4] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "4k3/8/8/3pP3/8/8/8/4K3 b - d6 0 12",
+];
+
+/* Styles de rendu couvrant cadres, grilles, thèmes, orientation et couleurs ; le thème creux
+ * par défaut ne distingue pas les couleurs, les rendus partent donc du thème Unicode */
+fn all_options() -> Vec<DisplayOptions> {
+    let unicode = || BoardStyle {
+        theme: PieceTheme::UNICODE,
+        ..BoardStyle::default()
+    };
+    let style = |style: BoardStyle| DisplayOptions {
+        style,
+        ..DisplayOptions::default()
+    };
+    let mut highlighted = style(unicode());
+    highlighted.highlight(Square::from_name("e1").unwrap(), HighlightKind::LastMove);
+    highlighted.highlight(Square::from_name("a8").unwrap(), HighlightKind::Check);
+    vec![
+        style(unicode()),
+        style(BoardStyle {
+            theme: PieceTheme::ASCII,
+            empty_square_char: '.',
+            ..unicode()
+        }),
+        style(BoardStyle {
+            border: BorderStyle::None,
+            show_coordinates: false,
+            ..unicode()
+        }),
+        style(BoardStyle {
+            border: BorderStyle::Rounded,
+            coordinate_sides: CoordinateSides::All,
+            ..unicode()
+        }),
+        style(BoardStyle {
+            border: BorderStyle::Heavy,
+            square_width: 3,
+            show_material: true,
+            ..unicode()
+        }),
+        DisplayOptions {
+            orientation: Orientation::Black,
+            ..style(unicode())
+        },
+        DisplayOptions {
+            mode: DisplayMode::Color,
+            ..style(unicode())
+        },
+        highlighted,
+    ]
+}
+
+/* Test la relecture de chaque rendu du plateau, pour tous les styles */
+#[test]
+fn test_render_round_trip() {
+    for fen in FENS {
+        let position = parse_fen(fen).unwrap();
+        for options in all_options() {
+            let rendered = position.render_board(&options);
+            let parsed = ChessPosition::from_board_diagram(&rendered, DiagramMeta::of(&position))
+                .unwrap_or_else(|error| panic!("{error}\n{rendered}"));
+            assert_eq!(parsed.to_fen(), fen, "\n{rendered}");
+        }
+    }
+}
+
+/* Test des diagrammes saisis à la main : lettres accolées, séparées, espaces pour les cases
+ * vides et coordonnées */
+#[test]
+fn test_hand_typed_diagrams() {
+    let expected = "r3k2r/8/8/8/4P3/8/8/R3K2R w - - 0 1";
+    let diagrams = [
+        "r...k..r\n........\n........\n........\n....P...\n........\n........\nR...K..R\n",
+        "r . . . k . . r\n. . . . . . . .\n. . . . . . . .\n. . . . . . . .\n\
+         . . . . P . . .\n. . . . . . . .\n. . . . . . . .\nR . . . K . . R\n",
+        "8 r   k  r\n7\n6\n5\n4     P\n3\n2\n1 R   K  R\n  abcdefgh\n",
+        "  r · · · k · · r\n  · · · · · · · ·\n  · · · · · · · ·\n  · · · · · · · ·\n\
+         \x20 · · · · ♙ · · ·\n  · · · · · · · ·\n  · · · · · · · ·\n  ♖ · · · ♔ · · ♖\n",
+    ];
+    for diagram in diagrams {
+        let position = ChessPosition::from_board_diagram(diagram, DiagramMeta::default())
+            .unwrap_or_else(|error| panic!("{error}\n{diagram}"));
+        assert_eq!(position.to_fen(), expected, "\n{diagram}");
+    }
+}
+
+/* Test les erreurs : caractère inconnu situé par ligne et colonne, nombre de lignes */
+#[test]
+fn test_diagram_errors() {
+    let diagram =
+        "r...k..r\n........\n..x.....\n........\n........\n........\n........\nR...K..R\n";
+    let error = ChessPosition::from_board_diagram(diagram, DiagramMeta::default()).unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::UnexpectedChar);
+    assert_eq!(error.found, "x (row 3, column 3)");
+
+    let error = ChessPosition::from_board_diagram("r...k..r\n........\n", DiagramMeta::default())
+        .unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::WrongLength);
+    assert_eq!(error.found, "2 rows");
+
+    let diagram =
+        "r...k..r\n........\n.........\n........\n........\n........\n........\nR...K..R\n";
+    let error = ChessPosition::from_board_diagram(diagram, DiagramMeta::default()).unwrap_err();
+    assert_eq!(error.found, "9 squares in row 3");
+}
@@ -0,0 +1,78 @@
+/* Tests des variantes de FEN.
+
+Ces tests vérifient la conversion des droits de roque entre FEN classique, Shredder-FEN et X-FEN, ainsi que les formes à quatre champs. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{DialectError, FenDialect};
+
+const START_960_CLASSICAL: &str = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w KQkq - 0 1";
+const START_960_SHREDDER: &str = "bqnbrkrn/pppppppp/8/8/8/8/PPPPPPPP/BQNBRKRN w GEge - 0 1";
+
+/* Test la position de départ 960 convertie dans les deux sens, puis reconvertie */
+#[test]
+fn test_chess960_start_round_trip() {
+    let position = FenDialect::Fen.parse(START_960_CLASSICAL).unwrap();
+    let shredder = FenDialect::Shredder.write(&position).unwrap();
+    assert_eq!(shredder, START_960_SHREDDER);
+
+    let back = FenDialect::Shredder.parse(&shredder).unwrap();
+    assert_eq!(back, position);
+    assert_eq!(FenDialect::Fen.write(&back).unwrap(), START_960_CLASSICAL);
+    assert_eq!(FenDialect::Xfen.write(&back).unwrap(), START_960_CLASSICAL);
+    assert_eq!(FenDialect::Shredder.write(&back).unwrap(), shredder);
+}
+
+/* Test les formes sans compteurs */
+#[test]
+fn test_four_field_dialects() {
+    let position = FenDialect::Fen4
+        .parse("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3")
+        .unwrap();
+    assert_eq!(position.fullmove_number, 1);
+    assert_eq!(
+        FenDialect::Epd.write(&position).unwrap(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3"
+    );
+    let position = FenDialect::Epd
+        .parse("4k3/8/8/8/8/8/8/4K3 w - - hmvc 7; id \"x\";")
+        .unwrap();
+    assert_eq!(position.halfmove_clock, 7);
+    assert!(matches!(
+        FenDialect::Fen4.parse("4k3/8/8/8/8/8/8/4K3 w - - 0 1"),
+        Err(DialectError::FieldCount {
+            expected: 4,
+            found: 6
+        })
+    ));
+}
+
+/* Test les droits de roque que Shredder et X-FEN ne peuvent pas exprimer */
+#[test]
+fn test_invalid_castling_combinations() {
+    let position = FenDialect::Fen
+        .parse("4k3/8/8/8/8/8/8/4K3 w K - 0 1")
+        .unwrap();
+    assert_eq!(
+        FenDialect::Shredder
+            .write(&position)
+            .unwrap_err()
+            .to_string(),
+        "the white kingside castling right has no rook to castle with"
+    );
+    assert!(FenDialect::Xfen.write(&position).is_err());
+
+    let error = FenDialect::Shredder
+        .parse("rr2k3/8/8/8/8/8/8/4K3 b b - 0 1")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "castling right 'b' names an inner rook, which cannot be represented"
+    );
+    assert!(FenDialect::Shredder
+        .parse("4k3/8/8/8/8/8/8/R3K3 w HA - 0 1")
+        .is_err());
+    assert!(FenDialect::Shredder
+        .parse("4k3/8/8/8/8/8/8/R3K3 w AA - 0 1")
+        .is_err());
+}
@@ -0,0 +1,72 @@
+/* Tests de la comparaison de positions.
+
+Ces tests vérifient le calcul des différences et leur rendu côte à côte. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, render_diff, Color, PositionDiff, Square};
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const AFTER_E4_FEN: &str = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+
+/* Test le calcul des différences après un coup */
+#[test]
+fn test_position_diff_after_one_move() {
+    let before = parse_fen(START_FEN).unwrap();
+    let after = parse_fen(AFTER_E4_FEN).unwrap();
+    let diff = PositionDiff::between(&before, &after);
+
+    let squares: Vec<Square> = diff.squares.iter().map(|change| change.square).collect();
+    assert_eq!(
+        squares,
+        vec![
+            Square::from_name("e2").unwrap(),
+            Square::from_name("e4").unwrap()
+        ]
+    );
+    assert_eq!(diff.active_color, Some((Color::White, Color::Black)));
+    assert_eq!(
+        diff.en_passant,
+        Some((None, Some(Square::from_name("e3").unwrap())))
+    );
+    assert_eq!(diff.castling_rights, None);
+    assert_eq!(diff.halfmove_clock, None);
+    assert!(PositionDiff::between(&before, &before).is_empty());
+}
+
+/* Test que les marqueurs apparaissent exactement sur les cases modifiées */
+#[test]
+fn test_render_diff_marks_changed_squares() {
+    let before = parse_fen(START_FEN).unwrap();
+    let after = parse_fen(AFTER_E4_FEN).unwrap();
+    let rendered = render_diff(&before, &after);
+
+    let board_lines: Vec<&str> = rendered
+        .lines()
+        .filter(|line| line.contains(" | "))
+        .collect();
+    for line in &board_lines {
+        let expected = match line.chars().next() {
+            Some('4') | Some('2') => 4,
+            _ => 0,
+        };
+        assert_eq!(line.matches('*').count(), expected, "line {:?}", line);
+    }
+    assert!(rendered.contains("4 | · · · ·*·*· · · |   4 | · · · ·*♙*· · · |"));
+    assert!(rendered.contains("2 | ♙ ♙ ♙ ♙*♙*♙ ♙ ♙ |   2 | ♙ ♙ ♙ ♙*·*♙ ♙ ♙ |"));
+
+    assert!(rendered.contains("  e2: P -> empty\n"));
+    assert!(rendered.contains("  e4: empty -> P\n"));
+    assert!(rendered.contains("  Active color: White -> Black\n"));
+    assert!(rendered.contains("  En passant: - -> e3\n"));
+}
+
+/* Test le rendu de deux positions identiques */
+#[test]
+fn test_render_diff_identical() {
+    let position = parse_fen(START_FEN).unwrap();
+    let rendered = render_diff(&position, &position);
+
+    assert!(!rendered.contains('*'));
+    assert!(rendered.ends_with("Positions are identical\n"));
+}
@@ -0,0 +1,488 @@
+/* Tests du rendu textuel des positions.
+
+Ces tests vérifient l'orientation du plateau et le format des chaînes produites par le moteur de rendu. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::display::{
+    ANSI_BLACK_PIECE, ANSI_DARK_SQUARE, ANSI_LAST_MOVE_SQUARE, ANSI_LIGHT_SQUARE, ANSI_RESET,
+    ANSI_WHITE_PIECE,
+};
+use fen_parser::{
+    parse_fen, BoardStyle, BorderStyle, CoordinateSides, DisplayMode, DisplayOptions,
+    HighlightKind, Move, Orientation, PieceTheme, Square,
+};
+
+/* Extrait la grille des cases (sans cadre ni coordonnées) d'un rendu */
+fn board_grid(rendered: &str) -> Vec<Vec<char>> {
+    rendered
+        .lines()
+        .filter(|line| line.contains(" | "))
+        .map(|line| {
+            let inner = &line[line.find('|').unwrap() + 1..line.rfind('|').unwrap()];
+            inner.chars().filter(|c| *c != ' ').collect()
+        })
+        .collect()
+}
+
+/* Test l'orientation automatique selon le camp ayant le trait */
+#[test]
+fn test_side_to_move_orientation_flips_board() {
+    let placement = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R";
+    let white = parse_fen(&format!("{} w KQkq - 2 3", placement)).unwrap();
+    let black = parse_fen(&format!("{} b KQkq - 2 3", placement)).unwrap();
+    let options = DisplayOptions {
+        orientation: Orientation::SideToMove,
+        ..Default::default()
+    };
+
+    let white_view = white.render_board(&options);
+    let black_view = black.render_board(&options);
+
+    /* Le rendu auto suit l'orientation explicite du camp au trait */
+    let explicit_white = DisplayOptions {
+        orientation: Orientation::White,
+        ..Default::default()
+    };
+    let explicit_black = DisplayOptions {
+        orientation: Orientation::Black,
+        ..Default::default()
+    };
+    assert_eq!(white_view, white.render_board(&explicit_white));
+    assert_eq!(black_view, black.render_board(&explicit_black));
+
+    /* Les deux grilles sont l'image retournée l'une de l'autre */
+    let flipped: Vec<Vec<char>> = board_grid(&white_view)
+        .into_iter()
+        .rev()
+        .map(|rank| rank.into_iter().rev().collect())
+        .collect();
+    assert_eq!(flipped, board_grid(&black_view));
+    assert_ne!(white_view, black_view);
+
+    /* Les coordonnées sont inversées avec le plateau */
+    assert!(white_view.starts_with("  +-----------------+\n8 | "));
+    assert!(white_view.ends_with("    a b c d e f g h\n"));
+    assert!(black_view.starts_with("  +-----------------+\n1 | "));
+    assert!(black_view.ends_with("    h g f e d c b a\n"));
+}
+
+/* Rend une position clairsemée avec le style donné */
+fn render_sparse(style: BoardStyle) -> String {
+    let position = parse_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    position.render_board(&DisplayOptions {
+        style,
+        ..Default::default()
+    })
+}
+
+/* Test que le style par défaut reproduit l'affichage historique */
+#[test]
+fn test_default_style_golden() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let expected = concat!(
+        "  +-----------------+\n",
+        "8 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |\n",
+        "7 | ♙ ♙ ♙ ♙ ♙ ♙ ♙ ♙ |\n",
+        "6 | · · · · · · · · |\n",
+        "5 | · · · · · · · · |\n",
+        "4 | · · · · · · · · |\n",
+        "3 | · · · · · · · · |\n",
+        "2 | ♙ ♙ ♙ ♙ ♙ ♙ ♙ ♙ |\n",
+        "1 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |\n",
+        "  +-----------------+\n",
+        "    a b c d e f g h\n",
+    );
+    assert_eq!(position.render_board(&DisplayOptions::default()), expected);
+}
+
+/* Test le rendu sans bordure */
+#[test]
+fn test_no_border_golden() {
+    let style = BoardStyle {
+        border: BorderStyle::None,
+        empty_square_char: '.',
+        ..Default::default()
+    };
+    let expected = concat!(
+        "8 . . . . ♔ . . .\n",
+        "7 . . . . . . . .\n",
+        "6 . . . . . . . .\n",
+        "5 . . . . . . . .\n",
+        "4 . . . . . . . .\n",
+        "3 . . . . . . . .\n",
+        "2 . . . . ♙ . . .\n",
+        "1 . . . . ♔ . . .\n",
+        "  a b c d e f g h\n",
+    );
+    assert_eq!(render_sparse(style), expected);
+}
+
+/* Test le rendu avec coordonnées sur les quatre côtés */
+#[test]
+fn test_all_sides_coordinates_golden() {
+    let style = BoardStyle {
+        coordinate_sides: CoordinateSides::All,
+        empty_square_char: '.',
+        ..Default::default()
+    };
+    let expected = concat!(
+        "    a b c d e f g h\n",
+        "  +-----------------+\n",
+        "8 | . . . . ♔ . . . | 8\n",
+        "7 | . . . . . . . . | 7\n",
+        "6 | . . . . . . . . | 6\n",
+        "5 | . . . . . . . . | 5\n",
+        "4 | . . . . . . . . | 4\n",
+        "3 | . . . . . . . . | 3\n",
+        "2 | . . . . ♙ . . . | 2\n",
+        "1 | . . . . ♔ . . . | 1\n",
+        "  +-----------------+\n",
+        "    a b c d e f g h\n",
+    );
+    assert_eq!(render_sparse(style), expected);
+}
+
+/* Test le rendu sans coordonnées ni bordure, avec des cases élargies */
+#[test]
+fn test_bare_wide_squares_golden() {
+    let style = BoardStyle {
+        show_coordinates: false,
+        border: BorderStyle::None,
+        square_width: 3,
+        empty_square_char: '.',
+        ..Default::default()
+    };
+    let expected = concat!(
+        " .   .   .   .   ♔   .   .   .\n",
+        " .   .   .   .   .   .   .   .\n",
+        " .   .   .   .   .   .   .   .\n",
+        " .   .   .   .   .   .   .   .\n",
+        " .   .   .   .   .   .   .   .\n",
+        " .   .   .   .   .   .   .   .\n",
+        " .   .   .   .   ♙   .   .   .\n",
+        " .   .   .   .   ♔   .   .   .\n",
+    );
+    assert_eq!(render_sparse(style), expected);
+}
+
+/* Test le cadre ASCII sans coordonnées et avec des cases élargies */
+#[test]
+fn test_ascii_border_without_coordinates_golden() {
+    let style = BoardStyle {
+        show_coordinates: false,
+        square_width: 3,
+        empty_square_char: '.',
+        ..Default::default()
+    };
+    let expected = concat!(
+        "+---------------------------------+\n",
+        "|  .   .   .   .   ♔   .   .   .  |\n",
+        "|  .   .   .   .   .   .   .   .  |\n",
+        "|  .   .   .   .   .   .   .   .  |\n",
+        "|  .   .   .   .   .   .   .   .  |\n",
+        "|  .   .   .   .   .   .   .   .  |\n",
+        "|  .   .   .   .   .   .   .   .  |\n",
+        "|  .   .   .   .   ♙   .   .   .  |\n",
+        "|  .   .   .   .   ♔   .   .   .  |\n",
+        "+---------------------------------+\n",
+    );
+    assert_eq!(render_sparse(style), expected);
+}
+
+/* Test que le mode texte brut ne contient aucune séquence d'échappement */
+#[test]
+fn test_plain_mode_has_no_escape_sequences() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let rendered = position.render_board(&DisplayOptions::default());
+
+    assert!(!rendered.contains('\x1b'));
+}
+
+/* Test les codes SGR du damier coloré sur a1 (sombre) et b1 (claire) */
+#[test]
+fn test_color_mode_sgr_codes() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let rendered = position.render_board(&DisplayOptions {
+        mode: DisplayMode::Color,
+        ..Default::default()
+    });
+    let first_rank = rendered
+        .lines()
+        .find(|line| line.starts_with("1 "))
+        .unwrap();
+
+    /* Tour blanche en a1 sur case sombre, cavalier blanc en b1 sur case claire */
+    let a1 = format!("{}{} ♖ {}", ANSI_DARK_SQUARE, ANSI_WHITE_PIECE, ANSI_RESET);
+    let b1 = format!("{}{} ♘ {}", ANSI_LIGHT_SQUARE, ANSI_WHITE_PIECE, ANSI_RESET);
+    assert!(first_rank.starts_with(&format!("1 | {}{}", a1, b1)));
+    assert_eq!(first_rank.matches("\x1b[48;5;94m").count(), 4);
+    assert_eq!(first_rank.matches("\x1b[48;5;180m").count(), 4);
+
+    /* Les pièces noires utilisent l'avant-plan atténué */
+    let eighth_rank = rendered
+        .lines()
+        .find(|line| line.starts_with("8 "))
+        .unwrap();
+    assert!(eighth_rank.contains(&format!("{}{} ♖ ", ANSI_LIGHT_SQUARE, ANSI_BLACK_PIECE)));
+}
+
+/* Test le rendu avec le thème de lettres ASCII */
+#[test]
+fn test_ascii_theme() {
+    let style = BoardStyle {
+        theme: PieceTheme::ASCII,
+        empty_square_char: '.',
+        ..Default::default()
+    };
+    let rendered = render_sparse(style);
+
+    assert!(rendered.contains("8 | . . . . k . . . |"));
+    assert!(rendered.contains("2 | . . . . P . . . |"));
+    assert!(rendered.contains("1 | . . . . K . . . |"));
+}
+
+/* Test le thème Unicode, à choisir pour distinguer les couleurs par le glyphe */
+#[test]
+fn test_unicode_theme_is_opt_in() {
+    assert_eq!(BoardStyle::default().theme, PieceTheme::HOLLOW);
+    let style = BoardStyle {
+        theme: PieceTheme::UNICODE,
+        ..Default::default()
+    };
+    let rendered = render_sparse(style);
+
+    assert!(rendered.contains("8 | · · · · ♚ · · · |"));
+    assert!(rendered.contains("1 | · · · · ♔ · · · |"));
+}
+
+/* Test le thème inversé pour les terminaux sombres */
+#[test]
+fn test_inverted_unicode_theme() {
+    let style = BoardStyle {
+        theme: PieceTheme::UNICODE_INVERTED,
+        ..Default::default()
+    };
+    let rendered = render_sparse(style);
+
+    assert!(rendered.contains("8 | · · · · ♔ · · · |"));
+    assert!(rendered.contains("1 | · · · · ♚ · · · |"));
+}
+
+/* Test qu'un thème arbitraire défini par l'utilisateur est respecté */
+#[test]
+fn test_custom_theme() {
+    let theme = PieceTheme {
+        white: ["WK", "WQ", "WR", "WB", "WN", "WP"],
+        black: ["bk", "bq", "br", "bb", "bn", "bp"],
+    };
+    let style = BoardStyle {
+        theme,
+        square_width: 2,
+        empty_square_char: '.',
+        ..Default::default()
+    };
+    let rendered = render_sparse(style);
+
+    assert!(rendered.contains("8 | .  .  .  .  bk .  .  .  |"));
+    assert!(rendered.contains("2 | .  .  .  .  WP .  .  .  |"));
+    assert!(rendered.contains("1 | .  .  .  .  WK .  .  .  |"));
+}
+
+/* Test la mise en évidence du dernier coup en texte brut */
+#[test]
+fn test_last_move_highlight_plain_golden() {
+    let position =
+        parse_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let mut options = DisplayOptions::default();
+    options.highlight_move(Move::from_uci("e2e4").unwrap());
+    options.highlight(Square::from_name("e3").unwrap(), HighlightKind::EnPassant);
+
+    let expected = concat!(
+        "  +-----------------+\n",
+        "8 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |\n",
+        "7 | ♙ ♙ ♙ ♙ ♙ ♙ ♙ ♙ |\n",
+        "6 | · · · · · · · · |\n",
+        "5 | · · · · · · · · |\n",
+        "4 | · · · ·[♙]· · · |\n",
+        "3 | · · · ·<·>· · · |\n",
+        "2 | ♙ ♙ ♙ ♙[·]♙ ♙ ♙ |\n",
+        "1 | ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖ |\n",
+        "  +-----------------+\n",
+        "    a b c d e f g h\n",
+    );
+    assert_eq!(position.render_board(&options), expected);
+}
+
+/* Test deux cases voisines mises en évidence et une case du bord */
+#[test]
+fn test_adjacent_and_edge_highlights_plain() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+    let mut options = DisplayOptions::default();
+    options.highlight_move(Move::from_uci("e1d1").unwrap());
+    options.highlight(Square::from_name("a1").unwrap(), HighlightKind::Check);
+    options.highlight(Square::from_name("h8").unwrap(), HighlightKind::Marked);
+
+    let rendered = position.render_board(&options);
+    assert!(rendered.contains("1 |(♖)· ·[·|♔]· · ♖ |\n"));
+    assert!(rendered.contains("8 | · · · · ♔ · ·{·}|\n"));
+}
+
+/* Test la mise en évidence du dernier coup en mode coloré */
+#[test]
+fn test_last_move_highlight_ansi_golden() {
+    let position =
+        parse_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let mut options = DisplayOptions {
+        mode: DisplayMode::Color,
+        ..Default::default()
+    };
+    options.highlight_move(Move::from_uci("e2e4").unwrap());
+
+    let rendered = position.render_board(&options);
+    let fourth_rank = rendered
+        .lines()
+        .find(|line| line.starts_with("4 "))
+        .unwrap();
+    let second_rank = rendered
+        .lines()
+        .find(|line| line.starts_with("2 "))
+        .unwrap();
+
+    /* e4 prend le fond du dernier coup, d4 et f4 gardent le damier */
+    let expected_fourth = format!(
+        "4 | {light} · {reset}{dark} · {reset}{light} · {reset}{dark} · {reset}{hl}{white} ♙ {reset}{dark} · {reset}{light} · {reset}{dark} · {reset} |",
+        dark = ANSI_DARK_SQUARE,
+        light = ANSI_LIGHT_SQUARE,
+        hl = ANSI_LAST_MOVE_SQUARE,
+        white = ANSI_WHITE_PIECE,
+        reset = ANSI_RESET,
+    );
+    assert_eq!(fourth_rank, expected_fourth);
+    assert!(second_rank.contains(&format!("{} · {}", ANSI_LAST_MOVE_SQUARE, ANSI_RESET)));
+    assert_eq!(rendered.matches(ANSI_LAST_MOVE_SQUARE).count(), 2);
+    assert!(!rendered.contains(']'));
+}
+
+/* Test la grille Unicode à coins arrondis */
+#[test]
+fn test_rounded_grid_golden() {
+    let style = BoardStyle {
+        border: BorderStyle::Rounded,
+        empty_square_char: '.',
+        ..Default::default()
+    };
+    let expected = concat!(
+        "  ╭───┬───┬───┬───┬───┬───┬───┬───╮\n",
+        "8 │ . │ . │ . │ . │ ♔ │ . │ . │ . │\n",
+        "  ├───┼───┼───┼───┼───┼───┼───┼───┤\n",
+        "7 │ . │ . │ . │ . │ . │ . │ . │ . │\n",
+        "  ├───┼───┼───┼───┼───┼───┼───┼───┤\n",
+        "6 │ . │ . │ . │ . │ . │ . │ . │ . │\n",
+        "  ├───┼───┼───┼───┼───┼───┼───┼───┤\n",
+        "5 │ . │ . │ . │ . │ . │ . │ . │ . │\n",
+        "  ├───┼───┼───┼───┼───┼───┼───┼───┤\n",
+        "4 │ . │ . │ . │ . │ . │ . │ . │ . │\n",
+        "  ├───┼───┼───┼───┼───┼───┼───┼───┤\n",
+        "3 │ . │ . │ . │ . │ . │ . │ . │ . │\n",
+        "  ├───┼───┼───┼───┼───┼───┼───┼───┤\n",
+        "2 │ . │ . │ . │ . │ ♙ │ . │ . │ . │\n",
+        "  ├───┼───┼───┼───┼───┼───┼───┼───┤\n",
+        "1 │ . │ . │ . │ . │ ♔ │ . │ . │ . │\n",
+        "  ╰───┴───┴───┴───┴───┴───┴───┴───╯\n",
+        "    a   b   c   d   e   f   g   h\n",
+    );
+    assert_eq!(render_sparse(style), expected);
+}
+
+/* Test la grille Unicode épaisse, sans marge et avec des pièces ASCII */
+#[test]
+fn test_heavy_grid_golden() {
+    let style = BoardStyle {
+        border: BorderStyle::Heavy,
+        square_padding: 0,
+        empty_square_char: '.',
+        theme: PieceTheme::ASCII,
+        ..Default::default()
+    };
+    let expected = concat!(
+        "  ┏━┳━┳━┳━┳━┳━┳━┳━┓\n",
+        "8 ┃.┃.┃.┃.┃k┃.┃.┃.┃\n",
+        "  ┣━╋━╋━╋━╋━╋━╋━╋━┫\n",
+        "7 ┃.┃.┃.┃.┃.┃.┃.┃.┃\n",
+        "  ┣━╋━╋━╋━╋━╋━╋━╋━┫\n",
+        "6 ┃.┃.┃.┃.┃.┃.┃.┃.┃\n",
+        "  ┣━╋━╋━╋━╋━╋━╋━╋━┫\n",
+        "5 ┃.┃.┃.┃.┃.┃.┃.┃.┃\n",
+        "  ┣━╋━╋━╋━╋━╋━╋━╋━┫\n",
+        "4 ┃.┃.┃.┃.┃.┃.┃.┃.┃\n",
+        "  ┣━╋━╋━╋━╋━╋━╋━╋━┫\n",
+        "3 ┃.┃.┃.┃.┃.┃.┃.┃.┃\n",
+        "  ┣━╋━╋━╋━╋━╋━╋━╋━┫\n",
+        "2 ┃.┃.┃.┃.┃P┃.┃.┃.┃\n",
+        "  ┣━╋━╋━╋━╋━╋━╋━╋━┫\n",
+        "1 ┃.┃.┃.┃.┃K┃.┃.┃.┃\n",
+        "  ┗━┻━┻━┻━┻━┻━┻━┻━┛\n",
+        "   a b c d e f g h\n",
+    );
+    assert_eq!(render_sparse(style), expected);
+}
+
+/* Test que les crochets de mise en évidence remplacent la marge de la grille */
+#[test]
+fn test_grid_highlight_uses_padding() {
+    let position = parse_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    let mut options = DisplayOptions {
+        style: BoardStyle {
+            border: BorderStyle::Rounded,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    options.highlight(Square::from_name("e2").unwrap(), HighlightKind::LastMove);
+    let board = position.render_board(&options);
+    assert!(board.contains("│[♙]│"));
+}
+
+/* Test le pied de plateau résumant le matériel, blancs avec une qualité d'avance */
+#[test]
+fn test_material_footer_exchange_up() {
+    let position = parse_fen("1n2k3/pppp4/8/8/8/8/PPPP4/R3K3 w - - 0 1").unwrap();
+    let options = DisplayOptions {
+        style: BoardStyle {
+            show_material: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let board = position.render_board(&options);
+    assert!(board.ends_with("White: ♖×1 ♙×4  |  Black: ♘×1 ♙×4 (+2 pawns for White)\n"));
+}
+
+/* Test que le pied de matériel est absent par défaut */
+#[test]
+fn test_material_footer_off_by_default() {
+    let position = parse_fen("1n2k3/pppp4/8/8/8/8/PPPP4/R3K3 w - - 0 1").unwrap();
+    let board = position.render_board(&DisplayOptions::default());
+    assert!(!board.contains("White:"));
+    assert!(board.ends_with("a b c d e f g h\n"));
+}
+
+/* Test le plateau rendu par `{:#}`, la FEN restant la forme simple */
+#[test]
+fn test_alternate_display_renders_board() {
+    let fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+    let position = parse_fen(fen).unwrap();
+    assert_eq!(format!("{}", position), fen);
+    assert_eq!(format!("{:>35}", position), format!("{:>35}", fen));
+    let board = format!("{:#}", position);
+    assert_eq!(board, position.render_board(&DisplayOptions::default()));
+    assert!(board.ends_with("    a b c d e f g h\n"));
+    let padded = format!("{:*^#25}", position);
+    assert!(padded
+        .lines()
+        .all(|line| line.chars().count() == 25 && line.starts_with('*')));
+    assert!(format!("{:#.3}", position)
+        .lines()
+        .all(|line| line.chars().count() <= 3));
+}
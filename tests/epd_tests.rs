@@ -0,0 +1,62 @@
+/* Tests de la lecture des enregistrements EPD.
+
+Ces tests vérifient le découpage des opérations, les compteurs `hmvc`/`fmvn` et les erreurs. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{EpdError, EpdRecord, FenErrorKind, FenField};
+
+/* Test un enregistrement de la suite WAC, guillemets et `;` compris */
+#[test]
+fn test_parse_wac_record() {
+    let record = EpdRecord::parse(
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\"; c0 \"a; b\";",
+    )
+    .unwrap();
+
+    assert_eq!(
+        record.fields,
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - -"
+    );
+    assert_eq!(record.operation("bm"), Some("Qg6"));
+    assert_eq!(record.operation("id"), Some("WAC.001"));
+    assert_eq!(record.operation("c0"), Some("a; b"));
+    assert_eq!(record.operation("am"), None);
+    assert_eq!(
+        record.to_fen(),
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+    );
+}
+
+/* Test que `hmvc` et `fmvn` fixent les compteurs de la FEN */
+#[test]
+fn test_move_counters_from_opcodes() {
+    let record = EpdRecord::parse("4k3/8/8/8/8/8/8/4K3 b - - hmvc 12; fmvn 40").unwrap();
+    assert_eq!(record.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 12 40");
+
+    let error = EpdRecord::parse("4k3/8/8/8/8/8/8/4K3 b - - fmvn x;").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid fmvn operand 'x': expected a non-negative integer"
+    );
+}
+
+/* Test les enregistrements mal formés */
+#[test]
+fn test_malformed_records() {
+    match EpdRecord::parse("4k3/8/8/8/8/8/8/4K3 w") {
+        Err(EpdError::Fen(error)) => {
+            assert_eq!(error.field, FenField::CastlingRights);
+            assert_eq!(error.kind, FenErrorKind::Missing);
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(matches!(
+        EpdRecord::parse("4k3/8/8/8/8/8/8/4K3 w - - 9bm Qg6;"),
+        Err(EpdError::MalformedOperation(_))
+    ));
+    assert!(matches!(
+        EpdRecord::parse("4k3/8/8/8/8/8/8/4K3 w - - id \"open;"),
+        Err(EpdError::UnterminatedString(_))
+    ));
+}
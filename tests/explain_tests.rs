@@ -0,0 +1,38 @@
+/* Tests de l'explication des champs FEN.
+
+Ces tests figent la sortie de `explain` pour quelques positions de référence. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::parse_fen;
+
+/* Test l'explication de la position initiale */
+#[test]
+fn test_explain_start_position() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let expected = concat!(
+        "Piece placement: rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR\n",
+        "Active color:    w (White to move)\n",
+        "Castling rights: KQkq (White can castle kingside and queenside; Black can castle kingside and queenside)\n",
+        "En passant:      none\n",
+        "Halfmove clock:  0 (50-move rule: 0 half-moves since last capture/pawn move)\n",
+        "Fullmove number: 1\n",
+    );
+    assert_eq!(position.explain(), expected);
+}
+
+/* Test l'explication d'un milieu de partie avec prise en passant */
+#[test]
+fn test_explain_midgame_with_en_passant() {
+    let position =
+        parse_fen("r1bqk2r/pp3ppp/2n1pn2/2ppP3/3P4/2PB1N2/PP3PPP/R1BQK2R w Kq d6 12 9").unwrap();
+    let expected = concat!(
+        "Piece placement: r1bqk2r/pp3ppp/2n1pn2/2ppP3/3P4/2PB1N2/PP3PPP/R1BQK2R\n",
+        "Active color:    w (White to move)\n",
+        "Castling rights: Kq (White can castle kingside only; Black can castle queenside only)\n",
+        "En passant:      d6 (the pawn that just advanced two squares can be captured en passant)\n",
+        "Halfmove clock:  12 (50-move rule: 12 half-moves since last capture/pawn move)\n",
+        "Fullmove number: 9\n",
+    );
+    assert_eq!(position.explain(), expected);
+}
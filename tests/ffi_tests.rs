@@ -0,0 +1,119 @@
+/* Tests de l'interface C.
+
+Ces tests appellent les fonctions `extern "C"` comme le ferait un programme C et vérifient que l'en-tête `include/fen_parser.h` reste accordé au module `ffi`. */
+
+#![cfg(feature = "capi")]
+
+use fen_parser::ffi::*;
+use std::ffi::{c_char, CStr, CString};
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+fn parse(fen: &str) -> Result<FenPositionC, i32> {
+    let fen = CString::new(fen).unwrap();
+    let mut out = FenPositionC {
+        board: [0; 64],
+        active_color: 0,
+        castling: 0,
+        en_passant: 0,
+        halfmove_clock: 0,
+        fullmove_number: 0,
+    };
+    match unsafe { fen_parse(fen.as_ptr(), &mut out) } {
+        FEN_OK => Ok(out),
+        code => Err(code),
+    }
+}
+
+fn message(code: i32) -> String {
+    unsafe { CStr::from_ptr(fen_error_message(code)) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/* Test la lecture puis la réécriture d'une FEN à travers l'interface C */
+#[test]
+fn test_parse_and_format() {
+    let position = parse("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w Kq e6 0 2").unwrap();
+    assert_eq!(position.board[4], 1);
+    assert_eq!(position.board[60], 7);
+    assert_eq!(position.board[12], 0);
+    assert_eq!(position.castling, 0b1001);
+    assert_eq!(position.en_passant, 44);
+
+    let mut buf = [0 as c_char; 128];
+    let written = unsafe { fen_to_string(&position, buf.as_mut_ptr(), buf.len()) };
+    let fen = unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap();
+    assert_eq!(
+        fen,
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w Kq e6 0 2"
+    );
+    assert_eq!(written as usize, fen.len());
+
+    /* Un tampon trop petit reçoit une FEN tronquée et la longueur nécessaire */
+    let mut small = [0 as c_char; 9];
+    let needed = unsafe { fen_to_string(&position, small.as_mut_ptr(), small.len()) };
+    assert_eq!(needed as usize, fen.len());
+    assert_eq!(
+        unsafe { CStr::from_ptr(small.as_ptr()) }.to_bytes(),
+        b"rnbqkbnr"
+    );
+}
+
+/* Test les codes d'erreur et leurs messages */
+#[test]
+fn test_error_codes() {
+    assert_eq!(
+        parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1").unwrap_err(),
+        FEN_ERR_ACTIVE_COLOR
+    );
+    assert_eq!(
+        parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq").unwrap_err(),
+        FEN_ERR_MISSING_FIELD
+    );
+    assert_eq!(
+        unsafe { fen_parse(std::ptr::null(), std::ptr::null_mut()) },
+        FEN_ERR_NULL_POINTER
+    );
+    assert_eq!(message(FEN_ERR_ACTIVE_COLOR), "invalid active color");
+    assert_eq!(message(42), "unknown error code");
+
+    let mut position = parse(START).unwrap();
+    position.board[0] = 13;
+    let result = unsafe { fen_to_string(&position, std::ptr::null_mut(), 0) };
+    assert_eq!(result, FEN_ERR_INVALID_POSITION);
+}
+
+/* Test que l'en-tête déclare les mêmes fonctions, constantes et champs que le module */
+#[test]
+fn test_header_matches_module() {
+    let header = include_str!("../include/fen_parser.h");
+    for declaration in [
+        "int32_t fen_parse(const char *fen, FenPositionC *out);",
+        "int32_t fen_to_string(const FenPositionC *position, char *buf, size_t len);",
+        "const char *fen_error_message(int32_t code);",
+        "uint8_t board[64];",
+        "uint32_t fullmove_number;",
+    ] {
+        assert!(header.contains(declaration), "missing {}", declaration);
+    }
+    for (name, value) in [
+        ("FEN_OK", FEN_OK),
+        ("FEN_ERR_NULL_POINTER", FEN_ERR_NULL_POINTER),
+        ("FEN_ERR_INVALID_UTF8", FEN_ERR_INVALID_UTF8),
+        ("FEN_ERR_INVALID_POSITION", FEN_ERR_INVALID_POSITION),
+        ("FEN_ERR_PIECE_PLACEMENT", FEN_ERR_PIECE_PLACEMENT),
+        ("FEN_ERR_MISSING_FIELD", FEN_ERR_MISSING_FIELD),
+        ("FEN_ERR_OTHER", FEN_ERR_OTHER),
+        ("FEN_NO_EN_PASSANT", i32::from(FEN_NO_EN_PASSANT)),
+    ] {
+        assert!(
+            header.contains(&format!("#define {} {}\n", name, value)),
+            "{} should be {}",
+            name,
+            value
+        );
+    }
+    assert_eq!(std::mem::size_of::<FenPositionC>(), 76);
+}
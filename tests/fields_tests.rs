@@ -0,0 +1,80 @@
+/* Tests du découpage d'une FEN en champs.
+
+Ces tests vérifient que les champs renvoyés sont des tranches de la chaîne d'origine, et le traitement des FEN à quatre, cinq et sept champs. */
+
+use fen_parser::{split_fen_fields, FenErrorKind, FenField};
+
+/* Test que chaque champ est une tranche de la FEN d'origine, à sa position */
+#[test]
+fn test_fields_borrow_the_input() {
+    let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+    let fields = split_fen_fields(fen).unwrap();
+    assert_eq!(
+        fields.placement(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR"
+    );
+    assert_eq!(fields.active_color(), "b");
+    assert_eq!(fields.active_color_char(), 'b');
+    assert_eq!(fields.castling(), "KQkq");
+    assert_eq!(fields.en_passant(), "e3");
+    assert_eq!(fields.halfmove_clock(), Some("0"));
+    assert_eq!(fields.fullmove_number(), Some("1"));
+
+    for (field, offset) in [
+        (fields.placement(), 0),
+        (fields.active_color(), 46),
+        (fields.castling(), 48),
+        (fields.en_passant(), 53),
+        (fields.halfmove_clock().unwrap(), 56),
+        (fields.fullmove_number().unwrap(), 58),
+    ] {
+        assert_eq!(field.as_ptr(), fen[offset..].as_ptr(), "{field}");
+    }
+    assert_eq!(fields.get(FenField::CastlingRights), Some("KQkq"));
+    assert_eq!(fields.get(FenField::Whole), None);
+}
+
+/* Test que les suites d'espaces et les tabulations séparent les champs comme un seul espace */
+#[test]
+fn test_separator_runs_and_tabs() {
+    let fields = split_fen_fields("8/8/8/8/8/8/8/8  w\t-  -   12 40 ").unwrap();
+    assert_eq!(fields.active_color(), "w");
+    assert_eq!(fields.halfmove_clock(), Some("12"));
+    assert_eq!(fields.fullmove_number(), Some("40"));
+}
+
+/* Test les FEN à quatre et cinq champs, le refus de sept champs et les champs manquants */
+#[test]
+fn test_field_counts() {
+    /* Quatre champs, comme dans l'EPD : pas de compteurs */
+    let fields = split_fen_fields("8/8/8/8/8/8/8/8 w - -").unwrap();
+    assert_eq!(fields.en_passant(), "-");
+    assert_eq!(fields.halfmove_clock(), None);
+    assert_eq!(fields.fullmove_number(), None);
+
+    /* Cinq champs : le numéro de coup manque */
+    let fields = split_fen_fields("8/8/8/8/8/8/8/8 w - - 7").unwrap();
+    assert_eq!(fields.halfmove_clock(), Some("7"));
+    assert_eq!(fields.fullmove_number(), None);
+
+    /* Sept champs : refusé, l'erreur situe le texte en trop */
+    let fen = "8/8/8/8/8/8/8/8 w - - 0 1 extra";
+    let error = split_fen_fields(fen).unwrap_err();
+    assert_eq!(error.field, FenField::Whole);
+    assert_eq!(error.kind, FenErrorKind::UnexpectedChar);
+    assert_eq!(error.span, Some(26..31));
+    assert_eq!(error.found, "extra");
+
+    let error = split_fen_fields("8/8/8/8/8/8/8/8 w -").unwrap_err();
+    assert_eq!(
+        (error.field, error.kind),
+        (FenField::EnPassant, FenErrorKind::Missing)
+    );
+    let error = split_fen_fields("").unwrap_err();
+    assert_eq!(error.field, FenField::PiecePlacement);
+    let error = split_fen_fields(" 8/8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(
+        (error.field, error.kind),
+        (FenField::PiecePlacement, FenErrorKind::UnexpectedChar)
+    );
+}
@@ -0,0 +1,99 @@
+/* Tests des filtres sur le matériel.
+
+Ces tests vérifient chaque opérateur des contraintes de nombre, le joker `*` des pions, l'indifférence au camp fort sauf ancrage par `^`, la conjonction des conditions et les erreurs de lecture. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, FilterError, MaterialFilter};
+
+/* Tour et pion blancs contre tour */
+const KRP_KR: &str = "4k3/8/8/8/8/8/4P3/R3K2r w - - 0 1";
+/* Même matériel, couleurs échangées */
+const KR_KRP: &str = "r3k2R/4p3/8/8/8/8/8/4K3 b - - 0 1";
+
+/* Vrai si la position `fen` satisfait le filtre */
+fn matches(filter: &str, fen: &str) -> bool {
+    MaterialFilter::parse(filter)
+        .unwrap()
+        .matches(&parse_fen(fen).unwrap())
+}
+
+/* Test qu'une signature exacte s'applique au camp fort comme au camp faible */
+#[test]
+fn test_exact_signature_either_side() {
+    assert!(matches("KRPvKR", KRP_KR));
+    assert!(matches("KRPvKR", KR_KRP));
+    assert!(matches("KRvKRP", KRP_KR));
+    assert!(!matches("KRvKR", KRP_KR));
+    assert!(!matches("KRPPvKR", KRP_KR));
+}
+
+/* Test que `^` fixe le premier côté de la signature aux blancs */
+#[test]
+fn test_anchored_signature() {
+    assert!(matches("^KRPvKR", KRP_KR));
+    assert!(!matches("^KRPvKR", KR_KRP));
+    assert!(matches("^KRvKRP", KR_KRP));
+}
+
+/* Test le joker `*` : un nombre quelconque de pions, y compris aucun */
+#[test]
+fn test_pawn_wildcard() {
+    assert!(matches("KR*vKR", KRP_KR));
+    assert!(matches("KR*vKR*", KR_KRP));
+    assert!(!matches("KR*vKR", "4k3/4p3/8/8/8/8/4P3/R3K2r w - - 0 1"));
+    assert!(matches("KR*vKR*", "4k3/4p3/8/8/8/8/4P3/R3K2r w - - 0 1"));
+    assert!(!matches("KQ*vK", KRP_KR));
+}
+
+/* Test chaque opérateur de nombre sur la position initiale */
+#[test]
+fn test_count_operators() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    for (filter, expected) in [
+        ("B=2", true),
+        ("B==2", true),
+        ("b=1", false),
+        ("q!=1", false),
+        ("Q!=0", true),
+        ("P<8", false),
+        ("p<9", true),
+        ("N<=2", true),
+        ("n<=1", false),
+        ("R>1", true),
+        ("r>2", false),
+        ("K>=1", true),
+        ("k>=2", false),
+    ] {
+        assert_eq!(matches(filter, start), expected, "{}", filter);
+    }
+}
+
+/* Test la conjonction des conditions, séparées par des espaces ou des virgules */
+#[test]
+fn test_conditions_are_combined() {
+    let bishops = "2b1k3/8/8/8/8/8/8/2B1KB2 w - - 0 1";
+    assert!(matches("B=2 b=1", bishops));
+    assert!(matches("B=2,b>=1", bishops));
+    assert!(!matches("B=2 b=0", bishops));
+    assert!(matches("B>=2 KBBvKB", bishops));
+    assert!(!matches("B>=2 ^KBvKBB", bishops));
+}
+
+/* Test les erreurs : filtre vide, signature sans roi, nombre illisible, condition inconnue */
+#[test]
+fn test_invalid_filters() {
+    assert_eq!(MaterialFilter::parse(" , "), Err(FilterError::Empty));
+    assert_eq!(
+        MaterialFilter::parse("KRvR"),
+        Err(FilterError::InvalidSignature("KRvR".to_string()))
+    );
+    assert_eq!(
+        MaterialFilter::parse("B>=two"),
+        Err(FilterError::InvalidCondition("B>=two".to_string()))
+    );
+    assert_eq!(
+        MaterialFilter::parse("KR B=2 X=1"),
+        Err(FilterError::InvalidCondition("KR".to_string()))
+    );
+}
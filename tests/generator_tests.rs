@@ -0,0 +1,61 @@
+/* Tests de la génération pseudo-aléatoire de positions.
+
+Ces tests vérifient le déterminisme par graine et la cohérence des positions contraintes. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, CastlingRights, RandomPositionGenerator};
+
+fn fens(seed: u64, legal_ish: bool) -> Vec<String> {
+    let mut generator = RandomPositionGenerator::new(seed);
+    (0..20)
+        .map(|_| {
+            let position = if legal_ish {
+                generator.random_plausible()
+            } else {
+                generator.random_board()
+            };
+            position.to_fen()
+        })
+        .collect()
+}
+
+/* Test qu'une graine produit toujours la même suite, et deux graines des suites différentes */
+#[test]
+fn test_generation_is_deterministic_per_seed() {
+    assert_eq!(fens(42, false), fens(42, false));
+    assert_eq!(fens(42, true), fens(42, true));
+    assert_ne!(fens(42, false), fens(43, false));
+    for fen in fens(7, false) {
+        assert!(parse_fen(&fen).is_ok(), "{}", fen);
+    }
+}
+
+/* Test que les positions vraisemblables passent la validation sémantique, y compris avec
+ * roques et prise en passant */
+#[test]
+fn test_plausible_positions_validate() {
+    let mut castling = 0;
+    let mut en_passant = 0;
+    for position in RandomPositionGenerator::new(2024).take(10_000) {
+        assert_eq!(position.validate(), Ok(()), "{}", position.to_fen());
+        castling += usize::from(position.castling_rights != CastlingRights::none());
+        en_passant += usize::from(position.en_passant.is_some());
+    }
+    assert!(
+        castling > 1000,
+        "{} positions with castling rights",
+        castling
+    );
+    assert!(en_passant > 100, "{} positions with en passant", en_passant);
+}
+
+/* Test que l'itérateur suit la même suite que `random_plausible` */
+#[test]
+fn test_iterator_matches_random_plausible() {
+    let iterated: Vec<String> = RandomPositionGenerator::new(42)
+        .take(20)
+        .map(|position| position.to_fen())
+        .collect();
+    assert_eq!(iterated, fens(42, true));
+}
@@ -0,0 +1,66 @@
+/* Tests du rendu HTML des positions.
+
+Ces tests vérifient la structure de la table, les classes des cases et l'échappement. */
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::check_well_formed_xml;
+use fen_parser::{parse_fen, PieceTheme};
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/* Renvoie la balise <td> d'une case */
+fn cell<'a>(html: &'a str, square: &str) -> &'a str {
+    let marker = format!("data-square=\"{}\"", square);
+    let at = html.find(&marker).unwrap();
+    let start = html[..at].rfind("<td").unwrap();
+    let end = at + html[at..].find("</td>").unwrap();
+    &html[start..end]
+}
+
+/* Test le nombre de cellules et les classes des cases */
+#[test]
+fn test_html_cells_and_classes() {
+    let html = parse_fen(START_FEN).unwrap().to_html();
+
+    assert_eq!(html.matches("<td ").count(), 64);
+    assert_eq!(html.matches("<th scope=\"col\">").count(), 8);
+    assert_eq!(html.matches("<th scope=\"row\">").count(), 8);
+    assert!(cell(&html, "a2").starts_with("<td class=\"square light piece-white\""));
+    assert!(cell(&html, "a2").contains("data-piece=\"pawn\""));
+    assert!(cell(&html, "a3").starts_with("<td class=\"square dark\""));
+    assert!(cell(&html, "e8").contains("aria-label=\"e8 black king\">♚"));
+    check_well_formed_xml(&html).unwrap();
+}
+
+/* Test que l'attribut data-fen restitue la position */
+#[test]
+fn test_html_data_fen_round_trip() {
+    let fen = "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2";
+    let html = parse_fen(fen).unwrap().to_html();
+
+    let start = html.find("data-fen=\"").unwrap() + "data-fen=\"".len();
+    let end = start + html[start..].find('"').unwrap();
+    let data_fen = &html[start..end];
+    assert_eq!(data_fen, fen);
+    assert_eq!(parse_fen(data_fen).unwrap().to_fen(), fen);
+}
+
+/* Test l'échappement des glyphes d'un thème personnalisé */
+#[test]
+fn test_html_escapes_theme_glyphs() {
+    let theme = PieceTheme {
+        white: ["<K>", "&Q", "\"R\"", "'B'", "N", "P"],
+        black: ["k", "q", "r", "b", "n", "<script>"],
+    };
+    let html = parse_fen(START_FEN).unwrap().to_html_with_theme(&theme);
+
+    assert!(!html.contains("<script>"));
+    assert!(!html.contains("<K>"));
+    assert!(html.contains("&lt;K&gt;"));
+    assert!(html.contains("&amp;Q"));
+    assert!(html.contains("&quot;R&quot;"));
+    check_well_formed_xml(&html).unwrap();
+}
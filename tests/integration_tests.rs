@@ -2,8 +2,11 @@
 
 Ces tests vérifient le bon fonctionnement global du parseur avec des cas typiques et des cas limites. */
 
-use fen_parser::parse_fen;
-use fen_parser::types::{Color, Piece, PieceKind};
+use fen_parser::types::{CastlingRights, Color, Piece, PieceKind, Square};
+use fen_parser::{
+    parse_fen, parse_fen_all_errors, parse_fen_into, parse_fen_nom, parse_fen_with_mode,
+    ChessPosition, FenError, FenErrorKind, FenField, ParseMode, ParseWarning,
+};
 
 /* Test la position initiale standard */
 #[test]
@@ -12,10 +15,10 @@ fn test_initial_position() {
     let position = parse_fen(fen).unwrap();
 
     assert_eq!(position.active_color, Color::White);
-    assert_eq!(position.castling_rights.white_kingside, true);
-    assert_eq!(position.castling_rights.white_queenside, true);
-    assert_eq!(position.castling_rights.black_kingside, true);
-    assert_eq!(position.castling_rights.black_queenside, true);
+    assert!(position.castling_rights.white_kingside);
+    assert!(position.castling_rights.white_queenside);
+    assert!(position.castling_rights.black_kingside);
+    assert!(position.castling_rights.black_queenside);
     assert_eq!(position.en_passant, None);
     assert_eq!(position.halfmove_clock, 0);
     assert_eq!(position.fullmove_number, 1);
@@ -96,8 +99,10 @@ fn test_invalid_row_count() {
     ];
 
     for fen in fens {
-        assert!(
-            parse_fen(fen).is_err(),
+        let error = parse_fen(fen).unwrap_err();
+        assert_eq!(
+            (error.field, error.kind),
+            (FenField::PiecePlacement, FenErrorKind::WrongLength),
             "Should have failed for FEN: {}",
             fen
         );
@@ -112,13 +117,22 @@ fn test_invalid_piece_chars() {
         "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e9 0 1", /* 'e9' invalide */
     ];
 
-    for fen in fens {
-        assert!(
-            parse_fen(fen).is_err(),
-            "Should have failed for FEN: {}",
-            fen
-        );
-    }
+    let error = parse_fen(fens[0]).unwrap_err();
+    assert_eq!(
+        (error.field, error.kind),
+        (FenField::PiecePlacement, FenErrorKind::UnexpectedChar)
+    );
+
+    let error = parse_fen(fens[1]).unwrap_err();
+    assert_eq!(
+        (error.field, error.kind),
+        (FenField::EnPassant, FenErrorKind::UnexpectedChar)
+    );
+    assert_eq!(error.span, Some(51..53));
+    assert_eq!(error.snippet, "e9");
+    assert_eq!(error.context, "...KBNR w KQkq e9 0 1");
+    assert_eq!(error.expected, "'-' or a square on rank 3 or 6");
+    assert_eq!(error.found, "9");
 }
 
 /* Test des FEN avec des valeurs numériques invalides */
@@ -127,14 +141,21 @@ fn test_invalid_numbers() {
     let fens = [
         "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - a 1", /* demi-coups non numérique */
         "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 -1", /* tour négatif */
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 99999999999", /* dépassement */
+    ];
+    let expected = [
+        (FenField::HalfmoveClock, FenErrorKind::UnexpectedChar, "a"),
+        (FenField::FullmoveNumber, FenErrorKind::UnexpectedChar, "-"),
+        (
+            FenField::FullmoveNumber,
+            FenErrorKind::OutOfRange,
+            "99999999999",
+        ),
     ];
 
-    for fen in fens {
-        assert!(
-            parse_fen(fen).is_err(),
-            "Should have failed for FEN: {}",
-            fen
-        );
+    for (fen, expected) in fens.into_iter().zip(expected) {
+        let error = parse_fen(fen).unwrap_err();
+        assert_eq!((error.field, error.kind, error.found.as_str()), expected);
     }
 }
 
@@ -144,14 +165,42 @@ fn test_invalid_castling() {
     let fens = [
         "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQXkq - 0 1", /* 'X' invalide */
         "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkqk - 0 1", /* duplication */
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq- - 0 1", /* '-' après les lettres */
+    ];
+    let expected = [
+        (
+            FenErrorKind::UnexpectedChar,
+            "'-' or a combination of 'KQkq'",
+            "X",
+        ),
+        (FenErrorKind::Duplicate, "each of 'KQkq' at most once", "k"),
+        (
+            FenErrorKind::UnexpectedChar,
+            "'-' or a combination of 'KQkq'",
+            "-",
+        ),
     ];
 
-    for fen in fens {
-        assert!(
-            parse_fen(fen).is_err(),
-            "Should have failed for FEN: {}",
-            fen
-        );
+    for (fen, (kind, expected, found)) in fens.into_iter().zip(expected) {
+        let error = parse_fen(fen).unwrap_err();
+        assert_eq!(error.field, FenField::CastlingRights);
+        assert_eq!(error.kind, kind);
+        assert_eq!(error.expected, expected);
+        assert_eq!(error.found, found);
+    }
+}
+
+/* Test que `-` n'est accepté que comme champ de roque entier : mêlé aux lettres, avant ou après,
+ * c'est un caractère inattendu et non une longueur fautive */
+#[test]
+fn test_castling_dash_only_alone() {
+    for (castling, found) in [("K-", "-"), ("-KQ", "K"), ("Kq-", "-"), ("KQkq-", "-")] {
+        let fen = format!("4k3/8/8/8/8/8/8/4K3 w {} - 0 1", castling);
+        let error = parse_fen(&fen).unwrap_err();
+        assert_eq!(error.field, FenField::CastlingRights, "{}", fen);
+        assert_eq!(error.kind, FenErrorKind::UnexpectedChar, "{}", fen);
+        assert_eq!(error.found, found, "{}", fen);
+        assert_eq!(parse_fen_nom(&fen), Err(error), "{}", fen);
     }
 }
 
@@ -190,3 +239,268 @@ fn test_midgame_position() {
     assert_eq!(position.active_color, Color::White);
     assert!(position.castling_rights.has_any());
 }
+
+/* Test la réécriture FEN des positions analysées */
+#[test]
+fn test_to_fen_round_trip() {
+    let fens = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b - - 3 3",
+        "8/8/8/8/8/8/8/8 w - - 0 1",
+    ];
+
+    for fen in fens {
+        assert_eq!(parse_fen(fen).unwrap().to_fen(), fen);
+    }
+}
+
+/* Test la forme compacte sur une ligne et le Debug associé */
+#[test]
+fn test_compact_string_and_debug() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let compact =
+        "rnbqkbnr/pppppppp/......../......../......../......../PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    assert_eq!(position.to_compact_string(), compact);
+    assert_eq!(
+        format!("{:?}", position),
+        format!("ChessPosition({:?})", compact)
+    );
+    assert!(!format!("{:?}", position).contains('\n'));
+
+    let ep = parse_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2").unwrap();
+    assert_eq!(
+        ep.to_compact_string(),
+        "rnbqkbnr/pp.ppppp/......../..p...../....P.../......../PPPP.PPP/RNBQKBNR w KQkq c6 0 2"
+    );
+}
+
+/* Test que chaque champ invalide produit une erreur portant ce champ */
+#[test]
+fn test_error_field_per_field() {
+    let error = parse_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+    assert_eq!(error.field, FenField::ActiveColor);
+    assert_eq!(error.snippet, "x");
+    assert_eq!(error.context, "...8/8/8/8/8/8 x - - 0 1");
+
+    let error = parse_fen("8/8/8/8/8/8/8/8 w - e9 0 1").unwrap_err();
+    assert_eq!(error.field, FenField::EnPassant);
+    assert_eq!(error.context, "...8/8/8/8 w - e9 0 1");
+
+    assert_eq!(
+        parse_fen("8/8/8/8/8/8/8/8 w"),
+        Err(FenError::from((
+            FenField::CastlingRights,
+            FenErrorKind::Missing
+        )))
+    );
+}
+
+/* Test que le message d'erreur cite le fragment fautif et son contexte */
+#[test]
+fn test_error_message_quotes_snippet() {
+    let cases = [
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e9 0 1",
+            "invalid en passant square 'e9' in \"...KBNR w KQkq e9 0 1\"",
+        ),
+        (
+            "8/8/8/8/8/8/8/8 x - - 0 1",
+            "invalid active color 'x' in \"...8/8/8/8/8/8 x - - 0 1\"",
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 z",
+            "invalid fullmove number 'z' in \"... w KQkq - 0 z\"",
+        ),
+        (
+            "rnbqkbnr/ppppXppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "invalid piece placement 'rnbqkbnr/ppppXppp/8/8/8/8/PPPPPPPP/RNBQKBNR' in",
+        ),
+    ];
+
+    for (fen, expected) in cases {
+        let message = parse_fen(fen).unwrap_err().to_string();
+        assert!(message.starts_with(expected), "{}", message);
+    }
+}
+
+/* Test le couple attendu/trouvé des erreurs de parsing */
+#[test]
+fn test_expected_and_found() {
+    let expected_found = |fen: &str| {
+        let error = parse_fen(fen).unwrap_err();
+        (error.expected, error.found)
+    };
+
+    assert_eq!(
+        expected_found("rnbqkbnr/ppppxppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+        ("one of 'KQRBNPkqrbnp1-8'", "x".to_string())
+    );
+    assert_eq!(
+        expected_found("8/8/8/8/8/8/8/8 g - - 0 1"),
+        ("'w' or 'b'", "g".to_string())
+    );
+    assert_eq!(
+        expected_found("8/8/8/8/8/8/8/8 wKQkq - 0 1"),
+        ("' ' between fields", "K".to_string())
+    );
+}
+
+/* Test que toutes les erreurs indépendantes sont relevées en une passe */
+#[test]
+fn test_parse_fen_all_errors() {
+    let errors = parse_fen_all_errors("8/8/8/8/8/8/8/8 x KX e9 0 1").unwrap_err();
+
+    assert_eq!(errors.len(), 3);
+    let fields: Vec<FenField> = errors.iter().map(|error| error.field).collect();
+    assert_eq!(
+        fields,
+        [
+            FenField::ActiveColor,
+            FenField::CastlingRights,
+            FenField::EnPassant
+        ]
+    );
+
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_eq!(parse_fen_all_errors(fen), Ok(parse_fen(fen).unwrap()));
+}
+
+/* Test les compteurs écrits `-` : remplacés en mode tolérant, refusés en mode strict */
+#[test]
+fn test_dash_clocks_in_lenient_mode() {
+    let board = "4k3/8/8/8/8/8/8/4K3 w - -";
+    let halfmove = ParseWarning::DefaultedField {
+        field: FenField::HalfmoveClock,
+        value: 0,
+    };
+    let fullmove = ParseWarning::DefaultedField {
+        field: FenField::FullmoveNumber,
+        value: 1,
+    };
+    for (clocks, expected, warnings) in [
+        ("- -", (0, 1), vec![halfmove, fullmove]),
+        ("- 5", (0, 5), vec![halfmove]),
+        ("12 -", (12, 1), vec![fullmove]),
+        ("3 7", (3, 7), vec![]),
+    ] {
+        let fen = format!("{} {}", board, clocks);
+        let parsed = parse_fen_with_mode(&fen, ParseMode::Lenient).unwrap();
+        let position = parsed.position;
+        assert_eq!(
+            (position.halfmove_clock, position.fullmove_number),
+            expected,
+            "{}",
+            fen
+        );
+        assert_eq!(parsed.warnings, warnings, "{}", fen);
+
+        if !warnings.is_empty() {
+            let error = parse_fen_with_mode(&fen, ParseMode::Strict).unwrap_err();
+            assert_eq!(error, parse_fen(&fen).unwrap_err());
+            assert_eq!(error.kind, FenErrorKind::UnexpectedChar);
+        }
+    }
+    assert_eq!(
+        halfmove.to_string(),
+        "halfmove clock is '-', defaulted to 0"
+    );
+
+    let error = parse_fen_with_mode(&format!("{} -5 1", board), ParseMode::Lenient).unwrap_err();
+    assert_eq!(error.field, FenField::HalfmoveClock);
+}
+
+/* Test le filtrage d'une erreur sur la combinaison champ/nature, et les aides de transition */
+#[test]
+fn test_match_on_field_and_kind() {
+    let error = parse_fen("8/8/8/8/8/8/8/8 w KQkqK - 0 1").unwrap_err();
+    let summary = match (error.field, error.kind) {
+        (FenField::CastlingRights, FenErrorKind::Duplicate) => "repeated castling right",
+        (FenField::CastlingRights, _) => "other castling problem",
+        _ => "other problem",
+    };
+    assert_eq!(summary, "repeated castling right");
+    let message = error.to_string();
+    assert!(message.starts_with("invalid castling rights 'KQkqK' in "));
+    assert!(message.contains("\": duplicate, expected each of 'KQkq' at most once"));
+
+    #[allow(deprecated)]
+    {
+        assert!(error.is_invalid_castling_rights());
+        assert!(!error.is_invalid_en_passant());
+    }
+}
+
+/* Test que `parse_fen_into` écrase la position entière, cases vides comprises */
+#[test]
+fn test_parse_fen_into_overwrites_everything() {
+    let mut position =
+        parse_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq a3 17 40")
+            .unwrap();
+    for fen in [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        /* Forme non canonique, lue par le parseur nom */
+        "8/8/8/3k4/8/8/8/4K3\tb\t-\t-\t5\t9",
+    ] {
+        assert_eq!(parse_fen_into(fen, &mut position), Ok(()));
+        assert_eq!(position, parse_fen(fen).unwrap());
+    }
+}
+
+/* Test qu'une erreur laisse la position par défaut, même quand la lecture a échoué après avoir
+ * écrit le plateau */
+#[test]
+fn test_parse_fen_into_resets_on_error() {
+    for fen in [
+        "",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBN w KQkq - 0 1",
+        "8/8/8/3k4/8/8/8/4K3 w KQkq z9 0 1",
+        "8/8/8/3k4/8/8/8/4K3 b - - 0 99999999999",
+        "8/8/8/3k4/8/8/8/4K3 b",
+    ] {
+        let mut position = parse_fen("8/8/8/3k4/8/8/8/4K3 b - - 7 70").unwrap();
+        let error = parse_fen_into(fen, &mut position).unwrap_err();
+        assert_eq!(Err(error), parse_fen(fen), "{:?}", fen);
+        assert_eq!(position, ChessPosition::default(), "{:?}", fen);
+    }
+}
+
+/* Test le plateau sur 64 caractères : aller-retour et ordre des cases */
+#[test]
+fn test_flat_board_round_trip() {
+    let start = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let flat = start.to_flat_board();
+    assert_eq!(
+        flat,
+        "rnbqkbnrpppppppp................................PPPPPPPPRNBQKBNR"
+    );
+    let parsed =
+        ChessPosition::from_flat_board(&flat, Color::White, start.castling_rights, None).unwrap();
+    assert!(parsed == start);
+
+    let corners = format!("k{}K", "1".repeat(62));
+    let position =
+        ChessPosition::from_flat_board(&corners, Color::Black, CastlingRights::none(), None)
+            .unwrap();
+    let king_at = |name| position.piece_at(Square::from_name(name).unwrap());
+    assert_eq!(king_at("a8").map(|piece| piece.color), Some(Color::Black));
+    assert_eq!(king_at("h1").map(|piece| piece.color), Some(Color::White));
+    assert_eq!(position.to_fen(), "k7/8/8/8/8/8/8/7K b - - 0 1");
+}
+
+/* Test les erreurs du plateau sur 64 caractères : longueur et caractère inconnu */
+#[test]
+fn test_flat_board_errors() {
+    let error =
+        ChessPosition::from_flat_board("rnbqkbnr", Color::White, CastlingRights::none(), None)
+            .unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::WrongLength);
+    assert_eq!(error.found, "8 characters");
+
+    let flat = format!("{}x{}", ".".repeat(10), ".".repeat(53));
+    let error = ChessPosition::from_flat_board(&flat, Color::White, CastlingRights::none(), None)
+        .unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::UnexpectedChar);
+    assert_eq!(error.span, Some(10..11));
+}
@@ -0,0 +1,39 @@
+/* Tests des fonctions destinées aux liaisons JavaScript.
+
+Ces tests vérifient le JSON, le rapport de validation et le SVG renvoyés sous forme de chaînes. */
+
+#![cfg(feature = "js-strings")]
+
+use fen_parser::js_strings::{parse_fen_js, render_svg, validate_fen};
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/* Test la position en JSON et le message d'une FEN invalide */
+#[test]
+fn test_parse_fen_js() {
+    let json = parse_fen_js(START).unwrap();
+    assert!(json.starts_with("{\"fen\":\"rnbqkbnr/"));
+    assert!(parse_fen_js("8/8/8 w")
+        .unwrap_err()
+        .contains("piece placement"));
+}
+
+/* Test le rapport de validation, syntaxe et cohérence */
+#[test]
+fn test_validate_fen() {
+    assert_eq!(validate_fen(START), "{\"valid\":true,\"errors\":[]}");
+    assert_eq!(
+        validate_fen("4k3/8/8/8/8/8/8/4KK2 w - - 0 1"),
+        "{\"valid\":false,\"errors\":[\"white has 2 kings, expected exactly one\"]}"
+    );
+    assert!(validate_fen("4k3/8/8/8/8/8/8/4K3 x - - 0 1").starts_with("{\"valid\":false"));
+}
+
+/* Test le diagramme SVG, retourné ou non */
+#[test]
+fn test_render_svg() {
+    let svg = render_svg(START, false).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert_ne!(render_svg(START, true).unwrap(), svg);
+    assert!(render_svg("bad", false).is_err());
+}
@@ -0,0 +1,75 @@
+/* Tests du rendu JSON des positions.
+
+Ces tests relisent le JSON produit et vérifient quelques clés représentatives. */
+
+mod common;
+
+use common::{parse_json, Json};
+use fen_parser::parse_fen;
+
+/* Test le document JSON produit pour une position avec prise en passant */
+#[test]
+fn test_to_json_round_trips_through_parser() {
+    let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
+    let json = parse_json(&parse_fen(fen).unwrap().to_json()).unwrap();
+
+    assert_eq!(json.get("fen").and_then(Json::as_str), Some(fen));
+    assert_eq!(
+        json.get("active_color").and_then(Json::as_str),
+        Some("black")
+    );
+    assert_eq!(json.get("en_passant").and_then(Json::as_str), Some("e3"));
+    assert_eq!(json.get("halfmove_clock").and_then(Json::as_f64), Some(0.0));
+    assert_eq!(
+        json.get("fullmove_number").and_then(Json::as_f64),
+        Some(1.0)
+    );
+
+    let board = json.get("board").unwrap();
+    assert_eq!(board.as_object().unwrap().len(), 32);
+    let e1 = board.get("e1").unwrap();
+    assert_eq!(e1.get("color").and_then(Json::as_str), Some("white"));
+    assert_eq!(e1.get("kind").and_then(Json::as_str), Some("king"));
+    assert_eq!(
+        board
+            .get("e4")
+            .and_then(|p| p.get("kind"))
+            .and_then(Json::as_str),
+        Some("pawn")
+    );
+    assert_eq!(board.get("e2"), None);
+
+    let castling = json.get("castling").unwrap();
+    for key in [
+        "white_kingside",
+        "white_queenside",
+        "black_kingside",
+        "black_queenside",
+    ] {
+        assert_eq!(castling.get(key).and_then(Json::as_bool), Some(true));
+    }
+}
+
+/* Test les valeurs nulles et l'absence de droits de roque */
+#[test]
+fn test_to_json_without_castling_or_en_passant() {
+    let json = parse_json(
+        &parse_fen("8/8/8/4k3/8/8/8/4K3 w - - 7 40")
+            .unwrap()
+            .to_json(),
+    )
+    .unwrap();
+
+    assert_eq!(json.get("en_passant"), Some(&Json::Null));
+    assert_eq!(json.get("halfmove_clock").and_then(Json::as_f64), Some(7.0));
+    assert_eq!(
+        json.get("castling")
+            .and_then(|c| c.get("black_queenside"))
+            .and_then(Json::as_bool),
+        Some(false)
+    );
+    assert_eq!(
+        json.get("board").and_then(Json::as_object).map(|b| b.len()),
+        Some(2)
+    );
+}
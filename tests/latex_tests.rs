@@ -0,0 +1,55 @@
+/* Tests de l'export LaTeX.
+
+Ces tests vérifient que la FEN insérée dans les macros est intacte et que les options sont traduites. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, LatexForm, LatexOptions, Square};
+
+/* Extrait le contenu entre un préfixe et l'accolade fermante correspondante */
+fn braced<'a>(text: &'a str, prefix: &str) -> &'a str {
+    let start = text.find(prefix).unwrap() + prefix.len();
+    let end = start + text[start..].find('}').unwrap();
+    &text[start..end]
+}
+
+/* Test la forme \fenboard + \showboard */
+#[test]
+fn test_latex_fenboard_form() {
+    let position =
+        parse_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2").unwrap();
+    let latex = position.to_latex(&LatexOptions::default());
+
+    assert_eq!(
+        latex,
+        "\\fenboard{rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2}\n\\showboard\n"
+    );
+    assert_eq!(braced(&latex, "\\fenboard{"), position.to_fen());
+
+    let flipped = position.to_latex(&LatexOptions {
+        flipped: true,
+        ..Default::default()
+    });
+    assert!(flipped.ends_with("\\showinverseboard\n"));
+}
+
+/* Test la forme \chessboard avec cases marquées et orientation */
+#[test]
+fn test_latex_chessboard_form() {
+    let position = parse_fen("8/8/8/4k3/8/8/8/4K3 b - - 10 60").unwrap();
+    let latex = position.to_latex(&LatexOptions {
+        form: LatexForm::Chessboard,
+        marked_squares: vec![
+            Square::from_name("e4").unwrap(),
+            Square::from_name("d5").unwrap(),
+        ],
+        flipped: true,
+    });
+
+    assert_eq!(
+        latex,
+        "\\chessboard[setfen={8/8/8/4k3/8/8/8/4K3 b - - 10 60}, markfields={e4,d5}, inverse]\n"
+    );
+    /* Les caractères '/' et '-' de la FEN ne sont pas échappés */
+    assert_eq!(braced(&latex, "setfen={"), position.to_fen());
+}
@@ -0,0 +1,53 @@
+/* Tests du rendu Markdown des positions.
+
+Ces tests figent la table produite pour la position initiale et vérifient la variante en bloc de code. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, DisplayOptions, MarkdownStyle};
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/* Test la table Markdown de la position initiale */
+#[test]
+fn test_markdown_table_start_position() {
+    let markdown = parse_fen(START_FEN).unwrap().to_markdown();
+    let expected = concat!(
+        "|   | a | b | c | d | e | f | g | h |\n",
+        "|---|---|---|---|---|---|---|---|---|\n",
+        "| 8 | ♜ | ♞ | ♝ | ♛ | ♚ | ♝ | ♞ | ♜ |\n",
+        "| 7 | ♟ | ♟ | ♟ | ♟ | ♟ | ♟ | ♟ | ♟ |\n",
+        "| 6 |   |   |   |   |   |   |   |   |\n",
+        "| 5 |   |   |   |   |   |   |   |   |\n",
+        "| 4 |   |   |   |   |   |   |   |   |\n",
+        "| 3 |   |   |   |   |   |   |   |   |\n",
+        "| 2 | ♙ | ♙ | ♙ | ♙ | ♙ | ♙ | ♙ | ♙ |\n",
+        "| 1 | ♖ | ♘ | ♗ | ♕ | ♔ | ♗ | ♘ | ♖ |\n",
+    );
+    assert_eq!(markdown, expected);
+
+    /* 9 lignes de données (en-tête compris) de 9 colonnes chacune */
+    let rows: Vec<&str> = markdown
+        .lines()
+        .filter(|line| !line.starts_with("|---"))
+        .collect();
+    assert_eq!(rows.len(), 9);
+    for row in rows {
+        assert_eq!(row.matches('|').count() - 1, 9, "row {:?}", row);
+    }
+}
+
+/* Test la variante en bloc de code */
+#[test]
+fn test_markdown_code_block() {
+    let position = parse_fen(START_FEN).unwrap();
+    let markdown = position.to_markdown_with(MarkdownStyle::CodeBlock);
+
+    assert_eq!(
+        markdown,
+        format!(
+            "```\n{}```\n",
+            position.render_board(&DisplayOptions::default())
+        )
+    );
+}
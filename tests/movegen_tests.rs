@@ -0,0 +1,205 @@
+/* Tests de la génération et de l'application des coups.
+
+Ces tests comparent le nombre de coups légaux à des valeurs de référence (perft) vérifient les coups spéciaux et leur classement avant d'être joués. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{
+    CastlingSide, ChessPosition, Color, Move, MoveError, MoveKind, PieceKind, Rank, SquareSet,
+};
+
+/* Nombre de feuilles de l'arbre des coups légaux à la profondeur donnée */
+fn perft(position: &ChessPosition, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    position
+        .legal_moves()
+        .into_iter()
+        .map(|mv| {
+            let mut next = position.clone();
+            next.make_move(mv).unwrap();
+            perft(&next, depth - 1)
+        })
+        .sum()
+}
+
+fn play(fen: &str, moves: &[&str]) -> ChessPosition {
+    let mut position = ChessPosition::from_fen(fen).unwrap();
+    for uci in moves {
+        position.make_move(Move::from_uci(uci).unwrap()).unwrap();
+    }
+    position
+}
+
+/* Test les valeurs perft de référence de la position initiale et de « Kiwipete » */
+#[test]
+fn test_perft_reference_positions() {
+    assert_eq!(perft(&ChessPosition::default(), 3), 8902);
+    let kiwipete = ChessPosition::from_fen(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    )
+    .unwrap();
+    assert_eq!(perft(&kiwipete, 2), 2039);
+}
+
+/* Test le roque, la prise en passant et la promotion */
+#[test]
+fn test_special_moves() {
+    let castled = play("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", &["e1g1", "e8c8"]);
+    assert_eq!(castled.to_fen(), "2kr3r/8/8/8/8/8/8/R4RK1 w - - 2 2");
+
+    let en_passant = play("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", &["e5d6"]);
+    assert_eq!(en_passant.to_fen(), "4k3/8/3P4/8/8/8/8/4K3 b - - 0 1");
+
+    let promoted = play("4k3/1P6/8/8/8/8/8/4K3 w - - 5 9", &["b7b8n"]);
+    assert_eq!(promoted.to_fen(), "1N2k3/8/8/8/8/8/8/4K3 b - - 0 9");
+}
+
+/* Test qu'un coup refusé laisse la position inchangée */
+#[test]
+fn test_rejected_moves() {
+    let mut position = play("4k3/8/8/8/8/8/8/r3K3 w - - 0 1", &[]);
+    let before = position.clone();
+    assert!(position.in_check());
+
+    let into_check = Move::from_uci("e1f1").unwrap();
+    assert_eq!(
+        position.make_move(into_check),
+        Err(MoveError::Illegal(into_check))
+    );
+    let empty = Move::from_uci("b2b3").unwrap();
+    assert_eq!(
+        position.make_move(empty),
+        Err(MoveError::EmptySquare(empty.from))
+    );
+    let opponent = Move::from_uci("e8e7").unwrap();
+    assert_eq!(
+        position.make_move(opponent),
+        Err(MoveError::WrongColor(opponent.from))
+    );
+    assert_eq!(position, before);
+    assert_eq!(position.legal_moves().len(), 3);
+}
+
+/* Test qu'une case de prise en passant incohérente n'ouvre aucune prise */
+#[test]
+fn test_inconsistent_en_passant_square_is_ignored() {
+    /* e3 avec les blancs au trait : la « victime » serait le pion blanc e2 */
+    let mut position = ChessPosition::from_fen("4k3/8/8/8/8/8/3PP3/4K3 w - e3 0 1").unwrap();
+    let capture = Move::from_uci("d2e3").unwrap();
+    assert!(!position.legal_moves().contains(&capture));
+    assert!(!position.classify_move(capture).en_passant);
+    assert!(position.make_move(capture).is_err());
+    assert_eq!(position.to_fen(), "4k3/8/8/8/8/8/3PP3/4K3 w - e3 0 1");
+
+    /* Bonne rangée, mais aucun pion noir en e5 à prendre */
+    let position = ChessPosition::from_fen("4k3/8/8/3P4/8/8/8/4K3 w - e6 0 1").unwrap();
+    assert!(!position
+        .legal_moves()
+        .contains(&Move::from_uci("d5e6").unwrap()));
+}
+
+/* Test `perft` et `divide` de la bibliothèque contre les valeurs de référence */
+#[test]
+fn test_library_perft_and_divide() {
+    let start = ChessPosition::default();
+    assert_eq!(start.perft(0), 1);
+    assert_eq!(start.perft(3), 8902);
+    let divide = start.divide(2);
+    assert_eq!(divide.len(), 20);
+    assert_eq!(divide[0].0.to_string(), "a2a3");
+    assert!(divide.iter().all(|&(_, count)| count == 20));
+
+    let position = ChessPosition::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap();
+    assert_eq!(position.perft(3), 2812);
+}
+
+/* Test de la carte des attaques */
+#[test]
+fn test_attack_map() {
+    let start = ChessPosition::default();
+    /* Les blancs attaquent toute la 3e rangée et toutes les cases de la 2e ou de la 1re sauf a1 et h1 */
+    let white = start.attack_map(Color::White);
+    assert_eq!(
+        white & SquareSet::rank(Rank::Third),
+        SquareSet::rank(Rank::Third)
+    );
+    assert_eq!(white.len(), 22);
+    assert!(white
+        .iter()
+        .all(|square| start.is_attacked(square, Color::White)));
+
+    let lone = ChessPosition::from_fen("8/8/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let names: Vec<String> = lone
+        .attack_map(Color::White)
+        .iter()
+        .map(|square| square.to_string())
+        .collect();
+    assert_eq!(names, ["b1", "a2", "b2"]);
+    assert!(lone.attack_map(Color::Black).is_empty());
+}
+
+/* Test le classement d'un coup de chaque sorte, et d'un coup tranquille */
+#[test]
+fn test_classify_move() {
+    let classify = |fen: &str, uci: &str| {
+        ChessPosition::from_fen(fen)
+            .unwrap()
+            .classify_move(Move::from_uci(uci).unwrap())
+    };
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    assert_eq!(classify(start, "g1f3"), MoveKind::default());
+    assert!(classify(start, "g1f3").is_quiet());
+
+    let push = classify(start, "e2e4");
+    assert!(push.double_pawn_push && push.resets_halfmove_clock && !push.capture);
+    assert!(!classify(start, "e2e3").double_pawn_push);
+
+    let capture = classify("4k3/8/8/3p4/4N3/8/8/4K3 w - - 3 1", "e4d6");
+    assert!(!capture.capture);
+    let capture = classify("4k3/8/2p5/8/3N4/8/8/4K3 w - - 3 1", "d4c6");
+    assert!(capture.capture && capture.resets_halfmove_clock && !capture.en_passant);
+
+    let en_passant = classify("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2", "e5d6");
+    assert!(en_passant.capture && en_passant.en_passant);
+
+    let castles = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 5 1";
+    let kingside = classify(castles, "e1g1");
+    assert!(kingside.is_castle(CastlingSide::Kingside));
+    assert!(!kingside.is_castle(CastlingSide::Queenside));
+    assert!(!kingside.resets_halfmove_clock);
+    assert_eq!(
+        classify(castles, "e1c1").castle,
+        Some(CastlingSide::Queenside)
+    );
+    assert_eq!(classify(castles, "e1f1").castle, None);
+
+    let promotion = classify("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7b8n");
+    assert_eq!(promotion.promotion, Some(PieceKind::Knight));
+    assert!(promotion.capture && !promotion.is_quiet());
+
+    /* Case de départ vide : rien */
+    assert_eq!(classify(start, "e4e5"), MoveKind::default());
+}
+
+/* Test la lecture des coups en notation abrégée : roques, prise en passant, promotions,
+ * désambiguïsation, et refus des coups ambigus ou illisibles */
+#[test]
+fn test_move_from_san() {
+    let position =
+        ChessPosition::from_fen("r3k2r/1P6/8/3pP3/8/2N3N1/8/R3K2R w KQkq d6 0 1").unwrap();
+    let san = |text: &str| position.move_from_san(text).map(|mv| mv.to_string());
+    assert_eq!(san("O-O").as_deref(), Some("e1g1"));
+    assert_eq!(san("0-0-0+").as_deref(), Some("e1c1"));
+    assert_eq!(san("exd6").as_deref(), Some("e5d6"));
+    assert_eq!(san("bxa8=Q+").as_deref(), Some("b7a8q"));
+    assert_eq!(san("b8N").as_deref(), Some("b7b8n"));
+    assert_eq!(san("Nce4").as_deref(), Some("c3e4"));
+    assert_eq!(san("Nge4!?").as_deref(), Some("g3e4"));
+    /* Deux cavaliers peuvent aller en e4 ; une promotion doit nommer sa pièce */
+    assert_eq!(san("Ne4"), None);
+    assert_eq!(san("b8"), None);
+    assert_eq!(san("Ke3x"), None);
+}
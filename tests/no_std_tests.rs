@@ -0,0 +1,30 @@
+/* Tests de la bibliothèque sans `std`.
+
+Ce fichier est lui-même `no_std` : il n'utilise que `core` et `alloc`, comme un programme embarqué, et vérifie que la lecture et l'écriture des FEN en restent accessibles. `cargo test --no-default-features --test no_std_tests` l'exécute sans la fonctionnalité `std`. */
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::ToString;
+use fen_parser::{parse_fen, Color, FenErrorKind, FenField, PieceKind};
+
+/* Test l'aller-retour d'une FEN et le décompte du matériel */
+#[test]
+fn test_parse_and_write_without_std() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b Kq e3 0 1";
+    let position = parse_fen(fen).unwrap();
+    assert_eq!(position.to_fen(), fen);
+    assert_eq!(position.active_color, Color::Black);
+    assert_eq!(position.material().count(Color::White, PieceKind::Pawn), 8);
+    assert_ne!(position.repetition_key(), 0);
+}
+
+/* Test que les erreurs gardent leur champ, leur nature et leur message */
+#[test]
+fn test_errors_without_std() {
+    let error = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQKq - 0 1").unwrap_err();
+    assert_eq!(error.field, FenField::CastlingRights);
+    assert_eq!(error.kind, FenErrorKind::Duplicate);
+    assert!(error.to_string().starts_with("invalid castling rights"));
+}
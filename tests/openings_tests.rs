@@ -0,0 +1,82 @@
+/* Tests de la table des noms d'ouvertures.
+
+Ces tests lisent une petite table au format TSV et vérifient que les positions atteintes en jouant les coups y retrouvent leur nom, que les compteurs et une prise en passant inutilisable n'empêchent pas la correspondance, qu'une absence rend `None` et que les lignes invalides sont signalées avec leur numéro. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{final_position, parse_fen, OpeningBook, OpeningBookError};
+
+/* Colonnes dans l'ordre des tables ECO courantes ; positions écrites en FEN ou en EPD */
+const SAMPLE: &str = "eco\tname\tfen
+# Début de partie
+B00\tKing's Pawn\trnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1
+C20\tKing's Pawn Game\trnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2
+B20\tSicilian Defence\trnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq -
+
+C60\tRuy Lopez\tr1bqkbnr/pppp1ppp/2n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3
+C60\tSpanish Game\tr1bqkbnr/pppp1ppp/2n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3
+";
+
+/* Table lue depuis `SAMPLE` */
+fn sample() -> OpeningBook {
+    OpeningBook::from_tsv(SAMPLE.as_bytes()).unwrap()
+}
+
+/* Test que les positions atteintes en jouant les coups retrouvent leur nom et leur code ECO */
+#[test]
+fn test_lookup_after_moves() {
+    let book = sample();
+    assert_eq!(book.len(), 4);
+    let sicilian = final_position("1. e4 c5").unwrap();
+    assert_eq!(sicilian.to_fen().split(' ').nth(3), Some("c6"));
+    let entry = book.lookup(&sicilian).unwrap();
+    assert_eq!(entry.name, "Sicilian Defence");
+    assert_eq!(entry.eco.as_deref(), Some("B20"));
+    let king_pawn = final_position("1. e4").unwrap();
+    assert_eq!(book.lookup(&king_pawn).unwrap().name, "King's Pawn");
+}
+
+/* Test que les compteurs n'empêchent pas la correspondance et que la première entrée l'emporte */
+#[test]
+fn test_lookup_ignores_clocks_and_keeps_first_entry() {
+    let book = sample();
+    let ruy_lopez =
+        parse_fen("r1bqkbnr/pppp1ppp/2n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 17 42").unwrap();
+    assert_eq!(book.lookup(&ruy_lopez).unwrap().name, "Ruy Lopez");
+}
+
+/* Test qu'une position absente, ou une table vide, rend `None` */
+#[test]
+fn test_lookup_misses() {
+    let book = sample();
+    assert!(book.lookup(&final_position("1. d4").unwrap()).is_none());
+    assert!(OpeningBook::default()
+        .lookup(&final_position("1. e4 c5").unwrap())
+        .is_none());
+}
+
+/* Test l'ordre des colonnes sans en-tête, et les lignes invalides signalées avec leur numéro */
+#[test]
+fn test_default_columns_and_errors() {
+    let book = OpeningBook::from_tsv(
+        "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq - 0 1\tQueen's Pawn\n".as_bytes(),
+    )
+    .unwrap();
+    let entry = book.lookup(&final_position("1. d4").unwrap()).unwrap();
+    assert_eq!(
+        (entry.name.as_str(), entry.eco.as_deref()),
+        ("Queen's Pawn", None)
+    );
+
+    let error = OpeningBook::from_tsv("\n8/8/8 w - - 0 1\tBroken\n".as_bytes()).unwrap_err();
+    assert!(matches!(
+        error,
+        OpeningBookError::InvalidFen { line: 2, .. }
+    ));
+    assert!(error.to_string().starts_with("line 2: "));
+    let error = OpeningBook::from_tsv(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\t \n".as_bytes(),
+    )
+    .unwrap_err();
+    assert_eq!(error.to_string(), "line 1: missing opening name");
+}
@@ -0,0 +1,63 @@
+/* Tests de la lecture parallèle.
+
+Ces tests vérifient que les résultats parallèles sont exactement ceux de la lecture séquentielle, dans le même ordre et avec les mêmes numéros de ligne. */
+
+#![cfg(feature = "parallel")]
+
+use fen_parser::parallel::parse_fens_par_with;
+use fen_parser::{batch, par_parse_reader, parse_fen, parse_fens_par, RandomPositionGenerator};
+
+/* Corpus mêlant positions valides et lignes invalides */
+fn corpus() -> Vec<String> {
+    let mut generator = RandomPositionGenerator::new(423);
+    (0..1_000)
+        .map(|index| {
+            let fen = generator.random_plausible().to_fen();
+            match index % 7 {
+                0 => fen.replacen(' ', " x ", 1),
+                3 => fen[..fen.len() / 2].to_string(),
+                _ => fen,
+            }
+        })
+        .collect()
+}
+
+/* Test que l'ordre et les erreurs suivent la lecture séquentielle, quel que soit le nombre de fils */
+#[test]
+fn test_parallel_matches_sequential() {
+    let corpus = corpus();
+    let lines: Vec<&str> = corpus.iter().map(String::as_str).collect();
+    let sequential: Vec<_> = lines.iter().map(|line| parse_fen(line)).collect();
+    assert_eq!(parse_fens_par(&lines), sequential);
+    for threads in [0, 1, 2, 3, 8, 64, 5_000] {
+        assert_eq!(parse_fens_par_with(&lines, threads), sequential);
+    }
+    assert!(parse_fens_par(&[]).is_empty());
+}
+
+/* Test que la lecture d'un flux par blocs garde l'ordre et les numéros de ligne */
+#[test]
+fn test_par_parse_reader_keeps_lines() {
+    let mut text = String::from("# header\n");
+    for (index, fen) in corpus().iter().enumerate() {
+        text.push_str(fen);
+        text.push('\n');
+        if index % 10 == 0 {
+            text.push('\n');
+        }
+    }
+    let expected: Vec<_> = batch::entries(text.as_bytes())
+        .map(|entry| {
+            let entry = entry.unwrap();
+            let result = parse_fen(&entry.fen);
+            (entry, result)
+        })
+        .collect();
+    for block in [1, 7, 64, 10_000] {
+        let parallel: Vec<_> = par_parse_reader(text.as_bytes(), block)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(parallel, expected, "block of {} lines", block);
+    }
+    assert_eq!(expected[0].0.line, 2);
+}
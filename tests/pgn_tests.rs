@@ -0,0 +1,142 @@
+/* Tests de la lecture des parties PGN.
+
+Ces tests rejouent une partie réelle jusqu'à sa position finale, connue, puis vérifient que les commentaires, variantes et annotations sont ignorés, que la balise `FEN` fixe la position de départ et que l'erreur d'un coup illégal en donne le numéro et l'écriture. Le parcours coup par coup est vérifié sur une partie de quarante coups, jusqu'à l'arrêt sur une erreur. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::pgn::positions;
+use fen_parser::{final_position, PgnError};
+
+/* Morphy contre le duc de Brunswick et le comte Isouard, Paris 1858 */
+const OPERA_GAME: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1.e4 e5 2.Nf3 d6 3.d4 Bg4 4.dxe5 Bxf3 5.Qxf3 dxe5 6.Bc4 Nf6 7.Qb3 Qe7
+8.Nc3 c6 9.Bg5 b5 10.Nxb5 cxb5 11.Bxb5+ Nbd7 12.O-O-O Rd8 13.Rxd7 Rxd7
+14.Rd1 Qe6 15.Bxd7+ Nxd7 16.Qb8+ Nxb8 17.Rd8# 1-0
+"#;
+
+const OPERA_FINAL: &str = "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17";
+
+/* Test la position finale de la partie de l'Opéra */
+#[test]
+fn test_final_position_of_a_real_game() {
+    assert_eq!(final_position(OPERA_GAME).unwrap().to_fen(), OPERA_FINAL);
+}
+
+/* Test que commentaires, variantes imbriquées, annotations `$n` et `!?` sont ignorés */
+#[test]
+fn test_annotations_are_ignored() {
+    let annotated = "1. e4 {King's pawn} e5 $1 2. Nf3 (2. f4 exf4 (2... d5) 3. Nf3 {gambit}) \
+                     2... Nc6 ; Italian next\n3. Bc4!? *";
+    assert_eq!(
+        final_position(annotated).unwrap().to_fen(),
+        "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3"
+    );
+}
+
+/* Test que la balise `FEN` fixe la position de départ, et le refus d'une balise invalide */
+#[test]
+fn test_fen_tag_sets_the_start() {
+    let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 40\"]\n\n40... Kd7 41. e4 Kc6";
+    assert_eq!(
+        final_position(pgn).unwrap().to_fen(),
+        "8/8/2k5/8/4P3/8/8/4K3 w - - 1 42"
+    );
+    assert!(matches!(
+        final_position("[FEN \"8/8/8 w - - 0 1\"]\n1. e4"),
+        Err(PgnError::InvalidFen(_))
+    ));
+}
+
+/* Test le numéro et l'écriture d'un coup illégal, et le commentaire jamais refermé */
+#[test]
+fn test_illegal_move_reports_number_and_token() {
+    let error = final_position("1. e4 e5 2. Nf3 Nc6 3. Bb5 Nxe4 4. O-O").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "move 3... Nxe4 is not legal in this position"
+    );
+    assert_eq!(
+        final_position("1. e4 {unfinished").unwrap_err(),
+        PgnError::Unclosed('{')
+    );
+}
+
+/* Test que les roques écrits avec des zéros sont lus et rendus comme `O-O` et `O-O-O` */
+#[test]
+fn test_zero_castling_is_normalised() {
+    let pgn =
+        "1. d4 d5 2. Nc3 Nc6 3. Bf4 Bf5 4. Qd2 Qd7 5. 0-0-0 e6 6. e3 Nf6 7. Nf3 Be7 8. Be2 0-0!";
+    assert_eq!(
+        final_position(pgn).unwrap().to_fen(),
+        "r4rk1/pppqbppp/2n1pn2/3p1b2/3P1B2/2N1PN2/PPPQBPPP/2KR3R w - - 5 9"
+    );
+    let sans: Vec<String> = positions(pgn)
+        .unwrap()
+        .map(|ply| ply.unwrap().0.to_string())
+        .collect();
+    assert_eq!(sans[8], "O-O-O");
+    assert_eq!(sans[15], "O-O!");
+}
+
+/* Partie espagnole de quarante coups, dont les cavaliers vont et viennent après 3...a6 */
+fn forty_moves() -> String {
+    let mut pgn = String::from("1. e4 e5 2. Nf3 Nc6 3. Bb5 a6");
+    for number in 4..=40 {
+        let pair = if number % 2 == 0 {
+            "Ng1 Nf6"
+        } else {
+            "Nf3 Ng8"
+        };
+        pgn.push_str(&format!(" {}. {}", number, pair));
+    }
+    pgn
+}
+
+/* Test les quatre-vingts coups d'une partie, chacun avec la position qui le suit */
+#[test]
+fn test_positions_after_each_move() {
+    let game = forty_moves();
+    let plies: Vec<_> = positions(&game).unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(plies.len(), 80);
+    let (san, position) = &plies[5];
+    assert_eq!(san.as_str(), "a6");
+    assert_eq!(
+        position.to_fen(),
+        "r1bqkbnr/1ppp1ppp/p1n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 0 4"
+    );
+    let (san, position) = &plies[39];
+    assert_eq!(san.to_string(), "Nf6");
+    assert_eq!(
+        position.to_fen(),
+        "r1bqkb1r/1ppp1ppp/p1n2n2/1B2p3/4P3/8/PPPP1PPP/RNBQK1NR w KQkq - 34 21"
+    );
+    assert_eq!(
+        plies[79].1.to_fen(),
+        final_position(&game).unwrap().to_fen()
+    );
+}
+
+/* Test l'arrêt du parcours après la première erreur, et le refus initial d'une balise `FEN`
+ * invalide */
+#[test]
+fn test_positions_stop_at_the_first_error() {
+    let game = forty_moves().replace("12. Ng1 Nf6", "12. Ng1 Nf5");
+    let mut plies = positions(&game).unwrap();
+    assert_eq!(plies.by_ref().take_while(Result::is_ok).count(), 23);
+    assert!(plies.next().is_none());
+    let error = positions(&game).unwrap().find_map(Result::err).unwrap();
+    assert_eq!(
+        error.to_string(),
+        "move 12... Nf5 is not legal in this position"
+    );
+    assert!(matches!(
+        positions("[FEN \"8/8/8 w - - 0 1\"]"),
+        Err(PgnError::InvalidFen(_))
+    ));
+}
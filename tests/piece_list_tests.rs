@@ -0,0 +1,64 @@
+/* Tests de la notation en liste de pièces.
+
+Ces tests vérifient que la liste écrite se relit à l'identique, que les variantes d'écriture usuelles sont acceptées et qu'une case occupée deux fois est refusée en la nommant. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, ChessPosition, Color, FenErrorKind};
+
+/* Test que la liste écrite se relit à l'identique, dans l'ordre roi, dame, tours, ..., pions */
+#[test]
+fn test_piece_list_round_trip() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w - - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ] {
+        let position = parse_fen(fen).unwrap();
+        let list = position.to_piece_list();
+        let reread = ChessPosition::from_piece_list(&list).unwrap();
+        assert_eq!(reread.to_fen(), fen, "{}", list);
+    }
+
+    let position = parse_fen("3rk3/p7/8/8/8/8/PP6/3R2K1 w - - 0 1").unwrap();
+    assert_eq!(
+        position.to_piece_list(),
+        "White: Kg1, Rd1, Pa2, Pb2; Black: Ke8, Rd8, Pa7"
+    );
+}
+
+/* Test les variantes acceptées : casse, virgules absentes, pions sans `P`, camps dans le désordre */
+#[test]
+fn test_piece_list_lenient_spelling() {
+    let canonical =
+        ChessPosition::from_piece_list("White: Kg1, Rd1, Pa2, Pb2; Black: Kg8, Rd8, Pa7").unwrap();
+    for text in [
+        "white: Kg1 Rd1 a2 b2 BLACK: Kg8 Rd8 a7",
+        "White:Kg1,Rd1,Pa2,b2;Black:Kg8,Rd8,Pa7",
+        "Black: Pa7, Rd8, Kg8\nWhite: Pb2, Pa2, Rd1, Kg1",
+    ] {
+        assert_eq!(
+            ChessPosition::from_piece_list(text).unwrap(),
+            canonical,
+            "{}",
+            text
+        );
+    }
+    assert_eq!(canonical.active_color, Color::White);
+    assert_eq!(canonical.fullmove_number, 1);
+}
+
+/* Test les refus : case occupée deux fois, pièce inconnue, camp non nommé */
+#[test]
+fn test_piece_list_errors() {
+    let error = ChessPosition::from_piece_list("White: Kg1, Re4; Black: Kg8, e4").unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::Duplicate);
+    assert!(error.found.contains("e4"), "{}", error.found);
+
+    let error = ChessPosition::from_piece_list("White: Kg1, Xe4; Black: Kg8").unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::UnexpectedChar);
+    assert_eq!(error.found, "Xe4");
+
+    let error = ChessPosition::from_piece_list("Kg1; Black: Kg8").unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::Missing);
+}
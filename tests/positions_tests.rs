@@ -0,0 +1,41 @@
+/* Tests des positions de référence.
+
+Ces tests vérifient que chaque position se lit, se réécrit à l'identique et a le nombre de coups légaux annoncé. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::positions::{self, TestPosition};
+use fen_parser::ChessPosition;
+
+/* Test que chaque position se réécrit à l'identique, a le perft(1) annoncé et se retrouve par son nom */
+#[test]
+fn test_every_position_parses_and_round_trips() {
+    for position in positions::ALL {
+        assert_eq!(
+            position.position().to_fen(),
+            position.fen,
+            "{}",
+            position.name
+        );
+        assert_eq!(
+            position.position().perft(1),
+            position.perft1,
+            "{}",
+            position.name
+        );
+        assert!(std::ptr::eq(
+            positions::by_name(position.name).unwrap(),
+            position
+        ));
+    }
+}
+
+/* Test quelques positions nommées : départ, perft(2) de Kiwipete, échec du piège de Lasker */
+#[test]
+fn test_named_positions() {
+    let start: &TestPosition = &positions::STARTPOS;
+    assert_eq!(start.position(), &ChessPosition::default());
+    assert_eq!(positions::KIWIPETE.position().perft(2), 2039);
+    assert!(positions::LASKER_TRAP.position().in_check());
+    assert!(positions::by_name("unknown").is_none());
+}
@@ -0,0 +1,37 @@
+/* Tests de l'évaluation par tables de cases.
+
+Ces tests vérifient la symétrie de l'évaluation entre les deux camps et l'effet des tables sur le placement des pièces. */
+
+#![cfg(feature = "eval")]
+
+use fen_parser::analysis::pst_eval;
+use fen_parser::parse_fen;
+
+/* Test que la position miroir obtient exactement le score opposé */
+#[test]
+fn test_pst_eval_is_symmetric() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r1bq1rk1/pp2bppp/2n2n2/3p4/3P4/2NB1N2/PP3PPP/R1BQ1RK1 w - - 0 10",
+        "8/5k2/3p4/1p1Pp2p/pP2Pp1P/P4P1K/8/8 b - - 99 50",
+        "4k3/8/8/8/3Q4/8/8/4K2R w K - 0 1",
+    ] {
+        let position = parse_fen(fen).unwrap();
+        assert_eq!(
+            pst_eval(&position.color_flipped()),
+            -pst_eval(&position),
+            "{fen}"
+        );
+    }
+    let start = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(pst_eval(&start), 0);
+}
+
+/* Test qu'un cavalier centralisé vaut plus qu'un cavalier au bord */
+#[test]
+fn test_centralized_knight_beats_rim_knight() {
+    let center = parse_fen("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1").unwrap();
+    let rim = parse_fen("4k3/8/8/8/N7/8/8/4K3 w - - 0 1").unwrap();
+    assert!(pst_eval(&center) > pst_eval(&rim));
+    assert!(pst_eval(&rim) > 0);
+}
@@ -0,0 +1,45 @@
+/* Tests du modèle de la classe Python `Position`.
+
+Ces tests construisent et interrogent des positions comme le feraient les liaisons Python. */
+
+#![cfg(feature = "py-model")]
+
+use fen_parser::py_model::Position;
+
+/* Test la construction et les accesseurs */
+#[test]
+fn test_position_accessors() {
+    let mut position =
+        Position::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(position.turn(), "white");
+    assert_eq!(position.castling(), "KQkq");
+    assert_eq!(position.board().len(), 32);
+    assert_eq!(position.board()["e1"], "K");
+    assert!(!position.is_check());
+
+    for uci in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+        position.apply_uci(uci).unwrap();
+    }
+    assert!(position.is_check());
+    assert_eq!(
+        position.fen(),
+        "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"
+    );
+}
+
+/* Test les messages que porterait la `ValueError` */
+#[test]
+fn test_position_errors() {
+    let error = Position::from_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+    assert!(error.starts_with("invalid active color"));
+    let mut position = Position::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(
+        position.apply_uci("e1e3").unwrap_err(),
+        "e1e3 is not legal in this position"
+    );
+    assert_eq!(
+        position.apply_uci("zz").unwrap_err(),
+        "'zz' is not a valid UCI move"
+    );
+    assert_eq!(position.fen(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+}
@@ -0,0 +1,138 @@
+/* Tests de la réparation des FEN.
+
+Ces tests vérifient chaque correction séparément, une FEN qui en demande trois, qu'une FEN valide passe sans alerte et qu'une FEN irréparable est refusée. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, repair_fen, FenField, RepairWarning, Square};
+
+/* Répare une FEN en vérifiant que le résultat se lit et passe la validation */
+fn repaired(fen: &str) -> (String, Vec<RepairWarning>) {
+    let (fixed, warnings) = repair_fen(fen).unwrap();
+    let position = parse_fen(&fixed).unwrap();
+    assert_eq!(position.validate(), Ok(()), "{fixed}");
+    (fixed, warnings)
+}
+
+/* Test qu'une FEN valide ressort telle quelle, sans alerte */
+#[test]
+fn test_valid_fen_is_unchanged() {
+    let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
+    assert_eq!(repaired(fen), (fen.to_string(), vec![]));
+}
+
+/* Test le retrait d'un numéro de coup placé devant la FEN */
+#[test]
+fn test_move_number_prefix() {
+    let (fixed, warnings) = repaired("12... 4k3/8/8/8/8/8/8/4K3 b - - 0 12");
+    assert_eq!(fixed, "4k3/8/8/8/8/8/8/4K3 b - - 0 12");
+    assert_eq!(
+        warnings,
+        [RepairWarning::StrippedPrefix {
+            prefix: "12...".to_string()
+        }]
+    );
+}
+
+/* Test les compteurs absents ou illisibles, remplacés par 0 et 1 */
+#[test]
+fn test_missing_clocks() {
+    let (fixed, warnings) = repaired("4k3/8/8/8/8/8/8/4K3 w - -");
+    assert_eq!(fixed, "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+    assert_eq!(
+        warnings,
+        [
+            RepairWarning::DefaultedClock {
+                field: FenField::HalfmoveClock,
+                value: 0
+            },
+            RepairWarning::DefaultedClock {
+                field: FenField::FullmoveNumber,
+                value: 1
+            },
+        ]
+    );
+    let (fixed, warnings) = repaired("4k3/8/8/8/8/8/8/4K3 w - - 3 -");
+    assert_eq!(fixed, "4k3/8/8/8/8/8/8/4K3 w - - 3 1");
+    assert_eq!(warnings.len(), 1);
+}
+
+/* Test l'échange des compteurs quand le demi-coup dépasse ce que permet le numéro de coup */
+#[test]
+fn test_swapped_clocks() {
+    let (fixed, warnings) = repaired("4k3/8/8/8/8/8/8/4K3 w - - 30 12");
+    assert_eq!(fixed, "4k3/8/8/8/8/8/8/4K3 w - - 12 30");
+    assert_eq!(
+        warnings,
+        [RepairWarning::SwappedClocks {
+            halfmove_clock: 12,
+            fullmove_number: 30
+        }]
+    );
+    /* Possible dans l'ordre donné : rien n'est échangé */
+    assert_eq!(repaired("4k3/8/8/8/8/8/8/4K3 w - - 12 30").1, []);
+}
+
+/* Test le retrait des roques sans roi ni tour à leur case d'origine */
+#[test]
+fn test_castling_without_pieces() {
+    let (fixed, warnings) = repaired("4k3/8/8/8/8/8/8/4K2R w KQkq - 0 1");
+    assert_eq!(fixed, "4k3/8/8/8/8/8/8/4K2R w K - 0 1");
+    assert_eq!(
+        warnings,
+        ['Q', 'k', 'q'].map(|right| RepairWarning::RemovedCastlingRight { right })
+    );
+}
+
+/* Test le retrait d'une case de prise en passant qu'aucun pion ne peut prendre */
+#[test]
+fn test_uncapturable_en_passant() {
+    let square = Square::from_name("e3").unwrap();
+    /* Cohérente, mais aucun pion noir à côté de e4 */
+    let (fixed, warnings) = repaired("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1");
+    assert_eq!(fixed, "4k3/8/8/8/4P3/8/8/4K3 b - - 0 1");
+    assert_eq!(warnings, [RepairWarning::RemovedEnPassant { square }]);
+    /* Incohérente : aucun pion blanc en e4 */
+    let (_, warnings) = repaired("4k3/8/8/8/8/8/8/4K3 b - e3 0 1");
+    assert_eq!(warnings, [RepairWarning::RemovedEnPassant { square }]);
+    /* Prenable : gardée */
+    assert_eq!(repaired("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").1, []);
+}
+
+/* Test le numéro de coup 0, ramené à 1 */
+#[test]
+fn test_fullmove_zero() {
+    let (fixed, warnings) = repaired("4k3/8/8/8/8/8/8/4K3 w - - 0 0");
+    assert_eq!(fixed, "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+    assert_eq!(warnings, [RepairWarning::FullmoveZero]);
+}
+
+/* Test une FEN qui demande trois corrections, alertes dans l'ordre */
+#[test]
+fn test_compound_repair() {
+    let (fixed, warnings) = repaired("1. r3k3/8/8/8/8/8/8/4K3 w KQkq -");
+    assert_eq!(fixed, "r3k3/8/8/8/8/8/8/4K3 w q - 0 1");
+    assert!(matches!(warnings[0], RepairWarning::StrippedPrefix { .. }));
+    assert!(matches!(
+        warnings[1..3],
+        [
+            RepairWarning::DefaultedClock { .. },
+            RepairWarning::DefaultedClock { .. }
+        ]
+    ));
+    assert_eq!(
+        warnings[3..],
+        ['K', 'Q', 'k'].map(|right| RepairWarning::RemovedCastlingRight { right })
+    );
+}
+
+/* Test le refus d'une FEN irréparable : roi manquant, trait illisible, texte quelconque */
+#[test]
+fn test_beyond_repair() {
+    let error = repair_fen("4k3/8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(error.field, FenField::PiecePlacement);
+    assert!(error.found.contains("kings"));
+    let error = repair_fen("4k3/8/8/8/8/8/8/4K3 x - - 0 1").unwrap_err();
+    assert_eq!(error.field, FenField::ActiveColor);
+    assert!(repair_fen("not a fen").is_err());
+}
@@ -0,0 +1,53 @@
+/* Tests de la session interactive.
+
+Ces tests rejouent des scripts de commandes et comparent la sortie capturée. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::ReplSession;
+
+/* Exécute un script et renvoie la sortie produite */
+fn run_script(script: &str) -> String {
+    let mut session = ReplSession::default();
+    let mut out = Vec::new();
+    session.run(script.as_bytes(), &mut out, false).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+/* Test le chargement, les coups, l'annulation et l'affichage de la FEN */
+#[test]
+fn test_moves_and_undo() {
+    let output = run_script("move e2e4 e7e5\nundo\nfen\nfen 4k3/8/8/8/8/8/8/4K3 w - - 0 1\nundo\n");
+    assert_eq!(
+        output,
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2\n\
+         rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\n\
+         rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\n\
+         4k3/8/8/8/8/8/8/4K3 w - - 0 1\n\
+         rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\n"
+    );
+}
+
+/* Test que les erreurs sont signalées sans interrompre la session, et que `quit` l'arrête */
+#[test]
+fn test_errors_do_not_stop_the_session() {
+    let output = run_script("bogus\nmove e2e5\nundo\nfen 8/8 w\nflip\nquit\nfen\n");
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "error: unknown command 'bogus' (type help)");
+    assert_eq!(
+        lines[1],
+        "error: move 1: e2e5 is not legal in this position"
+    );
+    assert_eq!(lines[3], "error: nothing to undo");
+    assert!(lines[4].starts_with("error: invalid piece placement"));
+    assert_eq!(lines[5], "black at the bottom");
+    assert_eq!(lines.len(), 6);
+}
+
+/* Test le rendu du plateau retourné, dernier coup mis en évidence */
+#[test]
+fn test_show_after_flip() {
+    let output = run_script("move e2e4\nflip\nshow\n");
+    assert!(output.contains("  h g f e d c b a\n"));
+    assert!(output.ends_with("Black to move\n"));
+}
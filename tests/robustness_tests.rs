@@ -0,0 +1,186 @@
+/* Tests de robustesse du parseur face à des entrées hostiles.
+
+Ces tests parcourent un corpus déterministe de chaînes pathologiques et vérifient qu'aucune ne provoque de panique. */
+
+use fen_parser::{parse_fen, parse_fen_all_errors, parse_fen_nom, FenErrorKind, FenField};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/* Caractères insérés à chaque position de la FEN de départ */
+const HOSTILE_CHARS: [&str; 12] = [
+    "", " ", "\t", "\n", "\0", "/", "9", "0", "é", "\u{FFFD}", "♔", "🦀",
+];
+
+/* Vérifie qu'une entrée est rejetée ou acceptée sans panique, que les erreurs sont cohérentes
+ * et que la voie rapide de `parse_fen` rend exactement le résultat du parseur nom */
+fn check(input: &str) {
+    assert_eq!(parse_fen(input), parse_fen_nom(input), "input: {:?}", input);
+    if let Err(error) = parse_fen(input) {
+        if let Some(span) = error.span.clone() {
+            assert_eq!(&input[span], error.snippet, "input: {:?}", input);
+        }
+        let _ = error.to_string();
+    }
+    if let Err(errors) = parse_fen_all_errors(input) {
+        assert!(!errors.is_empty());
+        for error in errors {
+            let _ = error.to_string();
+        }
+    }
+}
+
+/* Test des entrées dégénérées */
+#[test]
+fn test_pathological_inputs_do_not_panic() {
+    let lossy = String::from_utf8_lossy(&[0xff, 0xfe, 0xed, 0xa0, 0x80, b' ', b'w']).into_owned();
+    let corpus = [
+        String::new(),
+        " ".repeat(8),
+        "/".repeat(64),
+        "/".repeat(7) + " w - - 0 1",
+        "8".repeat(10_000),
+        "p".repeat(10_000) + " w - - 0 1",
+        format!("{} ", START_FEN).repeat(500),
+        lossy.clone(),
+        format!("{} {}", lossy, START_FEN),
+        "🦀/🦀/🦀/🦀/🦀/🦀/🦀/🦀 w - - 0 1".to_string(),
+        "8/8/8/8/8/8/8/8 ♔ ♕♖ é3 ٣ ١".to_string(),
+        "8/8/8/8/8/8/8/8 w - - 0 1 extra fields here".to_string(),
+        "255255255/8/8/8/8/8/8/8 w - - 0 1".to_string(),
+        "8/8/8/8/8/8/8/8 w KQkq e3 99999999999999999999 1".to_string(),
+        "44/8/8/8/8/8/8/8 w - - 0 1".to_string(),
+        "08/8/8/8/8/8/8/8 w - - 0 1".to_string(),
+        "8/8/8/8/8/8/8/8 w -K - 0 1".to_string(),
+        "8/8/8/8/8/8/8/8 w - - 007 4294967296".to_string(),
+        "8/8/8/8/8/8/8/8  w - - 0 1 ".to_string(),
+    ];
+
+    for input in &corpus {
+        check(input);
+    }
+}
+
+/* Test de mutations systématiques de la FEN de départ : insertion, remplacement et troncature */
+#[test]
+fn test_mutated_start_fen_does_not_panic() {
+    let boundaries: Vec<usize> = START_FEN
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([START_FEN.len()])
+        .collect();
+
+    for &at in &boundaries {
+        check(&START_FEN[..at]);
+        check(&START_FEN[at..]);
+        for hostile in HOSTILE_CHARS {
+            check(&format!(
+                "{}{}{}",
+                &START_FEN[..at],
+                hostile,
+                &START_FEN[at..]
+            ));
+            if at < START_FEN.len() {
+                check(&format!(
+                    "{}{}{}",
+                    &START_FEN[..at],
+                    hostile,
+                    &START_FEN[at + 1..]
+                ));
+            }
+        }
+    }
+}
+
+/* Test que le corpus d'amorçage de `fuzz/` respecte les propriétés vérifiées par les cibles */
+#[test]
+fn test_fuzz_seed_corpus() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus");
+    let mut seen = 0;
+    for target in ["parse_fen", "parse_epd", "round_trip"] {
+        for entry in fs::read_dir(corpus.join(target)).unwrap() {
+            let input = fs::read_to_string(entry.unwrap().path()).unwrap();
+            check(&input);
+            if let Ok(position) = parse_fen(&input) {
+                assert_eq!(parse_fen(&position.to_fen()), Ok(position), "{:?}", input);
+            }
+            seen += 1;
+        }
+    }
+    assert!(seen >= 30, "{} corpus entries", seen);
+}
+
+/* Test qu'un placement démesuré est refusé dès le neuvième rang */
+#[test]
+fn test_oversized_placement_fails_fast() {
+    let input = "8/".repeat(500_000) + " w - - 0 1";
+    let started = Instant::now();
+    let error = parse_fen(&input).unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(error.field, FenField::PiecePlacement);
+    assert_eq!(error.kind, FenErrorKind::WrongLength);
+    assert_eq!(error.found, "500001 ranks");
+
+    let error = parse_fen(&("p".repeat(500_000) + " w - - 0 1")).unwrap_err();
+    assert_eq!(error.kind, FenErrorKind::WrongLength);
+}
+
+/* Test qu'une prise en passant hors de l'échiquier, posée directement dans la structure,
+ * n'entraîne aucun débordement à l'affichage */
+#[cfg(feature = "std")]
+#[test]
+fn test_out_of_range_en_passant_does_not_panic() {
+    use fen_parser::ChessPosition;
+
+    let position = ChessPosition {
+        en_passant: Some((200, 250)),
+        ..ChessPosition::default()
+    };
+    position.display_ascii();
+    assert!(position.to_fen().contains(" - 0 1"));
+    let _ = position.explain();
+    let _ = position.to_json();
+}
+
+/* Test que la voie rapide et le parseur nom s'accordent sur des FEN générées et sur leurs
+ * variantes non canoniques */
+#[cfg(feature = "std")]
+#[test]
+fn test_fast_path_matches_nom_parser() {
+    let mut generator = fen_parser::RandomPositionGenerator::new(421);
+    for index in 0..2_000 {
+        let position = if index % 2 == 0 {
+            generator.random_board()
+        } else {
+            generator.random_plausible()
+        };
+        let fen = position.to_fen();
+        assert_eq!(parse_fen(&fen), Ok(position));
+        check(&fen);
+        check(&fen.replace(' ', "\t"));
+        check(&format!("{} extra", fen));
+        check(&fen.replacen("1", "01", 1));
+    }
+}
+
+/* Test qu'un nombre de cases vides est un seul chiffre de 1 à 8 : `0`, `9`, `08` et `44` sont
+ * refusés sur le caractère fautif, par les deux voies du parseur */
+#[test]
+fn test_empty_square_count_is_a_single_digit() {
+    for (rank, found) in [
+        ("0", "0"),
+        ("9", "9"),
+        ("08", "0"),
+        ("p0p6", "0"),
+        ("44", "4"),
+    ] {
+        let fen = format!("{}/8/8/8/8/8/8/8 w - - 0 1", rank);
+        check(&fen);
+        let error = parse_fen(&fen).unwrap_err();
+        assert_eq!(error.field, FenField::PiecePlacement, "{}", fen);
+        assert_eq!(error.kind, FenErrorKind::UnexpectedChar, "{}", fen);
+        assert_eq!(error.found, found, "{}", fen);
+    }
+}
@@ -0,0 +1,57 @@
+/* Tests du parseur sur des FEN générées.
+
+Ces tests vérifient que toute FEN produite par `valid_fen()` se lit et se réécrit à l'identique, et que toute FEN altérée par `almost_valid_fen()` est refusée sans panique. */
+
+#![cfg(feature = "strategies")]
+
+use fen_parser::parse_fen;
+use fen_parser::strategies::{almost_valid_fen, samples, valid_fen, Mutation, Strategy, TestRng};
+
+const CASES: usize = 2000;
+
+/* Test que les FEN générées se lisent et se réécrivent à l'identique, avec les 16 roques possibles */
+#[test]
+fn test_valid_strings_always_parse() {
+    let mut castling = std::collections::HashSet::new();
+    let mut empty_ranks = 0;
+    for fen in samples(valid_fen(), 7).take(CASES) {
+        let position = parse_fen(&fen).unwrap_or_else(|error| panic!("{}: {}", fen, error));
+        assert_eq!(position.to_fen(), fen);
+        castling.insert(fen.split(' ').nth(2).unwrap().to_string());
+        empty_ranks += fen
+            .split(' ')
+            .next()
+            .unwrap()
+            .split('/')
+            .filter(|rank| *rank == "8")
+            .count();
+    }
+    assert_eq!(castling.len(), 16);
+    assert!(empty_ranks > 0);
+}
+
+/* Test que les FEN altérées une fois sont refusées par une erreur, sans panique */
+#[test]
+fn test_single_mutation_strings_are_rejected_without_panic() {
+    for fen in samples(almost_valid_fen(), 11).take(CASES) {
+        let result = std::panic::catch_unwind(|| parse_fen(&fen));
+        assert!(
+            matches!(result, Ok(Err(_))),
+            "{} was not rejected cleanly",
+            fen
+        );
+    }
+}
+
+/* Test que chaque altération, appliquée seule, rend invalide une FEN valide */
+#[test]
+fn test_each_mutation_breaks_a_valid_fen() {
+    let mut rng = TestRng::new(3);
+    for mutation in Mutation::ALL {
+        for _ in 0..200 {
+            let fen = valid_fen().generate(&mut rng);
+            let mutated = mutation.apply(&fen, &mut rng);
+            assert!(parse_fen(&mutated).is_err(), "{:?}: {}", mutation, mutated);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+/* Tests du rendu SVG des positions.
+
+Ces tests comptent les éléments produits et vérifient que le document est bien formé. */
+
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::check_well_formed_xml;
+use fen_parser::{parse_fen, SvgOptions};
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/* Test la structure du SVG de la position initiale */
+#[test]
+fn test_svg_structure() {
+    let svg = parse_fen(START_FEN)
+        .unwrap()
+        .render_svg(&SvgOptions::default());
+
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"360\" height=\"360\"")
+    );
+    assert_eq!(svg.matches("<rect ").count(), 64);
+    assert_eq!(svg.matches("class=\"piece\"").count(), 32);
+    assert_eq!(svg.matches("class=\"coordinate\"").count(), 0);
+    check_well_formed_xml(&svg).unwrap();
+}
+
+/* Test les options : taille, couleurs, coordonnées et orientation */
+#[test]
+fn test_svg_options() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let options = SvgOptions {
+        square_size: 50,
+        light_color: "#ffffff".to_string(),
+        dark_color: "#000000".to_string(),
+        coordinates: true,
+        flipped: false,
+        ..Default::default()
+    };
+    let svg = position.render_svg(&options);
+
+    /* a1 (sombre) est en bas à gauche, après la marge des coordonnées */
+    assert!(svg.contains("width=\"450\""));
+    assert!(svg.contains("<rect x=\"25\" y=\"375\" width=\"50\" height=\"50\" fill=\"#000000\"/>"));
+    assert_eq!(svg.matches("class=\"piece\"").count(), 2);
+    assert_eq!(svg.matches("class=\"coordinate\"").count(), 16);
+    check_well_formed_xml(&svg).unwrap();
+
+    /* Plateau retourné : a1 passe en haut à droite */
+    let flipped = position.render_svg(&SvgOptions {
+        flipped: true,
+        ..options
+    });
+    assert!(
+        flipped.contains("<rect x=\"375\" y=\"25\" width=\"50\" height=\"50\" fill=\"#000000\"/>")
+    );
+    check_well_formed_xml(&flipped).unwrap();
+}
+
+/* Test l'échappement des valeurs fournies par l'utilisateur */
+#[test]
+fn test_svg_escapes_user_values() {
+    let svg = parse_fen(START_FEN).unwrap().render_svg(&SvgOptions {
+        light_color: "\"><script>".to_string(),
+        ..Default::default()
+    });
+
+    assert!(!svg.contains("<script>"));
+    check_well_formed_xml(&svg).unwrap();
+}
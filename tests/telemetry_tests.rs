@@ -0,0 +1,75 @@
+/* Tests de la télémétrie.
+
+Ces tests vérifient, avec un abonné qui garde les événements, qu'une lecture ouvre son intervalle avec la longueur de l'entrée, qu'un écart toléré produit un avertissement et qu'une erreur produit un événement portant ses champs. */
+
+#![cfg(feature = "telemetry")]
+
+use fen_parser::telemetry::{with_subscriber, EventKind, Level, Recorder};
+use fen_parser::{parse_fen, parse_fen_with_mode, ChessPosition, Move, ParseMode};
+
+/* Test l'intervalle `parse_fen` et l'avertissement d'un écart toléré en mode `Lenient` */
+#[test]
+fn test_lenient_fixup_emits_warning() {
+    let recorder = Recorder::default();
+    let fen = "4k3/8/8/8/8/8/8/4K3 w - - - 7";
+    with_subscriber(recorder.clone(), || {
+        parse_fen_with_mode(fen, ParseMode::Lenient).unwrap();
+    });
+    let events = recorder.events();
+
+    assert_eq!(events[0].kind, EventKind::SpanEnter);
+    assert_eq!(events[0].level, Level::Debug);
+    assert_eq!(events[0].name, "parse_fen");
+    assert_eq!(
+        events[0].field("input_len"),
+        Some(fen.len().to_string().as_str())
+    );
+
+    let warning = &events[1];
+    assert_eq!(
+        (warning.kind, warning.level),
+        (EventKind::Event, Level::Warn)
+    );
+    assert_eq!(warning.name, "lenient fixup");
+    assert_eq!(warning.span, Some("parse_fen"));
+    assert_eq!(warning.field("field"), Some("halfmove clock"));
+    assert_eq!(warning.field("value"), Some("0"));
+
+    assert_eq!(events[2].kind, EventKind::SpanExit);
+    assert!(events[2].field("elapsed_us").is_some());
+    assert_eq!(events.len(), 3);
+}
+
+/* Test les événements d'erreur d'une FEN refusée et d'un coup illégal, et leur absence sans abonné */
+#[test]
+fn test_failure_emits_error_fields() {
+    let recorder = Recorder::default();
+    with_subscriber(recorder.clone(), || {
+        parse_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+        let mut position = ChessPosition::default();
+        position
+            .make_move(Move::from_uci("e2e5").unwrap())
+            .unwrap_err();
+    });
+    let events = recorder.events();
+    let errors: Vec<_> = events
+        .iter()
+        .filter(|event| event.level == Level::Error)
+        .collect();
+    assert_eq!(errors.len(), 2);
+
+    assert_eq!(errors[0].name, "invalid FEN");
+    assert_eq!(errors[0].span, Some("parse_fen"));
+    assert_eq!(errors[0].field("field"), Some("active color"));
+    assert_eq!(errors[0].field("kind"), Some("unexpected character"));
+    assert_eq!(errors[0].field("span"), Some("16..17"));
+    assert_eq!(errors[0].field("found"), Some("x"));
+
+    assert_eq!(errors[1].name, "move rejected");
+    assert_eq!(errors[1].span, Some("make_move"));
+    assert_eq!(errors[1].field("move"), Some("e2e5"));
+
+    /* Hors de `with_subscriber`, plus rien n'est reçu */
+    parse_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+    assert_eq!(recorder.events().len(), events.len());
+}
@@ -0,0 +1,37 @@
+/* Tests du corpus de mesure.
+
+Ces tests vérifient que le corpus mixte compilé dans la bibliothèque est celui du fichier, et qu'il mêle bien positions valides et invalides. */
+
+#![cfg(feature = "test-support")]
+
+use fen_parser::parse_fen;
+use fen_parser::test_support::{corpus_lines, load_corpus, mixed_corpus, MIXED_CORPUS};
+
+/* Test que le corpus compilé est celui de `benches/data/mixed.fen`, 400 lignes */
+#[test]
+fn test_mixed_corpus_matches_file() {
+    let loaded = load_corpus("benches/data/mixed.fen").unwrap();
+    assert_eq!(loaded.len(), 400);
+    assert_eq!(loaded, corpus_lines(MIXED_CORPUS));
+}
+
+/* Test que `mixed_corpus` reprend le corpus en boucle, avec 60 FEN invalides sur 400 */
+#[test]
+fn test_mixed_corpus_cycles() {
+    let corpus = mixed_corpus(10_000);
+    assert_eq!(corpus.len(), 10_000);
+    assert_eq!(corpus[0], corpus[400]);
+
+    let failed = corpus[..400]
+        .iter()
+        .filter(|fen| parse_fen(fen).is_err())
+        .count();
+    assert_eq!(failed, 60);
+}
+
+/* Test que les lignes vides et les commentaires `#` sont sautés, les autres rognées */
+#[test]
+fn test_corpus_lines_skips_comments() {
+    let text = "# en-tête\n\n  8/8/8/8/8/8/8/8 w - - 0 1  \n# fin\n";
+    assert_eq!(corpus_lines(text), ["8/8/8/8/8/8/8/8 w - - 0 1"]);
+}
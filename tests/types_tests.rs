@@ -0,0 +1,536 @@
+/* Tests des types de base : cases, ensembles de cases, coups et droits de roque.
+
+Ces tests vérifient la conversion entre les noms algébriques et les indices internes, les ordres de parcours et la géométrie des cases (couleur, distances, directions, alignements), l'algèbre des ensembles de cases, le découpage du plateau en rangées, colonnes et diagonales, les cases liées à la prise en passant et l'écriture et la lecture des droits de roque seuls. */
+
+use fen_parser::types::between;
+use fen_parser::{
+    CastlingRights, ChessPosition, Color, Direction, FenErrorKind, FenField, File, Move, Piece,
+    PieceKind, Rank, Square, SquareColor, SquareOrder, SquareSet,
+};
+
+fn square(name: &str) -> Square {
+    Square::from_name(name).unwrap()
+}
+
+/* Test la correspondance entre noms de cases et indices */
+#[test]
+fn test_square_names_and_indices() {
+    let a1 = Square::from_name("a1").unwrap();
+    let h8 = Square::from_name("h8").unwrap();
+    let e4 = Square::new(File::E, Rank::Fourth);
+
+    assert_eq!(a1.index(), 0);
+    assert_eq!(h8.index(), 63);
+    assert_eq!(Square::from_name("e4"), Some(e4));
+    assert_eq!(e4.to_string(), "e4");
+    assert_eq!(Square::from_index(64), None);
+
+    for name in ["", "e", "i1", "a9", "e44", "é4"] {
+        assert_eq!(
+            Square::from_name(name),
+            None,
+            "{:?} should be rejected",
+            name
+        );
+    }
+}
+
+/* Test la couleur des cases sur les 64 cases, face à un plateau de référence (rangée 8 en
+ * tête, `L` pour une case blanche) */
+#[test]
+fn test_square_color() {
+    let reference = [
+        "LDLDLDLD", "DLDLDLDL", "LDLDLDLD", "DLDLDLDL", "LDLDLDLD", "DLDLDLDL", "LDLDLDLD",
+        "DLDLDLDL",
+    ];
+    for (row, line) in reference.iter().enumerate() {
+        for (file, cell) in line.chars().enumerate() {
+            let square = Square::from_coords(file as u8, 7 - row as u8).unwrap();
+            let light = cell == 'L';
+            assert_eq!(square.is_light(), light, "{square}");
+            assert_eq!(square.is_dark(), !light, "{square}");
+            assert_eq!(
+                square.color_complex(),
+                if light {
+                    SquareColor::Light
+                } else {
+                    SquareColor::Dark
+                }
+            );
+            assert_eq!(SquareSet::LIGHT_SQUARES.contains(square), light, "{square}");
+            assert_eq!(SquareSet::DARK_SQUARES.contains(square), !light, "{square}");
+        }
+    }
+    assert!(square("a1").is_dark() && square("h1").is_light());
+    assert_eq!(
+        SquareSet::LIGHT_SQUARES | SquareSet::DARK_SQUARES,
+        SquareSet::full()
+    );
+}
+
+/* Test la lecture et l'écriture des coups UCI */
+#[test]
+fn test_move_uci_round_trip() {
+    let mv = Move::from_uci("e7e8q").unwrap();
+
+    assert_eq!(mv.from, Square::from_name("e7").unwrap());
+    assert_eq!(mv.to, Square::from_name("e8").unwrap());
+    assert_eq!(mv.promotion, Some(PieceKind::Queen));
+    assert_eq!(mv.to_string(), "e7e8q");
+    assert_eq!(Move::from_uci("g1f3").unwrap().to_string(), "g1f3");
+
+    for uci in ["", "e2", "e2e", "e2e9", "e7e8k", "e7e8qq"] {
+        assert_eq!(Move::from_uci(uci), None, "{:?} should be rejected", uci);
+    }
+}
+
+/* Test la conversion d'une lettre FEN en pièce */
+#[test]
+fn test_piece_from_fen_char() {
+    for c in "KQRBNPkqrbnp".chars() {
+        assert_eq!(Piece::from_fen_char(c).map(Piece::to_fen_char), Some(c));
+    }
+    assert_eq!(Piece::from_fen_char('x'), None);
+    assert_eq!(Piece::from_fen_char('é'), None);
+}
+
+/* Test des identités de l'algèbre des ensembles et de l'ordre d'itération */
+#[test]
+fn test_square_set_algebra_and_order() {
+    let rank = SquareSet::rank(Rank::Fourth);
+    let file = SquareSet::file(File::E);
+    let sets = [
+        SquareSet::empty(),
+        SquareSet::full(),
+        rank,
+        file,
+        rank | file,
+        SquareSet(0x8000_0000_0000_0001),
+    ];
+    for a in sets {
+        assert_eq!(a | SquareSet::empty(), a);
+        assert_eq!(a & SquareSet::full(), a);
+        assert_eq!(a & !a, SquareSet::empty());
+        assert_eq!(a | !a, SquareSet::full());
+        assert_eq!(!!a, a);
+        assert_eq!(a.len() + (!a).len(), 64);
+        for b in sets {
+            assert_eq!(!(a | b), !a & !b);
+            assert_eq!(!(a & b), !a | !b);
+            assert_eq!(a & b, b & a);
+        }
+    }
+    assert_eq!(rank & file, SquareSet::from_iter([square("e4")]));
+    assert_eq!((rank | file).len(), 15);
+
+    let mut set = SquareSet::empty();
+    assert!(set.insert(square("h8")));
+    assert!(set.insert(square("a1")));
+    assert!(set.insert(square("e4")));
+    assert!(!set.insert(square("e4")));
+    let names: Vec<String> = set.iter().map(|square| square.to_string()).collect();
+    assert_eq!(names, ["a1", "e4", "h8"]);
+    assert!(set.remove(square("e4")));
+    assert!(!set.remove(square("e4")));
+    assert!(!set.contains(square("e4")) && set.contains(square("a1")));
+    assert_eq!(set.iter().len(), 2);
+    assert!(SquareSet::empty().is_empty());
+    assert_eq!(SquareSet::full().iter().count(), 64);
+}
+
+/* Test du diagramme de débogage et des ensembles rendus par la position */
+#[test]
+fn test_square_set_display_and_position_sets() {
+    let set = SquareSet::from_iter([square("a8"), square("h1"), square("e4")]);
+    assert_eq!(
+        set.to_string(),
+        "x . . . . . . .\n\
+         . . . . . . . .\n\
+         . . . . . . . .\n\
+         . . . . . . . .\n\
+         . . . . x . . .\n\
+         . . . . . . . .\n\
+         . . . . . . . .\n\
+         . . . . . . . x\n"
+    );
+
+    let start = ChessPosition::default();
+    let occupied = start.occupied();
+    assert_eq!(occupied.len(), 32);
+    assert_eq!(
+        occupied,
+        SquareSet::rank(Rank::First)
+            | SquareSet::rank(Rank::Second)
+            | SquareSet::rank(Rank::Seventh)
+            | SquareSet::rank(Rank::Eighth)
+    );
+}
+
+/* Test la position miroir et la détection des positions symétriques */
+#[test]
+fn test_color_symmetry() {
+    let start = ChessPosition::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+        .unwrap();
+    assert!(start.is_color_symmetric());
+    assert!(start.board_symmetric());
+
+    let e4_e5 =
+        ChessPosition::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2")
+            .unwrap();
+    assert!(!e4_e5.is_color_symmetric());
+    assert!(e4_e5.board_symmetric());
+
+    let e4_c5 =
+        ChessPosition::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2")
+            .unwrap();
+    assert!(!e4_c5.is_color_symmetric());
+    assert!(!e4_c5.board_symmetric());
+
+    let flipped = e4_c5.color_flipped();
+    assert_eq!(
+        flipped.to_fen(),
+        "rnbqkbnr/pppp1ppp/8/4p3/2P5/8/PP1PPPPP/RNBQKBNR b KQkq c3 0 2"
+    );
+    assert!(flipped.color_flipped() == e4_c5);
+}
+
+/* Test les distances entre cases, jusqu'aux coins opposés */
+#[test]
+fn test_square_distances() {
+    for (from, to, file, rank, chebyshev, manhattan) in [
+        ("e4", "e4", 0, 0, 0, 0),
+        ("e4", "f5", 1, 1, 1, 2),
+        ("g1", "e4", 2, 3, 3, 5),
+        ("b2", "b7", 0, 5, 5, 5),
+        ("a1", "h1", 7, 0, 7, 7),
+        ("a1", "h8", 7, 7, 7, 14),
+        ("h1", "a8", 7, 7, 7, 14),
+    ] {
+        let (from, to) = (square(from), square(to));
+        assert_eq!(from.file_distance(to), file);
+        assert_eq!(from.rank_distance(to), rank);
+        assert_eq!(from.chebyshev_distance(to), chebyshev);
+        assert_eq!(from.manhattan_distance(to), manhattan);
+        assert_eq!(to.manhattan_distance(from), manhattan, "symmetry");
+    }
+
+    let squares: Vec<Square> = (0..64).filter_map(Square::from_index).collect();
+    let max = |metric: fn(Square, Square) -> u8| {
+        squares
+            .iter()
+            .flat_map(|&a| squares.iter().map(move |&b| metric(a, b)))
+            .max()
+    };
+    assert_eq!(max(Square::chebyshev_distance), Some(7));
+    assert_eq!(max(Square::manhattan_distance), Some(14));
+}
+
+/* Test la direction entre cases alignées, et son absence ailleurs */
+#[test]
+fn test_square_direction() {
+    let e4 = square("e4");
+    assert_eq!(e4.direction_to(square("e8")), Some(Direction::North));
+    assert_eq!(e4.direction_to(square("h7")), Some(Direction::NorthEast));
+    assert_eq!(e4.direction_to(square("a4")), Some(Direction::West));
+    assert_eq!(e4.direction_to(square("b1")), Some(Direction::SouthWest));
+    assert_eq!(e4.direction_to(square("h1")), Some(Direction::SouthEast));
+    assert_eq!(e4.direction_to(square("e4")), None);
+    assert_eq!(e4.direction_to(square("f6")), None);
+    assert_eq!(
+        square("a1").direction_to(square("h8")),
+        Some(Direction::NorthEast)
+    );
+
+    for direction in Direction::ALL {
+        let next = e4.step(direction).unwrap();
+        assert_eq!(e4.direction_to(next), Some(direction));
+        assert_eq!(e4.chebyshev_distance(next), 1);
+    }
+    assert_eq!(square("h8").step(Direction::North), None);
+    assert_eq!(square("a1").step(Direction::SouthWest), None);
+}
+
+/* Test l'alignement de deux cases sur les quatre lignes possibles */
+#[test]
+fn test_square_alignment() {
+    let d4 = square("d4");
+    assert!(d4.same_rank(square("h4")) && !d4.same_rank(square("d5")));
+    assert!(d4.same_file(square("d8")) && !d4.same_file(square("e4")));
+    assert!(d4.same_diagonal(square("a1")) && d4.same_diagonal(square("h8")));
+    assert!(!d4.same_diagonal(square("a7")));
+    assert!(d4.same_antidiagonal(square("a7")) && d4.same_antidiagonal(square("g1")));
+    assert!(!d4.same_antidiagonal(square("h8")));
+    assert!(!square("h1").same_diagonal(square("a2")));
+}
+
+/* Test les cases entre deux cases, dans les quatre directions et hors alignement */
+#[test]
+fn test_between() {
+    let set = |names: &[&str]| {
+        names
+            .iter()
+            .map(|&name| square(name))
+            .collect::<SquareSet>()
+    };
+    assert_eq!(between(square("a1"), square("a4")), set(&["a2", "a3"]));
+    assert_eq!(between(square("h3"), square("e3")), set(&["g3", "f3"]));
+    assert_eq!(
+        between(square("b2"), square("f6")),
+        set(&["c3", "d4", "e5"])
+    );
+    assert_eq!(between(square("h1"), square("a8")).len(), 6);
+    assert_eq!(between(square("e8"), square("b5")), set(&["d7", "c6"]));
+    assert_eq!(between(square("b5"), square("e8")), set(&["d7", "c6"]));
+
+    assert!(between(square("a1"), square("b3")).is_empty());
+    assert!(between(square("e4"), square("e5")).is_empty());
+    assert!(between(square("e4"), square("f5")).is_empty());
+    assert!(between(square("e4"), square("e4")).is_empty());
+}
+
+/* Test les rayons depuis un coin et depuis le centre */
+#[test]
+fn test_square_rays() {
+    let names = |from: &str, direction| -> Vec<String> {
+        square(from)
+            .ray(direction)
+            .map(|square| square.to_string())
+            .collect()
+    };
+    assert_eq!(names("a1", Direction::North).len(), 7);
+    assert_eq!(
+        names("a1", Direction::NorthEast),
+        ["b2", "c3", "d4", "e5", "f6", "g7", "h8"]
+    );
+    assert!(names("a1", Direction::South).is_empty());
+    assert!(names("a1", Direction::NorthWest).is_empty());
+    assert_eq!(names("d4", Direction::West), ["c4", "b4", "a4"]);
+    assert_eq!(names("d4", Direction::SouthEast), ["e3", "f2", "g1"]);
+    let total: usize = Direction::ALL
+        .into_iter()
+        .map(|direction| square("d4").ray(direction).count())
+        .sum();
+    assert_eq!(total, 27);
+}
+
+/* Test la première pièce rencontrée sur un rayon, amie ou adverse */
+#[test]
+fn test_first_piece_along() {
+    let position = ChessPosition::from_fen("4k3/8/8/8/1b6/8/4R3/4K2R w K - 0 1").unwrap();
+    let e1 = square("e1");
+    let found = position.first_piece_along(e1, Direction::North).unwrap();
+    assert_eq!(found.0, square("e2"));
+    assert_eq!(found.1.kind, PieceKind::Rook);
+    assert_eq!(found.1.color, Color::White);
+
+    let found = position
+        .first_piece_along(e1, Direction::NorthWest)
+        .unwrap();
+    assert_eq!(found.0, square("b4"));
+    assert_eq!(found.1.color, Color::Black);
+
+    assert_eq!(
+        position
+            .first_piece_along(e1, Direction::East)
+            .map(|(square, _)| square),
+        Some(square("h1"))
+    );
+    assert_eq!(position.first_piece_along(e1, Direction::West), None);
+    assert_eq!(position.first_piece_along(e1, Direction::South), None);
+}
+
+/* Test l'aller-retour des seize combinaisons de droits de roque */
+#[test]
+fn test_castling_rights_round_trip() {
+    for mask in 0..16 {
+        let rights = CastlingRights {
+            white_kingside: mask & 1 != 0,
+            white_queenside: mask & 2 != 0,
+            black_kingside: mask & 4 != 0,
+            black_queenside: mask & 8 != 0,
+        };
+        let text = rights.to_string();
+        assert_eq!(text, rights.to_fen());
+        assert_eq!(text.parse::<CastlingRights>(), Ok(rights), "{text}");
+    }
+    assert_eq!(CastlingRights::none().to_string(), "-");
+}
+
+/* Test la forme développée `{:#}` */
+#[test]
+fn test_castling_rights_alternate_display() {
+    let rights: CastlingRights = "KQk".parse().unwrap();
+    assert_eq!(format!("{:#}", rights), "White: O-O, O-O-O; Black: O-O");
+    let rights: CastlingRights = "q".parse().unwrap();
+    assert_eq!(format!("{:#}", rights), "Black: O-O-O");
+    assert_eq!(format!("{:#}", CastlingRights::none()), "none");
+}
+
+/* Test les refus : lettre répétée ou inconnue, `-` mêlé aux lettres, champ vide, texte en trop */
+#[test]
+fn test_castling_rights_parse_errors() {
+    for (text, kind) in [
+        ("KK", FenErrorKind::Duplicate),
+        ("KQx", FenErrorKind::UnexpectedChar),
+        ("KQkq-", FenErrorKind::UnexpectedChar),
+        ("-KQ", FenErrorKind::UnexpectedChar),
+        ("", FenErrorKind::Missing),
+        ("KQ kq", FenErrorKind::UnexpectedChar),
+    ] {
+        let error = text.parse::<CastlingRights>().unwrap_err();
+        assert_eq!(error.field, FenField::CastlingRights, "{text}");
+        assert_eq!(error.kind, kind, "{text}");
+    }
+}
+
+/* Test les premières et dernières cases de chaque ordre de parcours */
+#[test]
+fn test_square_orders() {
+    let names = |order: SquareOrder| -> Vec<String> {
+        Square::iter(order)
+            .map(|square| square.to_string())
+            .collect()
+    };
+    for (order, first, last) in [
+        (SquareOrder::Lerf, ["a1", "b1", "c1"], ["f8", "g8", "h8"]),
+        (SquareOrder::Fen, ["a8", "b8", "c8"], ["f1", "g1", "h1"]),
+        (
+            SquareOrder::FileMajor,
+            ["a1", "a2", "a3"],
+            ["h6", "h7", "h8"],
+        ),
+    ] {
+        let names = names(order);
+        assert_eq!(names[..3], first, "{order:?}");
+        assert_eq!(names[61..], last, "{order:?}");
+    }
+    assert_eq!(names(SquareOrder::Fen)[8], "a7");
+    assert_eq!(names(SquareOrder::FileMajor)[8], "b1");
+    assert!(Square::all().eq(Square::iter(SquareOrder::Lerf)));
+    assert!(Square::all()
+        .enumerate()
+        .all(|(index, square)| square.index() == index));
+}
+
+/* Test que chaque ordre donne les 64 cases, une seule fois chacune */
+#[test]
+fn test_square_orders_are_complete() {
+    for order in [SquareOrder::Lerf, SquareOrder::Fen, SquareOrder::FileMajor] {
+        assert_eq!(Square::iter(order).len(), 64);
+        let set: SquareSet = Square::iter(order).collect();
+        assert_eq!(set.len(), 64, "{order:?}");
+    }
+}
+
+/* Test que les colonnes sont les rangées transposées */
+#[test]
+fn test_files_transpose_ranks() {
+    let position = ChessPosition::from_fen(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    )
+    .unwrap();
+    let ranks: Vec<_> = position.ranks().collect();
+    let files: Vec<_> = position.files().collect();
+    assert_eq!((ranks.len(), files.len()), (8, 8));
+    for file in 0..8 {
+        for rank in 0..8 {
+            assert_eq!(files[file][rank], ranks[rank][file]);
+        }
+    }
+    let square = square("e5");
+    assert_eq!(
+        files[square.file().index()][square.rank().index()],
+        position.piece_at(square)
+    );
+}
+
+/* Test les diagonales : longueurs, et grande diagonale a1-h8 de la position initiale */
+#[test]
+fn test_diagonals() {
+    let position = ChessPosition::default();
+    let lengths: Vec<usize> = position
+        .diagonals()
+        .map(|diagonal| diagonal.len())
+        .collect();
+    assert_eq!(lengths, [1, 2, 3, 4, 5, 6, 7, 8, 7, 6, 5, 4, 3, 2, 1]);
+    let lengths: Vec<usize> = position
+        .anti_diagonals()
+        .map(|diagonal| diagonal.len())
+        .collect();
+    assert_eq!(lengths, [1, 2, 3, 4, 5, 6, 7, 8, 7, 6, 5, 4, 3, 2, 1]);
+
+    let piece = |color, kind| Some(Piece { color, kind });
+    let long = position.diagonals().nth(7).unwrap();
+    assert_eq!(
+        long,
+        [
+            piece(Color::White, PieceKind::Rook),
+            piece(Color::White, PieceKind::Pawn),
+            None,
+            None,
+            None,
+            None,
+            piece(Color::Black, PieceKind::Pawn),
+            piece(Color::Black, PieceKind::Rook),
+        ]
+    );
+    /* h1-a8 : tour blanche en h1, tour noire en a8 */
+    let anti = position.anti_diagonals().nth(7).unwrap();
+    assert_eq!(anti[0], piece(Color::White, PieceKind::Rook));
+    assert_eq!(anti[7], piece(Color::Black, PieceKind::Rook));
+    /* a8 seule, puis h1 seule */
+    assert_eq!(
+        position.diagonals().next().unwrap(),
+        [piece(Color::Black, PieceKind::Rook)]
+    );
+    assert_eq!(
+        position.diagonals().last().unwrap(),
+        [piece(Color::White, PieceKind::Rook)]
+    );
+}
+
+/* Test le pion pris en passant et les cases d'où la prise peut partir */
+#[test]
+fn test_en_passant_helpers() {
+    let position = ChessPosition::from_fen("4k3/8/8/3pPp2/8/8/8/4K3 w - d6 0 2").unwrap();
+    assert_eq!(position.en_passant_victim(), Some(square("d5")));
+    assert_eq!(
+        position.en_passant_capture_origins(),
+        [square("e5")].into_iter().collect::<SquareSet>()
+    );
+
+    let position = ChessPosition::from_fen("4k3/8/8/8/1pPp4/8/8/4K3 b - c3 0 1").unwrap();
+    assert_eq!(position.en_passant_victim(), Some(square("c4")));
+    assert_eq!(
+        position.en_passant_capture_origins(),
+        [square("b4"), square("d4")]
+            .into_iter()
+            .collect::<SquareSet>()
+    );
+
+    /* Colonnes a et h : une seule case voisine */
+    let position = ChessPosition::from_fen("4k3/8/8/pP6/8/8/8/4K3 w - a6 0 2").unwrap();
+    assert_eq!(position.en_passant_victim(), Some(square("a5")));
+    assert_eq!(
+        position.en_passant_capture_origins(),
+        [square("b5")].into_iter().collect::<SquareSet>()
+    );
+    let position = ChessPosition::from_fen("4k3/8/8/8/6pP/8/8/4K3 b - h3 0 1").unwrap();
+    assert_eq!(position.en_passant_victim(), Some(square("h4")));
+    assert_eq!(
+        position.en_passant_capture_origins(),
+        [square("g4")].into_iter().collect::<SquareSet>()
+    );
+
+    let position = ChessPosition::default();
+    assert_eq!(position.en_passant_victim(), None);
+    assert!(position.en_passant_capture_origins().is_empty());
+}
+
+/* Test que l'indice d'un type de pièce est sa place dans `PieceKind::ALL` */
+#[test]
+fn test_piece_kind_index() {
+    for (index, kind) in PieceKind::ALL.into_iter().enumerate() {
+        assert_eq!(kind.index(), index);
+    }
+}
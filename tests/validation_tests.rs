@@ -0,0 +1,55 @@
+/* Tests de la validation sémantique des positions.
+
+Ces tests vérifient que des FEN syntaxiquement correctes mais impossibles sont refusées, avec toutes leurs incohérences. */
+
+#![cfg(feature = "std")]
+
+use fen_parser::{parse_fen, Color, Square, ValidationError};
+
+/* Test que la position initiale est cohérente */
+#[test]
+fn test_start_position_is_valid() {
+    let position = parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(position.validate(), Ok(()));
+}
+
+/* Test le nombre de rois et les pions sur les rangées extrêmes */
+#[test]
+fn test_kings_and_back_rank_pawns() {
+    let position = parse_fen("P7/8/8/8/8/8/8/3KK3 w - - 0 1").unwrap();
+    assert_eq!(
+        position.validate(),
+        Err(vec![
+            ValidationError::KingCount {
+                color: Color::White,
+                count: 2
+            },
+            ValidationError::KingCount {
+                color: Color::Black,
+                count: 0
+            },
+            ValidationError::PawnOnBackRank {
+                color: Color::White,
+                square: Square::from_name("a8").unwrap()
+            },
+        ])
+    );
+}
+
+/* Test la cohérence des droits de roque et de la case de prise en passant */
+#[test]
+fn test_castling_and_en_passant_consistency() {
+    let position = parse_fen("4k3/8/8/8/8/8/8/4K3 w K e3 0 1").unwrap();
+    let errors = position.validate().unwrap_err();
+    let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "castling right 'K' requires the king on e1 and a rook on h1",
+            "en passant square e3 does not match a pawn that just advanced two squares",
+        ]
+    );
+
+    let position = parse_fen("4k3/8/8/3pP3/8/8/8/4K2R w K d6 0 2").unwrap();
+    assert_eq!(position.validate(), Ok(()));
+}